@@ -27,9 +27,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 280);
-    assert_eq!(part2(input), 1797);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 280);
+        assert_eq!(part2(input), 1797);
+    }
 }
@@ -42,9 +42,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1588178);
-    assert_eq!(part2(input), 3783758);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1588178);
+        assert_eq!(part2(input), 3783758);
+    }
 }
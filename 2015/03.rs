@@ -56,9 +56,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 2081);
-    assert_eq!(part2(input), 2341);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 2081);
+        assert_eq!(part2(input), 2341);
+    }
 }
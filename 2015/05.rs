@@ -37,9 +37,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 255);
-    assert_eq!(part2(input), 55);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 255);
+        assert_eq!(part2(input), 55);
+    }
 }
@@ -83,9 +83,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 569999);
-    assert_eq!(part2(input), 17836115);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 569999);
+        assert_eq!(part2(input), 17836115);
+    }
 }
@@ -39,9 +39,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1333);
-    assert_eq!(part2(input), 2046);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1333);
+        assert_eq!(part2(input), 2046);
+    }
 }
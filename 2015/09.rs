@@ -92,9 +92,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 207);
-    assert_eq!(part2(input), 804);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 207);
+        assert_eq!(part2(input), 804);
+    }
 }
@@ -64,9 +64,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), String::from("hxbxxyzz"));
-    assert_eq!(part2(input), String::from("hxcaabcc"));
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), String::from("hxbxxyzz"));
+        assert_eq!(part2(input), String::from("hxcaabcc"));
+    }
 }
@@ -66,33 +66,38 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 111754);
-    assert_eq!(part2(input), 65402);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 111754);
+        assert_eq!(part2(input), 65402);
+    }
 }
 
-#[test]
-fn example1() {
-    let input = "[1,2,3]";
-    assert_eq!(part2(input), 6);
+advent::example! {
+    fn example1() {
+        let input = "[1,2,3]";
+        assert_eq!(part2(input), 6);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = r#"[1,{"c":"red","b":2},3]"#;
-    assert_eq!(part2(input), 4);
+advent::example! {
+    fn example2() {
+        let input = r#"[1,{"c":"red","b":2},3]"#;
+        assert_eq!(part2(input), 4);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = r#"{"d":"red","e":[1,2,3,4],"f":5}"#;
-    assert_eq!(part2(input), 0);
+advent::example! {
+    fn example3() {
+        let input = r#"{"d":"red","e":[1,2,3,4],"f":5}"#;
+        assert_eq!(part2(input), 0);
+    }
 }
 
-#[test]
-fn example4() {
-    let input = r#"[1,"red",5]"#;
-    assert_eq!(part2(input), 6);
+advent::example! {
+    fn example4() {
+        let input = r#"[1,"red",5]"#;
+        assert_eq!(part2(input), 6);
+    }
 }
\ No newline at end of file
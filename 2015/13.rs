@@ -102,16 +102,17 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 664);
-    assert_eq!(part2(input), 640);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 664);
+        assert_eq!(part2(input), 640);
+    }
 }
 
-#[test]
-fn example1() {
-    let input = "Alice would gain 54 happiness units by sitting next to Bob.
+advent::example! {
+    fn example1() {
+        let input = "Alice would gain 54 happiness units by sitting next to Bob.
 Alice would lose 79 happiness units by sitting next to Carol.
 Alice would lose 2 happiness units by sitting next to David.
 Bob would gain 83 happiness units by sitting next to Alice.
@@ -124,5 +125,6 @@ David would gain 46 happiness units by sitting next to Alice.
 David would lose 7 happiness units by sitting next to Bob.
 David would gain 41 happiness units by sitting next to Carol.
 ";
-    assert_eq!(part1(parse_input(input)), 330);
+        assert_eq!(part1(parse_input(input)), 330);
+    }
 }
\ No newline at end of file
@@ -55,9 +55,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 2640);
-    assert_eq!(part2(input), 1102);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 2640);
+        assert_eq!(part2(input), 1102);
+    }
 }
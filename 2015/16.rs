@@ -72,9 +72,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 40);
-    assert_eq!(part2(input), 241);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 40);
+        assert_eq!(part2(input), 241);
+    }
 }
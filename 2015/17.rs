@@ -39,9 +39,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 1638);
-    assert_eq!(part2(input), 17);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 1638);
+        assert_eq!(part2(input), 17);
+    }
 }
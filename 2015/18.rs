@@ -78,20 +78,22 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    // assert_eq!(part1(input), 1061);
-    assert_eq!(part2(input), 1006);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        // assert_eq!(part1(input), 1061);
+        assert_eq!(part2(input), 1006);
+    }
 }
 
-#[test]
-fn example1() {
-    let input = ".#.#.#
+advent::example! {
+    fn example1() {
+        let input = ".#.#.#
 ...##.
 #....#
 ..#...
 #.#..#
 ####..";
-    assert_eq!(part1(input), 4);
+        assert_eq!(part1(input), 4);
+    }
 }
\ No newline at end of file
@@ -80,15 +80,17 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 776160);
-    assert_eq!(part2(input), 786240);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 776160);
+        assert_eq!(part2(input), 786240);
+    }
 }
 
-#[test]
-fn example1() {
-    let input = "210";
-    assert_eq!(part1(parse_input(input)), 12);
+advent::example! {
+    fn example1() {
+        let input = "210";
+        assert_eq!(part1(parse_input(input)), 12);
+    }
 }
\ No newline at end of file
@@ -201,9 +201,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1824);
-    assert_eq!(part2(input), 1937);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1824);
+        assert_eq!(part2(input), 1937);
+    }
 }
@@ -86,9 +86,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 226);
-    assert_eq!(part2(input), 79);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 226);
+        assert_eq!(part2(input), 79);
+    }
 }
@@ -83,9 +83,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), "92435".to_string());
-    assert_eq!(part2(input), "C1A88".to_string());
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), "92435".to_string());
+        assert_eq!(part2(input), "C1A88".to_string());
+    }
 }
@@ -36,9 +36,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 1032);
-    assert_eq!(part2(input), 1838);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 1032);
+        assert_eq!(part2(input), 1838);
+    }
 }
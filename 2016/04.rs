@@ -79,9 +79,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 158835);
-    assert_eq!(part2(input), 993);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 158835);
+        assert_eq!(part2(input), 993);
+    }
 }
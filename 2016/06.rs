@@ -44,9 +44,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), "asvcbhvg".to_string());
-    assert_eq!(part2(input), "odqnikqv".to_string());
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), "asvcbhvg".to_string());
+        assert_eq!(part2(input), "odqnikqv".to_string());
+    }
 }
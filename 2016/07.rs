@@ -57,9 +57,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 118);
-    assert_eq!(part2(input), 260);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 118);
+        assert_eq!(part2(input), 260);
+    }
 }
@@ -1,58 +1,33 @@
 use advent::prelude::*;
+use utilities::Screen;
 
-const WIDTH: usize = 50;
-const HEIGHT: usize = 6;
-fn parse_input(input: &str) -> [bool; 300] {
-    let mut screen = [false; WIDTH * HEIGHT];
-    for instruction in input.lines() {
-        let (n1, n2) = instruction.get_numbers().collect_tuple().unwrap();
-        let (first, rest) = instruction.split_once(' ').unwrap();
-        if first == "rect" {
-            for y in 0..n2 {
-                for x in 0..n1 {
-                    screen[y * WIDTH + x] = true;
-                }
-            }
+fn parse_input(input: &str, width: usize, height: usize) -> Screen {
+    let mut screen = Screen::new(width, height);
+    for line in input.lines() {
+        let (a, b) = line.get_numbers().collect_tuple().unwrap();
+        if line.starts_with("rect") {
+            screen.rect(a, b);
+        } else if line.starts_with("rotate row") {
+            screen.rotate_row(a, b);
+        } else if line.starts_with("rotate column") {
+            screen.rotate_column(a, b);
         } else {
-            let offset = n2 as isize;
-            let (next, _) = rest.split_once(' ').unwrap();
-            match next {
-                "column" => {
-                    let col: Vec<bool> = (0..HEIGHT).into_iter()
-                        .map(|y| screen[y * WIDTH + n1])
-                        .collect();
-                    for y in 0..HEIGHT as isize {
-                        let y_offset = (y + offset).rem_euclid(HEIGHT as isize) as usize;
-                        screen[y_offset * WIDTH + n1] = col[y as usize];
-                    }
-                },
-                "row" => {
-                    let row: Vec<bool> = (0..WIDTH).into_iter()
-                        .map(|x| screen[n1 * WIDTH + x])
-                        .collect();
-                    for x in 0..WIDTH as isize {
-                        let x_offset = (x + offset).rem_euclid(WIDTH as isize) as usize;
-                        screen[n1 * WIDTH + x_offset] = row[x as usize];
-                    }
-                },
-                x => panic!("invalid command: {x}"),
-            }
+            panic!("invalid command: {line}");
         }
     }
     screen
 }
 
-fn default_input() -> [bool; 300] {
-    parse_input(include_input!(2016 / 08))
+fn default_input() -> Screen {
+    parse_input(include_input!(2016 / 08), 50, 6)
 }
 
-fn part1(screen: [bool; 300]) -> usize {
-    screen.into_iter().filter(|light| *light).count()
+fn part1(screen: Screen) -> usize {
+    screen.lit()
 }
 
-fn part2(screen: [bool; 300]) -> String {
-    let screen: Vec<bool> = screen.to_vec();
-    ocr((&screen, WIDTH)).unwrap()
+fn part2(screen: Screen) -> String {
+    screen.decode().unwrap()
 }
 
 fn main() {
@@ -60,9 +35,26 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 123);
-    assert_eq!(part2(input), "AFBUPZBJPS".to_string());
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
+rect 3x2
+rotate column x=1 by 1
+rotate row y=0 by 4
+rotate column x=1 by 1",
+            7,
+            3,
+        );
+        assert_eq!(input.render('#', '.'), ".#..#.#\n#.#....\n.#.....\n");
+        assert_eq!(part1(input), 6);
+    }
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 123);
+        assert_eq!(part2(input), "AFBUPZBJPS".to_string());
+    }
 }
@@ -48,9 +48,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 110346);
-    assert_eq!(part2(input), 10774309173);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 110346);
+        assert_eq!(part2(input), 10774309173);
+    }
 }
@@ -117,9 +117,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 101);
-    assert_eq!(part2(input), 37789);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 101);
+        assert_eq!(part2(input), 37789);
+    }
 }
@@ -33,9 +33,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1182);
-    assert_eq!(part2(input), 1152);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1182);
+        assert_eq!(part2(input), 1152);
+    }
 }
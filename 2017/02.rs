@@ -44,29 +44,32 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "5 1 9 5
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "5 1 9 5
 7 5 3
 2 4 6 8",
-    );
-    assert_eq!(part1(input), 18)
+        );
+        assert_eq!(part1(input), 18)
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "5 9 2 8
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "5 9 2 8
 9 4 7 3
 3 8 6 5",
-    );
-    assert_eq!(part2(input), 9)
+        );
+        assert_eq!(part2(input), 9)
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 45972);
-    assert_eq!(part2(input), 326);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 45972);
+        assert_eq!(part2(input), 326);
+    }
 }
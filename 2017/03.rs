@@ -63,9 +63,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 552);
-    assert_eq!(part2(input), 330785);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 552);
+        assert_eq!(part2(input), 330785);
+    }
 }
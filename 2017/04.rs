@@ -47,9 +47,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 455);
-    assert_eq!(part2(input), 186);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 455);
+        assert_eq!(part2(input), 186);
+    }
 }
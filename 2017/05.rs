@@ -35,9 +35,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 373160);
-    assert_eq!(part2(input), 26395586);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 373160);
+        assert_eq!(part2(input), 26395586);
+    }
 }
@@ -54,9 +54,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 12841);
-    assert_eq!(part2(input), 8038);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 12841);
+        assert_eq!(part2(input), 8038);
+    }
 }
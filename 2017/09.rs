@@ -42,9 +42,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 9251);
-    assert_eq!(part2(input), 4322);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 9251);
+        assert_eq!(part2(input), 4322);
+    }
 }
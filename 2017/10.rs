@@ -61,9 +61,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 23874);
-    assert_eq!(part2(input), String::from("e1a65bfb5a5ce396025fab5528c25a87"));
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 23874);
+        assert_eq!(part2(input), String::from("e1a65bfb5a5ce396025fab5528c25a87"));
+    }
 }
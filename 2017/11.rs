@@ -73,9 +73,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 747);
-    assert_eq!(part2(input), 1544);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 747);
+        assert_eq!(part2(input), 1544);
+    }
 }
@@ -58,9 +58,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 115);
-    assert_eq!(part2(input), 221);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 115);
+        assert_eq!(part2(input), 221);
+    }
 }
@@ -45,9 +45,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1528);
-    assert_eq!(part2(input), 3896406);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1528);
+        assert_eq!(part2(input), 3896406);
+    }
 }
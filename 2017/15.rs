@@ -59,9 +59,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 594);
-    assert_eq!(part2(input), 328);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 594);
+        assert_eq!(part2(input), 328);
+    }
 }
@@ -44,9 +44,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1547);
-    assert_eq!(part2(input), 31154878);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1547);
+        assert_eq!(part2(input), 31154878);
+    }
 }
@@ -1,186 +1,112 @@
 use advent::prelude::*;
+use register_machine::{run_pair, Control, Cpu, Exit, Isa, Machine, Operand, Reg};
 
-use crate::CommandType::{ADD, JGZ, MOD, MUL, RCV, SET, SND};
-
-#[derive(Copy, Clone)]
-enum CommandType {
-    SND,
-    SET,
-    ADD,
-    MUL,
-    MOD,
-    RCV,
-    JGZ,
+#[derive(Debug, Clone, Copy)]
+enum Instr {
+    Snd(Operand),
+    Set(Reg, Operand),
+    Add(Reg, Operand),
+    Mul(Reg, Operand),
+    Mod(Reg, Operand),
+    Rcv(Reg),
+    Jgz(Operand, Operand),
 }
 
-#[derive(Copy, Clone)]
-enum Argument {
-    None,
-    Register(char),
-    Value(i64),
+fn parse_input(input: &str) -> Vec<Instr> {
+    input
+        .lines()
+        .map(|line| {
+            let mut it = line.split_whitespace();
+            let name = it.next().unwrap();
+            let mut arg = || it.next().unwrap().parse().unwrap();
+            let mut reg = || match arg() {
+                Operand::Reg(r) => r,
+                Operand::Imm(_) => panic!("expected a register in `{line}`"),
+            };
+            match name {
+                "snd" => Instr::Snd(arg()),
+                "set" => Instr::Set(reg(), arg()),
+                "add" => Instr::Add(reg(), arg()),
+                "mul" => Instr::Mul(reg(), arg()),
+                "mod" => Instr::Mod(reg(), arg()),
+                "rcv" => Instr::Rcv(reg()),
+                "jgz" => Instr::Jgz(arg(), arg()),
+                name => panic!("unknown instruction `{name}`"),
+            }
+        })
+        .collect()
 }
 
-#[derive(Copy, Clone)]
-struct Command {
-    command_type: CommandType,
-    arg1: Argument,
-    arg2: Argument,
+fn default_input() -> Vec<Instr> {
+    parse_input(include_input!(2017 / 18))
 }
 
-struct Program {
-    commands: Vec<Command>,
-    p: i64,
-    index: i64,
-    deadlock: bool,
-    sends: i64,
-    register: HashMap<char, i64>,
-}
+/// The instruction set as first understood, `snd` plays a sound and `rcv`
+/// stops once the last sound played is recovered.
+struct Sound;
 
-impl Program {
-    fn new(
-        commands: Vec<Command>,
-        p: i64,
-    ) -> Self {
-        let mut register = HashMap::new();
-        register.insert('p', p);
-        Self {
-            commands,
-            p,
-            index: 0,
-            deadlock: false,
-            sends: 0,
-            register,
-        }
-    }
+/// The instruction set as actually intended, `snd` and `rcv` send and receive
+/// values to and from the other program.
+struct Duet;
 
-    fn value_of(&self, argument: Argument) -> i64 {
-        match argument {
-            Argument::None => 0,
-            Argument::Register(r) => *self.register.get(&r).unwrap_or(&0i64),
-            Argument::Value(v) => v,
-        }
+/// Executes the instructions common to both instruction sets.
+fn exec(instr: &Instr, cpu: &mut Cpu) -> Control {
+    match *instr {
+        Instr::Set(r, x) => cpu.regs[r] = cpu.get(x),
+        Instr::Add(r, x) => cpu.regs[r] += cpu.get(x),
+        Instr::Mul(r, x) => cpu.regs[r] *= cpu.get(x),
+        Instr::Mod(r, x) => cpu.regs[r] %= cpu.get(x),
+        Instr::Jgz(x, y) if cpu.get(x) > 0 => return Control::Jump(cpu.get(y)),
+        Instr::Jgz(..) => {}
+        Instr::Snd(_) | Instr::Rcv(_) => unreachable!(),
     }
+    Control::Next
+}
 
-    fn execute(&mut self, own: &mut VecDeque<i64>, other: &mut VecDeque<i64>) {
-        if let Some(&command) = self.commands.try_get(self.index) {
-            let arg1 = match command.arg1 {
-                Argument::Register(c) => c
-                _ => panic!("Expected a Register variant"),
-            };
-            if self.deadlock {
-                self.rcv(command, own)
-            } else {
-                match command.command_type {
-                    SND => {
-                        other.push_back(self.value_of(command.arg1));
-                        self.sends += 1;
-                    }
-                    SET => {
-                        self.register.insert(arg1, self.value_of(command.arg2));
-                    }
-                    ADD => {
-                        self.register.insert(
-                            arg1,
-                            self.value_of(command.arg1) + self.value_of(command.arg2),
-                        );
-                    }
-                    MUL => {
-                        self.register.insert(
-                            arg1,
-                            self.value_of(command.arg1) * self.value_of(command.arg2),
-                        );
-                    }
-                    MOD => {
-                        self.register.insert(
-                            arg1,
-                            self.value_of(command.arg1) % self.value_of(command.arg2),
-                        );
-                    }
-                    RCV => {
-                        self.rcv(command, own);
-                        return;
-                    }
-                    JGZ => {
-                        if self.value_of(command.arg1) > 0 {
-                            self.index += self.value_of(command.arg2);
-                            return;
-                        }
-                    }
-                }
-                self.index += 1;
-            }
-        } else {
-            self.deadlock = true;
-        }
-    }
+impl Isa for Sound {
+    type Instr = Instr;
 
-    fn rcv(&mut self, command: Command, own: &mut VecDeque<i64>) {
-        if let Some(value) = own.pop_front() {
-            let arg1 = match command.arg1 {
-                Argument::Register(arg1) => arg1,
-                _ => return
-            };
-            self.register.insert(arg1, value);
-            self.index += 1;
-            self.deadlock = false;
-        } else {
-            self.deadlock = true;
+    fn exec(&self, instr: &Instr, cpu: &mut Cpu) -> Control {
+        match *instr {
+            Instr::Snd(x) => cpu.send(cpu.get(x)),
+            Instr::Rcv(r) if cpu.regs[r] != 0 => return Control::Halt,
+            Instr::Rcv(_) => {}
+            _ => return exec(instr, cpu),
         }
+        Control::Next
     }
 }
 
-fn parse_input(input: &str) -> Vec<Command> {
-    input
-        .lines()
-        .map(|line| {
-            let (command_type, remainder) = line.split_once(' ').unwrap();
-            let command_type = match command_type {
-                "snd" => SND,
-                "set" => SET,
-                "add" => ADD,
-                "mul" => MUL,
-                "mod" => MOD,
-                "rcv" => RCV,
-                "jgz" => JGZ,
-                _ => panic!("Invalid input"),
-            };
-            if let Some((arg1, arg2)) = line.split_once(' ') {
-                let arg1 = Argument::Register(arg1.chars().next().unwrap());
-                if let Ok(value) = arg2.parse::<i64>() {
-                    Command {
-                        command_type,
-                        arg1,
-                        arg2: Argument::Value(value),
-                    }
-                } else {
-                    Command {
-                        command_type,
-                        arg1,
-                        arg2: Argument::Register(arg2.chars().next().unwrap()),
-                    }
-                }
-            } else {
-                let arg1 = Argument::Register(remainder.chars().next().unwrap());
-                Command {
-                    command_type,
-                    arg1,
-                    arg2: Argument::None,
-                }
-            }
-        })
-        .collect()
-}
+impl Isa for Duet {
+    type Instr = Instr;
 
-fn default_input() -> Vec<Command> {
-    parse_input(include_input!(2017 / 18))
+    fn exec(&self, instr: &Instr, cpu: &mut Cpu) -> Control {
+        match *instr {
+            Instr::Snd(x) => cpu.send(cpu.get(x)),
+            Instr::Rcv(r) => match cpu.recv() {
+                Some(v) => cpu.regs[r] = v,
+                None => return Control::Wait,
+            },
+            _ => return exec(instr, cpu),
+        }
+        Control::Next
+    }
 }
 
-fn part1(input: Vec<Command>) -> i64 {
-    todo!("part 1")
+fn part1(input: Vec<Instr>) -> i64 {
+    let mut m = Machine::new(Sound, input);
+    assert_eq!(m.run(), Exit::Halted);
+    m.output().last().unwrap()
 }
 
-fn part2(input: Vec<Command>) -> i64 {
-    todo!("part 2")
+fn part2(input: Vec<Instr>) -> u64 {
+    let [mut a, mut b] = [0, 1].map(|p| {
+        let mut m = Machine::new(Duet, input.clone());
+        m.regs_mut()[Reg::new('p')] = p;
+        m
+    });
+    run_pair(&mut a, &mut b);
+    b.sent()
 }
 
 fn main() {
@@ -188,9 +114,37 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1);
-    assert_eq!(part2(input), 2);
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "\
+set a 1
+add a 2
+mul a a
+mod a 5
+snd a
+set a 0
+rcv a
+jgz a -1
+set a 1
+jgz a -2",
+        );
+        assert_eq!(part1(input), 4);
+    }
+}
+
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "\
+snd 1
+snd 2
+snd p
+rcv a
+rcv b
+rcv c
+rcv d",
+        );
+        assert_eq!(part2(input), 3);
+    }
 }
@@ -125,9 +125,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), String::from("EOCZQMURF"));
-    assert_eq!(part2(input), 16312);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), String::from("EOCZQMURF"));
+        assert_eq!(part2(input), 16312);
+    }
 }
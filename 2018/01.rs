@@ -32,28 +32,31 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    for (input, result) in [("+1 +1 +1", 3), ("+1 +1 -2", 0), ("-1 -2 -3", -6)] {
-        assert_eq!(part1(parse_input(input)), result)
+advent::example! {
+    fn example1() {
+        for (input, result) in [("+1 +1 +1", 3), ("+1 +1 -2", 0), ("-1 -2 -3", -6)] {
+            assert_eq!(part1(parse_input(input)), result)
+        }
     }
 }
 
-#[test]
-fn example2() {
-    for (input, result) in [
-        ("+1 -1", 0),
-        ("+3 +3 +4 -2 -4", 10),
-        ("-6 +3 +8 +5 -6", 5),
-        ("+7 +7 -2 -7 -4", 14),
-    ] {
-        assert_eq!(part2(parse_input(input)), result)
+advent::example! {
+    fn example2() {
+        for (input, result) in [
+            ("+1 -1", 0),
+            ("+3 +3 +4 -2 -4", 10),
+            ("-6 +3 +8 +5 -6", 5),
+            ("+7 +7 -2 -7 -4", 14),
+        ] {
+            assert_eq!(part2(parse_input(input)), result)
+        }
     }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 433);
-    assert_eq!(part2(input), 256);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 433);
+        assert_eq!(part2(input), 256);
+    }
 }
@@ -68,10 +68,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "\
 abcdef
 bababc
 abbcde
@@ -79,14 +79,15 @@ abcccd
 aabcdd
 abcdee
 ababab",
-    );
-    assert_eq!(part1(input), 12)
+        );
+        assert_eq!(part1(input), 12)
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "\
 abcde
 fghij
 klmno
@@ -94,13 +95,15 @@ pqrst
 fguij
 axcye
 wvxyz",
-    );
-    assert_eq!(part2(input), "fgij")
+        );
+        assert_eq!(part2(input), "fgij")
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 7688);
-    assert_eq!(part2(input), "lsrivmotzbdxpkxnaqmuwcchj");
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 7688);
+        assert_eq!(part2(input), "lsrivmotzbdxpkxnaqmuwcchj");
+    }
 }
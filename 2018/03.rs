@@ -57,20 +57,22 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "#1 @ 1,3: 4x4
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "#1 @ 1,3: 4x4
 #2 @ 3,1: 4x4
 #3 @ 5,5: 2x2",
-    );
-    assert_eq!(part1(input.clone()), 4);
-    assert_eq!(part2(input), 3);
+        );
+        assert_eq!(part1(input.clone()), 4);
+        assert_eq!(part2(input), 3);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 110891);
-    assert_eq!(part2(input), 297);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 110891);
+        assert_eq!(part2(input), 297);
+    }
 }
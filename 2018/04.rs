@@ -91,10 +91,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 [1518-11-01 00:00] Guard #10 begins shift
 [1518-11-01 00:05] falls asleep
 [1518-11-01 00:25] wakes up
@@ -112,13 +112,15 @@ fn example() {
 [1518-11-05 00:03] Guard #99 begins shift
 [1518-11-05 00:45] falls asleep
 [1518-11-05 00:55] wakes up",
-    );
-    assert_eq!(part1(input), 240)
+        );
+        assert_eq!(part1(input), 240)
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 19025);
-    assert_eq!(part2(input), 23776);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 19025);
+        assert_eq!(part2(input), 23776);
+    }
 }
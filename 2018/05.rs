@@ -40,16 +40,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = "dabAcCaCBAcCcaDA";
-    assert_eq!(part1(parse_input(input)), 10);
-    assert_eq!(part2(parse_input(input)), 4);
-}
-
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 10972);
-    assert_eq!(part2(input), 5278);
+advent::example! {
+    fn example() {
+        let input = "dabAcCaCBAcCcaDA";
+        assert_eq!(part1(parse_input(input)), 10);
+        assert_eq!(part2(parse_input(input)), 4);
+    }
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 10972);
+        assert_eq!(part2(input), 5278);
+    }
 }
@@ -84,24 +84,26 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 1, 1
 1, 6
 8, 3
 3, 4
 5, 5
 8, 9",
-    );
-    assert_eq!(part1(input.clone()), 17);
-    assert_eq!(part2(input, 32), 16);
+        );
+        assert_eq!(part1(input.clone()), 17);
+        assert_eq!(part2(input, 32), 16);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 4589);
-    assert_eq!(part2(input, 10_000), 40252);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 4589);
+        assert_eq!(part2(input, 10_000), 40252);
+    }
 }
@@ -110,10 +110,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 Step C must be finished before step A can begin.
 Step C must be finished before step F can begin.
 Step A must be finished before step B can begin.
@@ -121,14 +121,16 @@ Step A must be finished before step D can begin.
 Step B must be finished before step E can begin.
 Step D must be finished before step E can begin.
 Step F must be finished before step E can begin.",
-    );
-    assert_eq!(part1(input.clone()), "CABDFE");
-    assert_eq!(part2(input, 2, 0), 15);
+        );
+        assert_eq!(part1(input.clone()), "CABDFE");
+        assert_eq!(part2(input, 2, 0), 15);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), "ABGKCMVWYDEHFOPQUILSTNZRJX");
-    assert_eq!(part2(input, 5, 60), 898);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), "ABGKCMVWYDEHFOPQUILSTNZRJX");
+        assert_eq!(part2(input, 5, 60), 898);
+    }
 }
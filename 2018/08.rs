@@ -58,16 +58,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2");
-    assert_eq!(part1(input.clone()), 138);
-    assert_eq!(part2(input), 66);
+advent::example! {
+    fn example() {
+        let input = parse_input("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2");
+        assert_eq!(part1(input.clone()), 138);
+        assert_eq!(part2(input), 66);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 36027);
-    assert_eq!(part2(input), 23960);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 36027);
+        assert_eq!(part2(input), 23960);
+    }
 }
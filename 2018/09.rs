@@ -43,24 +43,26 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    for (input, exp) in [
-        ("9 players; last marble is worth 25 points", 32),
-        ("10 players; last marble is worth 1618 points", 8317),
-        ("13 players; last marble is worth 7999 points", 146373),
-        ("17 players; last marble is worth 1104 points", 2764),
-        ("21 players; last marble is worth 6111 points", 54718),
-        ("30 players; last marble is worth 5807 points", 37305),
-    ] {
-        let input = parse_input(input);
-        assert_eq!(part1(input), exp);
+advent::example! {
+    fn example() {
+        for (input, exp) in [
+            ("9 players; last marble is worth 25 points", 32),
+            ("10 players; last marble is worth 1618 points", 8317),
+            ("13 players; last marble is worth 7999 points", 146373),
+            ("17 players; last marble is worth 1104 points", 2764),
+            ("21 players; last marble is worth 6111 points", 54718),
+            ("30 players; last marble is worth 5807 points", 37305),
+        ] {
+            let input = parse_input(input);
+            assert_eq!(part1(input), exp);
+        }
     }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 429943);
-    assert_eq!(part2(input), 3615691746);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 429943);
+        assert_eq!(part2(input), 3615691746);
+    }
 }
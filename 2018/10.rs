@@ -1,6 +1,7 @@
 use std::ops::RangeInclusive;
 
 use advent::prelude::*;
+use utilities::Screen;
 
 fn parse_input(input: &str) -> Vec<(Vector2, Vector2)> {
     input
@@ -14,24 +15,8 @@ fn default_input() -> Vec<(Vector2, Vector2)> {
     parse_input(include_input!(2018 / 10))
 }
 
-fn to_string(points: &[(Vector2, Vector2)]) -> String {
-    let min_x = points.iter().map(|(p, _)| p.x).min().unwrap();
-    let min_y = points.iter().map(|(p, _)| p.y).min().unwrap();
-    let max_x = points.iter().map(|(p, _)| p.x).max().unwrap();
-    let max_y = points.iter().map(|(p, _)| p.y).max().unwrap();
-    let set: HashSet<_> = points.iter().map(|(p, _)| *p).collect();
-    let mut s = String::new();
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            if set.contains(&vector!(x, y)) {
-                s.push('█');
-            } else {
-                s.push('░');
-            }
-        }
-        s.push('\n');
-    }
-    s
+fn to_screen(points: &[(Vector2, Vector2)]) -> Screen {
+    Screen::from_points(points.iter().map(|(p, _)| (p.x, p.y)))
 }
 
 fn min_max_ranges<'a>(vectors: impl Iterator<Item=&'a Vector2>) -> (RangeInclusive<i64>, RangeInclusive<i64>) {
@@ -50,26 +35,31 @@ fn min_max_ranges<'a>(vectors: impl Iterator<Item=&'a Vector2>) -> (RangeInclusi
     (min_x..=max_x, min_y..=max_y)
 }
 
-fn solve(points: Vec<(Vector2, Vector2)>) -> (usize, Vec<(Vector2, Vector2)>) {
-    iter::successors(Some(points), |prev| Some(move_points(prev)))
-        .enumerate()
-        .find(|(_, points)| {
-            let positions = points
-                .iter()
-                .map(|(pos, _)| pos);
-            let (_, y_range) = min_max_ranges(positions);
-            y_range.end() - y_range.start() == 9
-        })
-        .unwrap()
+fn height(points: &[(Vector2, Vector2)]) -> i64 {
+    let (_, y_range) = min_max_ranges(points.iter().map(|(pos, _)| pos));
+    y_range.end() - y_range.start()
 }
 
-fn move_points(points: &Vec<(Vector2, Vector2)>) -> Vec<(Vector2, Vector2)> {
+fn solve(mut points: Vec<(Vector2, Vector2)>) -> (usize, Vec<(Vector2, Vector2)>) {
+    // The message appears when the points are closest together, after that
+    // they drift apart again.
+    for secs in 0.. {
+        let next = move_points(&points);
+        if height(&next) >= height(&points) {
+            return (secs, points);
+        }
+        points = next;
+    }
+    unreachable!()
+}
+
+fn move_points(points: &[(Vector2, Vector2)]) -> Vec<(Vector2, Vector2)> {
     points.iter().map(|&(pos, vel)| (pos + vel, vel)).collect()
 }
 
 fn part1(points: Vec<(Vector2, Vector2)>) -> String {
     let (_, points) = solve(points);
-    to_string(&points)
+    to_screen(&points).decode().unwrap()
 }
 
 fn part2(points: Vec<(Vector2, Vector2)>) -> usize {
@@ -82,10 +72,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 position=< 9,  1> velocity=< 0,  2>
 position=< 7,  0> velocity=<-1,  0>
 position=< 3, -2> velocity=<-1,  1>
@@ -117,10 +107,11 @@ position=<-6,  0> velocity=< 2,  0>
 position=< 5,  9> velocity=< 1, -2>
 position=<14,  7> velocity=<-2,  0>
 position=<-3,  6> velocity=< 2, -1>",
-    );
-    assert_eq!(
-        part1(input.clone()),
-        "\
+        );
+        let (_, points) = solve(input.clone());
+        assert_eq!(
+            to_screen(&points).to_string(),
+            "\
 █░░░█░░███
 █░░░█░░░█░
 █░░░█░░░█░
@@ -130,27 +121,15 @@ position=<-3,  6> velocity=< 2, -1>",
 █░░░█░░░█░
 █░░░█░░███
 "
-    );
-    assert_eq!(part2(input), 3);
+        );
+        assert_eq!(part2(input), 3);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(
-        part1(input.clone()),
-        "\
-█░░░░█░░█░░░░░░░██████░░░░██░░░░█░░░░█░░█████░░░░████░░░██████
-█░░░░█░░█░░░░░░░░░░░░█░░░█░░█░░░█░░░█░░░█░░░░█░░█░░░░█░░░░░░░█
-░█░░█░░░█░░░░░░░░░░░░█░░█░░░░█░░█░░█░░░░█░░░░█░░█░░░░░░░░░░░░█
-░█░░█░░░█░░░░░░░░░░░█░░░█░░░░█░░█░█░░░░░█░░░░█░░█░░░░░░░░░░░█░
-░░██░░░░█░░░░░░░░░░█░░░░█░░░░█░░██░░░░░░█████░░░█░░░░░░░░░░█░░
-░░██░░░░█░░░░░░░░░█░░░░░██████░░██░░░░░░█░░░░█░░█░░███░░░░█░░░
-░█░░█░░░█░░░░░░░░█░░░░░░█░░░░█░░█░█░░░░░█░░░░█░░█░░░░█░░░█░░░░
-░█░░█░░░█░░░░░░░█░░░░░░░█░░░░█░░█░░█░░░░█░░░░█░░█░░░░█░░█░░░░░
-█░░░░█░░█░░░░░░░█░░░░░░░█░░░░█░░█░░░█░░░█░░░░█░░█░░░██░░█░░░░░
-█░░░░█░░██████░░██████░░█░░░░█░░█░░░░█░░█████░░░░███░█░░██████
-"
-    );
-    assert_eq!(part2(input), 10656);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), "XLZAKBGZ");
+        assert_eq!(part2(input), 10656);
+    }
 }
@@ -74,20 +74,22 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    for (input, exp) in [
-        (18, String::from("33,45")),
-        (42, String::from("232, 251, 12")),
-    ] {
-        assert_eq!(part1(input), exp);
-        assert_eq!(part2(input), exp);
+advent::example! {
+    fn example() {
+        for (input, exp) in [
+            (18, String::from("33,45")),
+            (42, String::from("232, 251, 12")),
+        ] {
+            assert_eq!(part1(input), exp);
+            assert_eq!(part2(input), exp);
+        }
     }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), String::from("235,48"));
-    assert_eq!(part2(input), String::from("285,113,11"));
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), String::from("235,48"));
+        assert_eq!(part2(input), String::from("285,113,11"));
+    }
 }
@@ -77,10 +77,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 initial state: #..#.#..##......###...###
 
 ...## => #
@@ -97,13 +97,15 @@ initial state: #..#.#..##......###...###
 ###.. => #
 ###.# => #
 ####. => #",
-    );
-    assert_eq!(part1(input), 325);
+        );
+        assert_eq!(part1(input), 325);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1_733);
-    assert_eq!(part2(input), 1_000_000_000_508);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1_733);
+        assert_eq!(part2(input), 1_000_000_000_508);
+    }
 }
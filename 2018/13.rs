@@ -126,24 +126,25 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        r"/->-\
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            r"/->-\
 |   |  /----\
 | /-+--+-\  |
 | | |  | v  |
 \-+-/  \-+--/
   \------/
 ",
-    );
-    assert_eq!(part1(input), vector![7, 3]);
+        );
+        assert_eq!(part1(input), vector![7, 3]);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        r"/>-<\
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            r"/>-<\
 |   |
 | /<+-\
 | | | v
@@ -151,13 +152,15 @@ fn example2() {
   |   ^
   \<->/
 ",
-    );
-    assert_eq!(part2(input), vector![6, 4]);
+        );
+        assert_eq!(part2(input), vector![6, 4]);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), vector![118, 66]);
-    assert_eq!(part2(input), vector![70, 129]);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), vector![118, 66]);
+        assert_eq!(part2(input), vector![70, 129]);
+    }
 }
@@ -54,16 +54,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    assert_eq!(part1("5"), 124515891);
-    assert_eq!(part1("18"), 9251071085);
-    assert_eq!(part1("2018"), 5941429882);
+advent::example! {
+    fn example() {
+        assert_eq!(part1("5"), 124515891);
+        assert_eq!(part1("18"), 9251071085);
+        assert_eq!(part1("2018"), 5941429882);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 1342316410);
-    assert_eq!(part2(input), 20235230);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 1342316410);
+        assert_eq!(part2(input), 20235230);
+    }
 }
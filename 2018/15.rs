@@ -204,10 +204,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        r#"
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            r#"
 #######
 #.G...#
 #...EG#
@@ -215,15 +215,16 @@ fn example1() {
 #..G#E#
 #.....#
 #######"#,
-    );
-    assert_eq!(part1(input.clone()), 27730);
-    assert_eq!(part2(input), 4988);
+        );
+        assert_eq!(part1(input.clone()), 27730);
+        assert_eq!(part2(input), 4988);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        r#"
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            r#"
 #######
 #G..#E#
 #E#E.E#
@@ -231,15 +232,16 @@ fn example2() {
 #...#E#
 #...E.#
 #######"#,
-    );
-    assert_eq!(part1(input.clone()), 36334);
-    assert_eq!(part2(input), 29064);
+        );
+        assert_eq!(part1(input.clone()), 36334);
+        assert_eq!(part2(input), 29064);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input(
-        r#"
+advent::example! {
+    fn example3() {
+        let input = parse_input(
+            r#"
 #######
 #E..EG#
 #.#G.E#
@@ -247,30 +249,32 @@ fn example3() {
 #G..#.#
 #..E#.#
 #######"#,
-    );
-    assert_eq!(part1(input.clone()), 39514);
-    assert_eq!(part2(input), 31284)
+        );
+        assert_eq!(part1(input.clone()), 39514);
+        assert_eq!(part2(input), 31284)
+    }
 }
 
-#[test]
-fn example4() {
-    let input = parse_input(
-        r#"#######
+advent::example! {
+    fn example4() {
+        let input = parse_input(
+            r#"#######
 #E.G#.#
 #.#G..#
 #G.#.G#
 #G..#.#
 #...E.#
 #######"#,
-    );
-    assert_eq!(part1(input.clone()), 27755);
-    assert_eq!(part2(input), 3478);
+        );
+        assert_eq!(part1(input.clone()), 27755);
+        assert_eq!(part2(input), 3478);
+    }
 }
 
-#[test]
-fn example5() {
-    let input = parse_input(
-        r#"
+advent::example! {
+    fn example5() {
+        let input = parse_input(
+            r#"
 #######
 #.E...#
 #.#..G#
@@ -278,15 +282,16 @@ fn example5() {
 #E#G#G#
 #...#G#
 #######"#,
-    );
-    assert_eq!(part1(input.clone()), 28944);
-    assert_eq!(part2(input), 6474);
+        );
+        assert_eq!(part1(input.clone()), 28944);
+        assert_eq!(part2(input), 6474);
+    }
 }
 
-#[test]
-fn example6() {
-    let input = parse_input(
-        r#"
+advent::example! {
+    fn example6() {
+        let input = parse_input(
+            r#"
 #########
 #G......#
 #.E.#...#
@@ -296,14 +301,16 @@ fn example6() {
 #.G...G.#
 #.....G.#
 #########"#,
-    );
-    assert_eq!(part1(input.clone()), 18740);
-    assert_eq!(part2(input), 1140);
+        );
+        assert_eq!(part1(input.clone()), 18740);
+        assert_eq!(part2(input), 1140);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 201638);
-    assert_eq!(part2(input), 95764);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 201638);
+        assert_eq!(part2(input), 95764);
+    }
 }
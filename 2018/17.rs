@@ -147,10 +147,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 x=495, y=2..7
 y=7, x=495..501
 x=501, y=3..7
@@ -159,14 +159,16 @@ x=506, y=1..2
 x=498, y=10..13
 x=504, y=10..13
 y=13, x=498..504",
-    );
-    assert_eq!(part1(input.clone()), 57);
-    assert_eq!(part2(input), 29);
+        );
+        assert_eq!(part1(input.clone()), 57);
+        assert_eq!(part2(input), 29);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 36790);
-    assert_eq!(part2(input), 30765);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 36790);
+        assert_eq!(part2(input), 30765);
+    }
 }
@@ -115,10 +115,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input::<10>(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input::<10>(
+            "\
 .#.#...|#.
 .....#|##|
 .|..|...#.
@@ -129,13 +129,15 @@ fn example() {
 ||...#|.#|
 |.||||..|.
 ...#.|..|.",
-    );
-    assert_eq!(part1(input), 1147);
+        );
+        assert_eq!(part1(input), 1147);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 355918);
-    assert_eq!(part2(input), 202806);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 355918);
+        assert_eq!(part2(input), 202806);
+    }
 }
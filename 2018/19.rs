@@ -1,5 +1,5 @@
 use advent::prelude::*;
-use device::{compute, parse_program, Program};
+use device::{parse_program, Device, Halt, Program};
 
 mod device;
 
@@ -7,70 +7,43 @@ fn default_input() -> Program {
     parse_program(include_input!(2018 / 19))
 }
 
-fn sum_of_divisors(n: usize) -> usize {
-    let mut sum = 0;
-    let mut i = 1;
-    while i * i <= n {
-        if n % i == 0 {
-            sum += i;
-            let j = n / i;
-            if j != i {
-                sum += j
-            }
-        }
-        i += 1
-    }
-    sum
-}
-
 fn part1(prog: Program) -> usize {
-    let Program { ip, instrs } = prog;
-    let mut regs = [0; 6];
-    while regs[ip] < instrs.len() {
-        regs = compute(regs, instrs[regs[ip]]);
-        regs[ip] += 1;
-    }
-    regs[0]
+    let mut device = Device::new(prog);
+    assert_eq!(device.run(), Halt::Exited);
+    device.regs()[0]
 }
 
-// Oh no, its too slow. No choice but to deconstruct the program. The important
-// part seems to be the following.
+// Oh no, its too slow. No choice but to deconstruct the program. Running it
+// through `decompile` shows that the important part is the following.
 //
-//   seti 1 . 1    ->    r1 = 1
-//   seti 1 . 3    ->    r3 = 1
-//   mulr 1 3 5    ->    r5 = r1 * r3          r1 = 1
-//   eqrr 5 2 5    ->    r5 = r5 == r2         r3 = 1
-//   addr 5 4 4    ->    ip = r5 + ip          while r1 <= r2 {
-//   addi 4 1 4    ->    ip = ip + 1               while r3 <= r2 {
-//   addr 1 0 0    ->    r0 = r1 + r0                  if r1 * r3 == r2 {
-//   addi 3 1 3    ->    r3 = r3 + 1     ->                  r0 += r1
-//   gtrr 3 2 5    ->    r5 = r3 > r2                  }
-//   addr 4 5 4    ->    ip = ip + r5                  r3 += 1
-//   seti 2 . 4    ->    ip = 2                    }
-//   addi 1 1 1    ->    r1 += 1                   r1 += 1
-//   gtrr 1 2 5    ->    r5 = r1 > r2          }
-//   addr 5 4 4    ->    ip = r5 + ip
-//   seti 1 . 4    ->    ip = 1
+//   L1:
+//   r1 = 1
+//   loop {
+//       r3 = 1
+//       do {
+//           if r1 * r3 == r2 {
+//               r0 += r1
+//           }
+//           r3 += 1
+//       } while r3 <= r2
+//       r1 += 1
+//       if r1 > r2 {
+//           halt
+//       }
+//   }
 //
 // This piece of code of seems to be trying to calculate the sum of factors for
 // the current value in r2, storing the result in r0. The rest of the program
 // seems to be involved with constructing the value used for the above code and
 // involved in part 1.
 //
+// The device recognises the inner loop as a divisor scan and runs it natively,
+// which is fast enough to just run the whole program.
 fn part2(prog: Program) -> usize {
-    let Program { ip, instrs } = prog;
-    let mut regs = [0; 6];
-    regs[0] = 1;
-    while regs[ip] < instrs.len() {
-        if regs[ip] == 2 {
-            // Based on the code deconstruction this will be the value that
-            // we're calculating the sum of factors for.
-            return sum_of_divisors(regs[2]);
-        }
-        regs = compute(regs, instrs[regs[ip]]);
-        regs[ip] += 1;
-    }
-    unreachable!()
+    let mut device = Device::new(prog).with_regs([1, 0, 0, 0, 0, 0]);
+    device.optimize();
+    assert_eq!(device.run(), Halt::Exited);
+    device.regs()[0]
 }
 
 fn main() {
@@ -78,9 +51,81 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1350);
-    assert_eq!(part2(input), 15844608);
+advent::example! {
+    fn example() {
+        let input = parse_program(
+            "\
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5",
+        );
+        let mut device = Device::new(input);
+        device.add_breakpoint(6);
+        device.watch(5);
+        assert_eq!(device.run(), Halt::Breakpoint(6));
+        assert_eq!(device.regs(), [6, 5, 6, 0, 0, 0]);
+        assert_eq!(device.run(), Halt::Watch { reg: 5, old: 0, new: 9 });
+        assert_eq!(device.run(), Halt::Exited);
+        assert_eq!(device.regs(), [7, 5, 6, 0, 0, 9]);
+        assert_eq!(device.executed(), 5);
+    }
+}
+
+advent::example! {
+    fn example_decompile() {
+        let input = parse_program(
+            "\
+#ip 4
+seti 10551264 0 2
+seti 1 0 1
+seti 1 0 3
+mulr 1 3 5
+eqrr 5 2 5
+addr 5 4 4
+addi 4 1 4
+addr 1 0 0
+addi 3 1 3
+gtrr 3 2 5
+addr 4 5 4
+seti 2 0 4
+addi 1 1 1
+gtrr 1 2 5
+addr 5 4 4
+seti 1 0 4
+mulr 4 4 4",
+        );
+        assert_eq!(
+            device::decompile(&input).to_string(),
+            "\
+r2 = 10551264
+r1 = 1
+loop {
+    r3 = 1
+    do {
+        if r1 * r3 == r2 {
+            r0 += r1
+        }
+        r3 += 1
+    } while r3 <= r2
+    r1 += 1
+    if r1 > r2 {
+        halt
+    }
+}
+"
+        );
+    }
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1350);
+        assert_eq!(part2(input), 15844608);
+    }
 }
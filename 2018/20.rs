@@ -81,27 +81,31 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input("^WNE$");
-    assert_eq!(part1(input), 3);
+advent::example! {
+    fn example1() {
+        let input = parse_input("^WNE$");
+        assert_eq!(part1(input), 3);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input("^ENWWW(NEEE|SSE(EE|N))$");
-    assert_eq!(part1(input), 10);
+advent::example! {
+    fn example2() {
+        let input = parse_input("^ENWWW(NEEE|SSE(EE|N))$");
+        assert_eq!(part1(input), 10);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$");
-    assert_eq!(part1(input), 18);
+advent::example! {
+    fn example3() {
+        let input = parse_input("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$");
+        assert_eq!(part1(input), 18);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 4406);
-    assert_eq!(part2(input), 8468);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 4406);
+        assert_eq!(part2(input), 8468);
+    }
 }
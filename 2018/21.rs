@@ -1,5 +1,5 @@
 use advent::prelude::*;
-use device::{compute, parse_program, Program};
+use device::{parse_program, Device, Halt, Op, Program};
 
 mod device;
 
@@ -17,77 +17,69 @@ fn default_input() -> Program {
 // Clearly when r0 is equal to r1 the program will halt, otherwise it jumps back
 // (ip = 5). Thus, setting r0 to the value of r1 at this point would make the
 // program halt.
+fn halt_check(prog: &Program) -> (usize, usize) {
+    prog.instrs
+        .iter()
+        .enumerate()
+        .find_map(|(i, instr)| match *instr {
+            (Op::Eqrr, [0, r, _] | [r, 0, _]) => Some((i, r)),
+            _ => None,
+        })
+        .unwrap()
+}
+
 fn part1(prog: Program) -> usize {
-    let Program { ip, instrs } = prog;
-    let mut regs = [0; 6];
-    while regs[ip] < instrs.len() {
-        if regs[ip] == 28 {
-            return regs[1];
-        }
-        regs = compute(regs, instrs[regs[ip]]);
-        regs[ip] += 1;
-    }
-    unreachable!()
+    let (at, reg) = halt_check(&prog);
+    let mut device = Device::new(prog);
+    device.add_breakpoint(at);
+    assert_eq!(device.run(), Halt::Breakpoint(at));
+    device.regs()[reg]
 }
 
 // For part 2 we can just wait for the last r1 value to occur before the
-// sequence repeats. This is quite slow though, so let's try deconstructing the
-// program.
-//
-//   seti 123 . 1        ->  r1 = 123
-//   bani 1 456 1        ->  r1 = r1 & 456
-//   eqri 1 72 1         ->  r1 = r1 == 72         ->   bitwise verification loop
-//   addr 1 2 2          ->  ip = ip + r1
-//   seti 0 . 2          ->  ip = 0
-//
-//   seti 0 . 1          ->  r1 = 0
-//   bori 1 65536 4      ->  r4 = r1 | 65536
-//   seti 16298264 . 1   ->  r1 = 16298264
+// sequence repeats. Running the program through `decompile` shows why this
+// would be slow.
 //
-//   bani 4 255 5        ->  r5 = r4 & 0xff
-//   addr 1 5 1          ->  r1 = r1 + r5               r1 = f(r1, r4)
-//   bani 1 16777215 1   ->  r1 = r1 & 0xff_ffff        if r4 < 256 {
-//   muli 1 65899 1      ->  r1 = r1 * 65899       ->
-//   bani 1 16777215 1   ->  r1 = r1 & 0xff_ffff        } else {
-//   gtir 256 4 5        ->  r5 = r4 < 256
-//   addr 5 2 2          ->  ip = ip + r5               }
-//   addi 2 1 2          ->  ip = ip + 1
-//   seti 27 . 2         ->  ip = 27
+//   r1 = 123
+//   do {
+//       r1 = r1 & 456 == 72
+//   } while r1 == 0
+//   r1 = 0
+//   loop {
+//       r4 = r1 | 65536
+//       r1 = 16298264
+//       loop {
+//           r1 = (r1 + (r4 & 255) & 16777215) * 65899 & 16777215
+//           if r4 < 256 {
+//               break
+//           }
+//           r5 = 0
+//           while (r5 + 1) * 256 <= r4 {
+//               r5 += 1
+//           }
+//           r4 = r5
+//       }
+//       if r1 == r0 {
+//           halt
+//       }
+//   }
 //
-//   seti 0 . 5          ->  r5 = 0
-//   addi 5 1 3          ->  r3 = r5 + 1
-//   muli 3 256 3        ->  r3 = r5 * 256
-//   gtrr 3 4 3          ->  r3 = r3 > r4
-//   addr 3 2 2          ->  ip = ip + r3          ->   // just division
-//   addi 2 1 2          ->  ip = ip + 1                r4 /= 256
-//   seti 25 . 2         ->  ip = 25
-//   addi 5 1 5          ->  r5 = r5 + 1
-//   seti 17 . 2         ->  ip = 17
-//
-//   setr 5 . 4          ->  r4 = r5
-//   seti 7 . 2          ->  ip = 7
-//   eqrr 1 0 5          ->  r5 = r1 == r0         ->   halt condition
-//   addr 5 2 2          ->  ip = ip + r5
-//   seti 5 . 2          ->  ip = 5
-//
-fn part2(_: Program) -> usize {
+// The innermost loop is just dividing r4 by 256 by counting up, the device
+// recognises it and runs it natively.
+fn part2(prog: Program) -> usize {
+    let (at, reg) = halt_check(&prog);
+    let mut device = Device::new(prog);
+    device.optimize();
+    device.add_breakpoint(at);
     let mut seen = HashSet::new();
-    let mut r1 = 0;
-    let mut prev_r1 = 0;
-
-    let f = |r1, r4| (((r1 + (r4 & 0xff)) & 0xff_ffff) * 65899) & 0xff_ffff;
-
+    let mut prev = 0;
     loop {
-        let mut r4 = r1 | 65536;
-        r1 = f(16298264, r4);
-        while r4 >= 256 {
-            r4 /= 256;
-            r1 = f(r1, r4);
-        }
-        if !seen.insert(r1) {
-            return prev_r1;
+        assert_eq!(device.run(), Halt::Breakpoint(at));
+        let value = device.regs()[reg];
+        if !seen.insert(value) {
+            return prev;
         }
-        prev_r1 = r1;
+        prev = value;
     }
 }
 
@@ -96,9 +88,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3345459);
-    assert_eq!(part2(input), 5857354);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3345459);
+        assert_eq!(part2(input), 5857354);
+    }
 }
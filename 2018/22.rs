@@ -111,16 +111,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = (510, vector![10, 10]);
-    assert_eq!(part1(input), 114);
-    assert_eq!(part2(input), 45);
+advent::example! {
+    fn example() {
+        let input = (510, vector![10, 10]);
+        assert_eq!(part1(input), 114);
+        assert_eq!(part2(input), 45);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 11810);
-    assert_eq!(part2(input), 1015);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 11810);
+        assert_eq!(part2(input), 1015);
+    }
 }
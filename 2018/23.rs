@@ -55,10 +55,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        r"pos=<0,0,0>, r=4
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            r"pos=<0,0,0>, r=4
 pos=<1,0,0>, r=1
 pos=<4,0,0>, r=3
 pos=<0,2,0>, r=1
@@ -67,26 +67,29 @@ pos=<0,0,3>, r=1
 pos=<1,1,1>, r=1
 pos=<1,1,2>, r=1
 pos=<1,3,1>, r=1",
-    );
-    assert_eq!(part1(input), 7);
+        );
+        assert_eq!(part1(input), 7);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        r"pos=<10,12,12>, r=2
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            r"pos=<10,12,12>, r=2
 pos=<12,14,12>, r=2
 pos=<16,12,12>, r=4
 pos=<14,14,14>, r=6
 pos=<50,50,50>, r=200
 pos=<10,10,10>, r=5",
-    );
-    assert_eq!(part2(input), 36);
+        );
+        assert_eq!(part2(input), 36);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 674);
-    assert_eq!(part2(input), 129444177);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 674);
+        assert_eq!(part2(input), 129444177);
+    }
 }
@@ -256,22 +256,24 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("Immune System:
+advent::example! {
+    fn example() {
+        let input = parse_input("Immune System:
 17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
 989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
 
 Infection:
 801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
 4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4");
-    assert_eq!(part1(input.clone()), 5216);
-    assert_eq!(part2(input), 51);
+        assert_eq!(part1(input.clone()), 5216);
+        assert_eq!(part2(input), 51);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 15392);
-    assert_eq!(part2(input), 1092);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 15392);
+        assert_eq!(part2(input), 1092);
+    }
 }
@@ -55,10 +55,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "0,0,0,0
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "0,0,0,0
 3,0,0,0
 0,3,0,0
 0,0,3,0
@@ -66,14 +66,15 @@ fn example1() {
 0,0,0,6
 9,0,0,0
 12,0,0,0",
-    );
-    assert_eq!(part1(input), 2);
+        );
+        assert_eq!(part1(input), 2);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "-1,2,2,0
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "-1,2,2,0
 0,0,2,-2
 0,0,0,-2
 -1,2,0,0
@@ -83,14 +84,15 @@ fn example2() {
 -1,0,-1,0
 0,2,1,-2
 3,0,0,0",
-    );
-    assert_eq!(part1(input), 4);
+        );
+        assert_eq!(part1(input), 4);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input(
-        "1,-1,0,1
+advent::example! {
+    fn example3() {
+        let input = parse_input(
+            "1,-1,0,1
 2,0,-1,0
 3,2,-1,0
 0,0,3,1
@@ -100,14 +102,15 @@ fn example3() {
 2,-2,0,-1
 1,-1,0,-1
 3,2,0,2",
-    );
-    assert_eq!(part1(input), 3);
+        );
+        assert_eq!(part1(input), 3);
+    }
 }
 
-#[test]
-fn example4() {
-    let input = parse_input(
-        "1,-1,-1,-2
+advent::example! {
+    fn example4() {
+        let input = parse_input(
+            "1,-1,-1,-2
 -2,-2,0,1
 0,2,1,3
 -2,3,-2,1
@@ -117,12 +120,14 @@ fn example4() {
 -2,2,3,-1
 1,2,2,0
 -1,-2,0,-2",
-    );
-    assert_eq!(part1(input), 8);
+        );
+        assert_eq!(part1(input), 8);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 396);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 396);
+    }
 }
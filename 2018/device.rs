@@ -1,6 +1,19 @@
 #![allow(dead_code)]
 
+use std::fmt;
+
 use advent::prelude::*;
+use register_machine::Budget;
+
+pub use decompile::{decompile, Cfg, Decompiled};
+pub use idiom::Idiom;
+pub use infer::{infer, DecodeError, Decoder, InferError, Sample};
+
+mod decompile;
+mod idiom;
+mod infer;
+#[cfg(test)]
+mod tests;
 
 pub type Instr = (Op, [usize; 3]);
 
@@ -30,28 +43,53 @@ pub enum Op {
     Eqrr,
 }
 
-fn parse_op(input: &str) -> Op {
-    match input {
-        "addr" => Op::Addr,
-        "addi" => Op::Addi,
-        "mulr" => Op::Mulr,
-        "muli" => Op::Muli,
-        "banr" => Op::Banr,
-        "bani" => Op::Bani,
-        "borr" => Op::Borr,
-        "bori" => Op::Bori,
-        "setr" => Op::Setr,
-        "seti" => Op::Seti,
-        "gtir" => Op::Gtir,
-        "gtri" => Op::Gtri,
-        "gtrr" => Op::Gtrr,
-        "eqir" => Op::Eqir,
-        "eqri" => Op::Eqri,
-        "eqrr" => Op::Eqrr,
-        i => panic!("unexpected instruction `{i}`"),
+impl Op {
+    #[rustfmt::skip]
+    pub const ALL: [Op; 16] = [
+        Op::Addr, Op::Addi,
+        Op::Mulr, Op::Muli,
+        Op::Banr, Op::Bani,
+        Op::Borr, Op::Bori,
+        Op::Setr, Op::Seti,
+        Op::Gtir, Op::Gtri, Op::Gtrr,
+        Op::Eqir, Op::Eqri, Op::Eqrr,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Op::Addr => "addr",
+            Op::Addi => "addi",
+            Op::Mulr => "mulr",
+            Op::Muli => "muli",
+            Op::Banr => "banr",
+            Op::Bani => "bani",
+            Op::Borr => "borr",
+            Op::Bori => "bori",
+            Op::Setr => "setr",
+            Op::Seti => "seti",
+            Op::Gtir => "gtir",
+            Op::Gtri => "gtri",
+            Op::Gtrr => "gtrr",
+            Op::Eqir => "eqir",
+            Op::Eqri => "eqri",
+            Op::Eqrr => "eqrr",
+        }
     }
 }
 
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+fn parse_op(input: &str) -> Op {
+    Op::ALL
+        .into_iter()
+        .find(|op| op.name() == input)
+        .unwrap_or_else(|| panic!("unexpected instruction `{input}`"))
+}
+
 fn parse_instr(input: &str) -> Instr {
     let mut it = input.split_whitespace();
     let op = it.next().map(parse_op).unwrap();
@@ -89,3 +127,179 @@ pub fn compute<const N: usize>(mut regs: [usize; N], instr: Instr) -> [usize; N]
     regs[c] = res;
     regs
 }
+
+/// The reason a [`Device`] stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Halt {
+    /// The instruction pointer moved outside of the program.
+    Exited,
+    /// The instruction budget was exhausted.
+    Budget,
+    /// The instruction pointer reached a breakpoint. The instruction at the
+    /// breakpoint has not been executed yet.
+    Breakpoint(usize),
+    /// A watched register changed value.
+    Watch { reg: usize, old: usize, new: usize },
+}
+
+/// A device running a program with the instruction pointer bound to a
+/// register.
+#[derive(Clone)]
+pub struct Device {
+    program: Program,
+    regs: [usize; 6],
+    budget: Budget,
+    breakpoints: HashSet<usize>,
+    watches: Vec<usize>,
+    paused: bool,
+    idioms: Vec<Option<Idiom>>,
+}
+
+impl Device {
+    pub fn new(program: Program) -> Self {
+        Self {
+            program,
+            regs: [0; 6],
+            budget: Budget::default(),
+            breakpoints: HashSet::new(),
+            watches: Vec::new(),
+            paused: false,
+            idioms: Vec::new(),
+        }
+    }
+
+    /// Sets the initial value of the registers.
+    pub fn with_regs(mut self, regs: [usize; 6]) -> Self {
+        self.regs = regs;
+        self
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn regs(&self) -> [usize; 6] {
+        self.regs
+    }
+
+    pub fn regs_mut(&mut self) -> &mut [usize; 6] {
+        &mut self.regs
+    }
+
+    /// Returns the current value of the instruction pointer.
+    pub fn ip(&self) -> usize {
+        self.regs[self.program.ip]
+    }
+
+    /// Returns how many instructions the device has executed, including the
+    /// iterations of any loops it ran natively.
+    pub fn executed(&self) -> u64 {
+        self.budget.executed()
+    }
+
+    /// Halts the device with [`Halt::Budget`] once it has executed this many
+    /// more instructions, or never if `None`. Loops that would overrun the
+    /// budget are stepped through so that it halts at exactly that point.
+    pub fn set_budget(&mut self, instrs: Option<u64>) {
+        self.budget.limit(instrs);
+    }
+
+    pub fn add_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.insert(ip);
+    }
+
+    pub fn remove_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.remove(&ip);
+    }
+
+    pub fn watch(&mut self, reg: usize) {
+        assert!(reg < self.regs.len(), "register {reg} out of range");
+        if !self.watches.contains(&reg) {
+            self.watches.push(reg);
+        }
+    }
+
+    pub fn unwatch(&mut self, reg: usize) {
+        self.watches.retain(|&r| r != reg);
+    }
+
+    /// Recognises hot loops in the program and runs them natively from now on.
+    /// Loops are still executed one instruction at a time if they contain a
+    /// breakpoint, write to a watched register, or would exceed the budget.
+    pub fn optimize(&mut self) {
+        self.idioms = idiom::find(&self.program);
+    }
+
+    /// Executes a single instruction, ignoring breakpoints.
+    pub fn step(&mut self) -> Option<Halt> {
+        self.paused = false;
+        let ip = self.ip();
+        let Some(&instr) = self.program.instrs.get(ip) else {
+            return Some(Halt::Exited);
+        };
+        if !self.budget.allows(1) {
+            return Some(Halt::Budget);
+        }
+        let old = self.regs;
+        self.regs = compute(self.regs, instr);
+        self.regs[self.program.ip] += 1;
+        self.budget.spend(1);
+        // The instruction pointer always changes so a watch on its register
+        // only reports writes made by the instruction itself.
+        self.watches.iter().find_map(|&reg| {
+            let new = match reg == self.program.ip {
+                true => self.regs[reg] - 1,
+                false => self.regs[reg],
+            };
+            (old[reg] != new).then_some(Halt::Watch {
+                reg,
+                old: old[reg],
+                new,
+            })
+        })
+    }
+
+    /// Runs the device until it halts.
+    ///
+    /// Running again after stopping at a breakpoint continues past it.
+    pub fn run(&mut self) -> Halt {
+        loop {
+            let ip = self.ip();
+            if !self.paused && self.breakpoints.contains(&ip) {
+                self.paused = true;
+                return Halt::Breakpoint(ip);
+            }
+            if let Some(&Some(idiom)) = self.idioms.get(ip) {
+                if self.run_idiom(idiom) {
+                    continue;
+                }
+            }
+            if let Some(halt) = self.step() {
+                return halt;
+            }
+        }
+    }
+
+    /// Runs the loop at the instruction pointer natively, returning whether it
+    /// was possible.
+    fn run_idiom(&mut self, idiom: Idiom) -> bool {
+        let ip = self.ip();
+        let watched = self.watches.iter().fold(0, |acc, r| acc | 1 << r);
+        if watched & (idiom.writes() | 1 << self.program.ip) != 0
+            || (ip..ip + idiom.len()).any(|i| self.breakpoints.contains(&i))
+        {
+            return false;
+        }
+        let mut regs = self.regs;
+        let Some(n) = idiom.apply(&mut regs, self.program.ip) else {
+            return false;
+        };
+        if !self.budget.allows(n) {
+            return false;
+        }
+        self.regs = regs;
+        self.budget.spend(n);
+        self.paused = false;
+        true
+    }
+}
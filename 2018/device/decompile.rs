@@ -0,0 +1,942 @@
+//! Decompile elfcode programs into structured pseudo-code.
+//!
+//! Every write to the instruction pointer register is a jump. Jumps to a
+//! constant address are plain gotos, and adding a register that was just set
+//! by a comparison is a conditional skip over the next instruction. Anything
+//! else is a computed jump which is not followed. The resulting control-flow
+//! graph is then structured into loops and if/else statements using
+//! dominators, falling back to labels and gotos where that doesn't work out.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::fmt::Write as _;
+
+use super::{Instr, Op, Program};
+
+/// A binary operator in an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Mul,
+    And,
+    Or,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    Eq,
+    Ne,
+}
+
+/// An expression over registers and immediate values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Reg(usize),
+    Imm(usize),
+    Bin(BinOp, Box<Expr>, Box<Expr>),
+}
+
+/// How control leaves a basic block. Targets are block indexes, or
+/// [`Cfg::exit`] for leaving the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    Goto(usize),
+    /// Continues at the first block if the condition is true, otherwise at
+    /// the second.
+    Branch(Expr, usize, usize),
+    /// Continues at the instruction given by the expression.
+    Computed(Expr),
+}
+
+/// A straight line sequence of instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    /// The index of the first instruction in the block.
+    pub start: usize,
+    /// The register assignments made by the block, in order. Temporaries that
+    /// are only read once within the block are substituted into their use.
+    pub stmts: Vec<(usize, Expr)>,
+    pub term: Term,
+}
+
+/// The control-flow graph of a program.
+#[derive(Debug, Clone)]
+pub struct Cfg {
+    pub blocks: Vec<Block>,
+    /// The blocks execution can start from, the first is the start of the
+    /// program and the rest are only reachable through computed jumps.
+    pub roots: Vec<usize>,
+}
+
+/// A structured statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    Label(usize),
+    Assign(usize, Expr),
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+    Loop(Vec<Stmt>),
+    While(Expr, Vec<Stmt>),
+    DoWhile(Vec<Stmt>, Expr),
+    Break,
+    Continue,
+    /// A jump to the label at the given instruction.
+    Goto(usize),
+    /// A computed jump to the instruction given by the expression.
+    Jump(Expr),
+    Halt,
+}
+
+/// A decompiled program.
+#[derive(Debug, Clone)]
+pub struct Decompiled {
+    pub stmts: Vec<Stmt>,
+}
+
+/// Decompiles the given program.
+pub fn decompile(program: &Program) -> Decompiled {
+    let cfg = Cfg::new(program);
+    let mut d = Decompiler::new(&cfg);
+    let mut stmts = Vec::new();
+    for &root in &cfg.roots {
+        d.region(&mut stmts, root, None);
+    }
+    let mut labels = HashSet::new();
+    labels.extend(cfg.roots.iter().skip(1).map(|&b| cfg.blocks[b].start));
+    gotos(&stmts, &mut labels);
+    Decompiled {
+        stmts: simplify(stmts, &labels),
+    }
+}
+
+impl BinOp {
+    fn eval(self, a: usize, b: usize) -> usize {
+        match self {
+            BinOp::Add => a.wrapping_add(b),
+            BinOp::Mul => a.wrapping_mul(b),
+            BinOp::And => a & b,
+            BinOp::Or => a | b,
+            BinOp::Gt => (a > b) as usize,
+            BinOp::Lt => (a < b) as usize,
+            BinOp::Ge => (a >= b) as usize,
+            BinOp::Le => (a <= b) as usize,
+            BinOp::Eq => (a == b) as usize,
+            BinOp::Ne => (a != b) as usize,
+        }
+    }
+
+    fn is_cmp(self) -> bool {
+        matches!(
+            self,
+            BinOp::Gt | BinOp::Lt | BinOp::Ge | BinOp::Le | BinOp::Eq | BinOp::Ne
+        )
+    }
+
+    /// Returns the operator to use if the operands are swapped.
+    fn flip(self) -> Self {
+        match self {
+            BinOp::Gt => BinOp::Lt,
+            BinOp::Lt => BinOp::Gt,
+            BinOp::Ge => BinOp::Le,
+            BinOp::Le => BinOp::Ge,
+            op => op,
+        }
+    }
+
+    fn negate(self) -> Option<Self> {
+        match self {
+            BinOp::Gt => Some(BinOp::Le),
+            BinOp::Lt => Some(BinOp::Ge),
+            BinOp::Ge => Some(BinOp::Lt),
+            BinOp::Le => Some(BinOp::Gt),
+            BinOp::Eq => Some(BinOp::Ne),
+            BinOp::Ne => Some(BinOp::Eq),
+            _ => None,
+        }
+    }
+
+    /// Follows Rust's operator precedence.
+    fn precedence(self) -> u8 {
+        match self {
+            BinOp::Mul => 4,
+            BinOp::Add => 3,
+            BinOp::And => 2,
+            BinOp::Or => 1,
+            _ => 0,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            BinOp::Add => "+",
+            BinOp::Mul => "*",
+            BinOp::And => "&",
+            BinOp::Or => "|",
+            BinOp::Gt => ">",
+            BinOp::Lt => "<",
+            BinOp::Ge => ">=",
+            BinOp::Le => "<=",
+            BinOp::Eq => "==",
+            BinOp::Ne => "!=",
+        }
+    }
+}
+
+impl Expr {
+    /// Constructs a binary expression, folding constants and moving immediate
+    /// values to the right.
+    fn bin(op: BinOp, a: Expr, b: Expr) -> Self {
+        match (a, b) {
+            (Expr::Imm(a), Expr::Imm(b)) => Expr::Imm(op.eval(a, b)),
+            (a @ Expr::Imm(_), b) => Expr::Bin(op.flip(), Box::new(b), Box::new(a)),
+            (a, b) => Expr::Bin(op, Box::new(a), Box::new(b)),
+        }
+    }
+
+    /// Returns the expression for the instruction after this one.
+    fn succ(self) -> Self {
+        match self {
+            Expr::Bin(BinOp::Add, a, b) if matches!(*b, Expr::Imm(_)) => {
+                let Expr::Imm(b) = *b else { unreachable!() };
+                Expr::bin(BinOp::Add, *a, Expr::Imm(b + 1))
+            }
+            e => Expr::bin(BinOp::Add, e, Expr::Imm(1)),
+        }
+    }
+
+    /// Returns an expression that is true when this one is false.
+    fn not(self) -> Self {
+        match self {
+            Expr::Bin(op, a, b) if op.is_cmp() => Expr::Bin(op.negate().unwrap(), a, b),
+            e => Expr::bin(BinOp::Eq, e, Expr::Imm(0)),
+        }
+    }
+
+    /// Returns this expression as a comparison.
+    fn truthy(self) -> Self {
+        match self {
+            Expr::Bin(op, ..) if op.is_cmp() => self,
+            e => Expr::bin(BinOp::Ne, e, Expr::Imm(0)),
+        }
+    }
+
+    /// Returns a bit set of the registers read by the expression.
+    fn reads(&self) -> u32 {
+        match self {
+            Expr::Reg(r) => 1 << r,
+            Expr::Imm(_) => 0,
+            Expr::Bin(_, a, b) => a.reads() | b.reads(),
+        }
+    }
+
+    /// Returns the number of times the register is read by the expression.
+    fn uses(&self, reg: usize) -> usize {
+        match self {
+            Expr::Reg(r) => (*r == reg) as usize,
+            Expr::Imm(_) => 0,
+            Expr::Bin(_, a, b) => a.uses(reg) + b.uses(reg),
+        }
+    }
+
+    fn replace(&mut self, reg: usize, with: &Expr) {
+        match self {
+            Expr::Reg(r) if *r == reg => *self = with.clone(),
+            Expr::Reg(_) | Expr::Imm(_) => {}
+            Expr::Bin(op, a, b) => {
+                a.replace(reg, with);
+                b.replace(reg, with);
+                if let (Expr::Imm(a), Expr::Imm(b)) = (&**a, &**b) {
+                    *self = Expr::Imm(op.eval(*a, *b));
+                }
+            }
+        }
+    }
+}
+
+fn is_cmp(op: Op) -> bool {
+    matches!(
+        op,
+        Op::Gtir | Op::Gtri | Op::Gtrr | Op::Eqir | Op::Eqri | Op::Eqrr
+    )
+}
+
+/// Returns the value computed by the instruction at the given index, reads of
+/// the instruction pointer register are replaced by the index.
+fn expr(ip: usize, at: usize, (op, [a, b, _]): Instr) -> Expr {
+    let r = |x| match x == ip {
+        true => Expr::Imm(at),
+        false => Expr::Reg(x),
+    };
+    let i = Expr::Imm;
+    let (op, a, b) = match op {
+        Op::Addr => (BinOp::Add, r(a), r(b)),
+        Op::Addi => (BinOp::Add, r(a), i(b)),
+        Op::Mulr => (BinOp::Mul, r(a), r(b)),
+        Op::Muli => (BinOp::Mul, r(a), i(b)),
+        Op::Banr => (BinOp::And, r(a), r(b)),
+        Op::Bani => (BinOp::And, r(a), i(b)),
+        Op::Borr => (BinOp::Or, r(a), r(b)),
+        Op::Bori => (BinOp::Or, r(a), i(b)),
+        Op::Setr => return r(a),
+        Op::Seti => return i(a),
+        Op::Gtir => (BinOp::Gt, i(a), r(b)),
+        Op::Gtri => (BinOp::Gt, r(a), i(b)),
+        Op::Gtrr => (BinOp::Gt, r(a), r(b)),
+        Op::Eqir => (BinOp::Eq, i(a), r(b)),
+        Op::Eqri => (BinOp::Eq, r(a), i(b)),
+        Op::Eqrr => (BinOp::Eq, r(a), r(b)),
+    };
+    Expr::bin(op, a, b)
+}
+
+/// A jump using instruction indexes.
+enum Jump {
+    Goto(usize),
+    /// Skips the next instruction if the register is set.
+    Skip(usize),
+    Computed(Expr),
+}
+
+/// Whether the register holds the result of a comparison when the instruction
+/// at the given index is executed.
+fn is_bool(program: &Program, at: usize, reg: usize) -> bool {
+    program.instrs[..at]
+        .iter()
+        .rev()
+        .take_while(|(_, [_, _, c])| *c != program.ip)
+        .find(|(_, [_, _, c])| *c == reg)
+        .is_some_and(|&(op, _)| is_cmp(op))
+}
+
+/// Returns the register added to the instruction pointer if the expression is
+/// a relative jump.
+fn skipped_by(e: &Expr, at: usize) -> Option<usize> {
+    match e {
+        Expr::Bin(BinOp::Add, a, b) if **b == Expr::Imm(at) => match **a {
+            Expr::Reg(r) => Some(r),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Cfg {
+    /// Builds the control-flow graph for the given program.
+    pub fn new(program: &Program) -> Self {
+        let Program { ip, instrs } = program;
+        let (ip, n) = (*ip, instrs.len());
+
+        let jumps: Vec<_> = instrs
+            .iter()
+            .enumerate()
+            .map(|(at, &instr)| {
+                let (_, [_, _, c]) = instr;
+                if c != ip {
+                    return None;
+                }
+                let e = expr(ip, at, instr);
+                let jump = if let Expr::Imm(v) = e {
+                    Jump::Goto(v + 1)
+                } else if let Some(r) = skipped_by(&e, at).filter(|&r| is_bool(program, at, r)) {
+                    Jump::Skip(r)
+                } else {
+                    Jump::Computed(e.succ())
+                };
+                Some(jump)
+            })
+            .collect();
+
+        // Find where each block starts
+        let mut leader = vec![false; n + 2];
+        leader[0] = true;
+        for (at, jump) in jumps.iter().enumerate() {
+            match jump {
+                Some(Jump::Goto(t)) => leader[(*t).min(n)] = true,
+                Some(Jump::Skip(_)) => leader[at + 2] = true,
+                Some(Jump::Computed(_)) => {}
+                None => continue,
+            }
+            leader[at + 1] = true;
+        }
+        let starts: Vec<_> = (0..n).filter(|&i| leader[i]).collect();
+        let mut ids = vec![starts.len(); n + 2];
+        for (b, &start) in starts.iter().enumerate() {
+            ids[start] = b;
+        }
+        let id = |at: usize| ids[at.min(n)];
+
+        let mut blocks: Vec<_> = starts
+            .iter()
+            .enumerate()
+            .map(|(b, &start)| {
+                let end = starts.get(b + 1).copied().unwrap_or(n);
+                let mut stmts = Vec::new();
+                let mut term = Term::Goto(id(end));
+                for at in start..end {
+                    let (_, [_, _, c]) = instrs[at];
+                    term = match &jumps[at] {
+                        None => {
+                            stmts.push((c, expr(ip, at, instrs[at])));
+                            continue;
+                        }
+                        Some(Jump::Goto(t)) => Term::Goto(id(*t)),
+                        Some(Jump::Skip(r)) => Term::Branch(Expr::Reg(*r), id(at + 2), id(at + 1)),
+                        Some(Jump::Computed(e)) => Term::Computed(e.clone()),
+                    };
+                }
+                Block { start, stmts, term }
+            })
+            .collect();
+
+        // Jump straight past blocks that only contain a goto
+        let mut skipped = vec![false; blocks.len()];
+        let resolve = |mut t: usize, skipped: &mut Vec<bool>| {
+            for _ in 0..blocks.len() {
+                match blocks.get(t) {
+                    Some(Block {
+                        stmts,
+                        term: Term::Goto(u),
+                        ..
+                    }) if stmts.is_empty() => {
+                        skipped[t] = true;
+                        t = *u;
+                    }
+                    _ => break,
+                }
+            }
+            t
+        };
+        let terms: Vec<_> = blocks
+            .iter()
+            .map(|block| match &block.term {
+                Term::Goto(t) => Term::Goto(resolve(*t, &mut skipped)),
+                Term::Branch(c, t, f) => Term::Branch(
+                    c.clone(),
+                    resolve(*t, &mut skipped),
+                    resolve(*f, &mut skipped),
+                ),
+                Term::Computed(e) => Term::Computed(e.clone()),
+            })
+            .collect();
+        for (block, term) in blocks.iter_mut().zip(terms) {
+            block.term = term;
+        }
+
+        let mut cfg = Self {
+            blocks,
+            roots: Vec::new(),
+        };
+        let preds = cfg.preds();
+        cfg.roots = (0..cfg.blocks.len())
+            .filter(|&b| b == 0 || (preds[b].is_empty() && !skipped[b]))
+            .collect();
+        cfg.fold();
+        cfg
+    }
+
+    /// Returns the pseudo-block index used for leaving the program.
+    pub fn exit(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the successors of the given block. Computed jumps are treated
+    /// as leaving the program.
+    pub fn succs(&self, b: usize) -> Vec<usize> {
+        match self.blocks[b].term {
+            Term::Goto(t) => vec![t],
+            Term::Branch(_, t, f) => vec![t, f],
+            Term::Computed(_) => vec![self.exit()],
+        }
+    }
+
+    fn preds(&self) -> Vec<Vec<usize>> {
+        let mut preds = vec![Vec::new(); self.blocks.len() + 1];
+        for b in 0..self.blocks.len() {
+            for s in self.succs(b) {
+                preds[s].push(b);
+            }
+        }
+        preds
+    }
+
+    /// Returns the registers that are live on leaving each block. Only the
+    /// first register is live when the program halts since that is where
+    /// elfcode programs leave their result, but all registers are live across
+    /// a computed jump.
+    fn liveness(&self) -> Vec<u32> {
+        let transfer = |b: usize, out: u32| {
+            let block = &self.blocks[b];
+            let mut live = out
+                | match &block.term {
+                    Term::Branch(e, ..) | Term::Computed(e) => e.reads(),
+                    Term::Goto(_) => 0,
+                };
+            for (r, e) in block.stmts.iter().rev() {
+                live = (live & !(1 << r)) | e.reads();
+            }
+            live
+        };
+        let mut live_in = vec![0; self.blocks.len()];
+        live_in.push(1);
+        let mut live_out = vec![0; self.blocks.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in (0..self.blocks.len()).rev() {
+                let out = match self.blocks[b].term {
+                    Term::Computed(_) => !0,
+                    _ => self.succs(b).iter().fold(0, |acc, &s| acc | live_in[s]),
+                };
+                let inn = transfer(b, out);
+                if (out, inn) != (live_out[b], live_in[b]) {
+                    live_out[b] = out;
+                    live_in[b] = inn;
+                    changed = true;
+                }
+            }
+        }
+        live_out
+    }
+
+    /// Substitutes registers that are only read once into their use and
+    /// removes assignments that are never read.
+    fn fold(&mut self) {
+        let live_out = self.liveness();
+        for (block, live) in self.blocks.iter_mut().zip(live_out) {
+            let Block { stmts, term, .. } = block;
+            let mut k = 0;
+            while k < stmts.len() {
+                let (d, e) = stmts[k].clone();
+                let mut uses = 0;
+                let mut first = None;
+                let mut clobbered = false;
+                let mut killed = false;
+                for (j, (w, f)) in stmts.iter().enumerate().skip(k + 1) {
+                    let n = f.uses(d);
+                    if n > 0 {
+                        uses += n;
+                        first.get_or_insert(j);
+                    } else if first.is_none() && e.reads() & (1 << w) != 0 {
+                        clobbered = true;
+                    }
+                    if *w == d {
+                        killed = true;
+                        break;
+                    }
+                }
+                if !killed {
+                    if let Term::Branch(c, ..) | Term::Computed(c) = &*term {
+                        let n = c.uses(d);
+                        if n > 0 {
+                            uses += n;
+                            first.get_or_insert(stmts.len());
+                        }
+                    }
+                    if live & (1 << d) != 0 {
+                        // Read by a later block
+                        uses += 2;
+                    }
+                }
+                match (uses, first) {
+                    (0, _) => {}
+                    (1, Some(j)) if !clobbered => match stmts.get_mut(j) {
+                        Some((_, f)) => f.replace(d, &e),
+                        None => match term {
+                            Term::Branch(c, ..) | Term::Computed(c) => c.replace(d, &e),
+                            Term::Goto(_) => unreachable!(),
+                        },
+                    },
+                    _ => {
+                        k += 1;
+                        continue;
+                    }
+                }
+                stmts.remove(k);
+            }
+        }
+    }
+}
+
+/// Computes the immediate dominator of every node reachable from the entry
+/// using the algorithm from "A Simple, Fast Dominance Algorithm" by Cooper,
+/// Harvey and Kennedy.
+fn idoms(entry: usize, succs: &[Vec<usize>]) -> Vec<Option<usize>> {
+    let n = succs.len();
+    let mut preds = vec![Vec::new(); n];
+    for (v, ss) in succs.iter().enumerate() {
+        for &s in ss {
+            preds[s].push(v);
+        }
+    }
+
+    let mut order = Vec::new();
+    let mut seen = vec![false; n];
+    let mut stack = vec![(entry, 0)];
+    seen[entry] = true;
+    while let Some((v, i)) = stack.pop() {
+        match succs[v].get(i) {
+            Some(&s) => {
+                stack.push((v, i + 1));
+                if !seen[s] {
+                    seen[s] = true;
+                    stack.push((s, 0));
+                }
+            }
+            None => order.push(v),
+        }
+    }
+    let mut po = vec![usize::MAX; n];
+    for (i, &v) in order.iter().enumerate() {
+        po[v] = i;
+    }
+
+    let mut idom = vec![None; n];
+    idom[entry] = Some(entry);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &v in order.iter().rev().skip(1) {
+            let mut new = None;
+            for &p in &preds[v] {
+                if idom[p].is_none() {
+                    continue;
+                }
+                new = Some(match new {
+                    None => p,
+                    Some(mut q) => {
+                        let mut p = p;
+                        while p != q {
+                            while po[p] < po[q] {
+                                p = idom[p].unwrap();
+                            }
+                            while po[q] < po[p] {
+                                q = idom[q].unwrap();
+                            }
+                        }
+                        p
+                    }
+                });
+            }
+            if new != idom[v] {
+                idom[v] = new;
+                changed = true;
+            }
+        }
+    }
+    idom
+}
+
+struct Decompiler<'a> {
+    cfg: &'a Cfg,
+    /// The immediate post-dominator of each block.
+    ipdom: Vec<Option<usize>>,
+    /// The loop headers and the block each loop exits to.
+    loops: HashMap<usize, Option<usize>>,
+    /// The header and exit of the loops currently being emitted.
+    stack: Vec<(usize, Option<usize>)>,
+    emitted: Vec<bool>,
+}
+
+impl<'a> Decompiler<'a> {
+    fn new(cfg: &'a Cfg) -> Self {
+        let n = cfg.blocks.len();
+        let exit = cfg.exit();
+
+        // Add a pseudo-block that leads to every root
+        let mut succs: Vec<_> = (0..n).map(|b| cfg.succs(b)).collect();
+        succs.push(Vec::new());
+        succs.push(cfg.roots.clone());
+        let idom = idoms(n + 1, &succs);
+        let mut preds = vec![Vec::new(); n + 2];
+        for (v, ss) in succs.iter().enumerate() {
+            for &s in ss {
+                preds[s].push(v);
+            }
+        }
+        let ipdom = idoms(exit, &preds);
+
+        let dominates = |h: usize, mut v: usize| loop {
+            if v == h {
+                break true;
+            }
+            match idom[v] {
+                Some(d) if d != v => v = d,
+                _ => break false,
+            }
+        };
+
+        // Find the natural loop for every back edge
+        let mut bodies: BTreeMap<usize, Vec<bool>> = BTreeMap::new();
+        for (u, ss) in succs.iter().enumerate().take(n) {
+            for &h in ss {
+                if h >= n || !dominates(h, u) {
+                    continue;
+                }
+                let body = bodies.entry(h).or_insert_with(|| vec![false; n]);
+                body[h] = true;
+                let mut stack = vec![u];
+                while let Some(v) = stack.pop() {
+                    if !body[v] {
+                        body[v] = true;
+                        stack.extend(preds[v].iter().filter(|&&p| p < n));
+                    }
+                }
+            }
+        }
+        let loops = bodies
+            .into_iter()
+            .map(|(h, body)| {
+                let exits: BTreeSet<_> = (0..n)
+                    .filter(|&v| body[v])
+                    .flat_map(|v| cfg.succs(v))
+                    .filter(|&s| s != exit && !body[s])
+                    .collect();
+                (h, exits.first().copied())
+            })
+            .collect();
+
+        Self {
+            cfg,
+            ipdom,
+            loops,
+            stack: Vec::new(),
+            emitted: vec![false; n],
+        }
+    }
+
+    /// Whether jumping to the block leaves the current structure.
+    fn is_edge(&self, b: usize) -> bool {
+        b == self.cfg.exit()
+            || self.emitted[b]
+            || self
+                .stack
+                .last()
+                .is_some_and(|&(h, e)| b == h || Some(b) == e)
+    }
+
+    /// Emits the blocks starting at the given one until the stop block.
+    fn region(&mut self, out: &mut Vec<Stmt>, mut b: usize, stop: Option<usize>) {
+        loop {
+            if Some(b) == stop {
+                return;
+            }
+            if b == self.cfg.exit() {
+                out.push(Stmt::Halt);
+                return;
+            }
+            if let Some(&(header, exit)) = self.stack.last() {
+                if b == header {
+                    out.push(Stmt::Continue);
+                    return;
+                }
+                if Some(b) == exit {
+                    out.push(Stmt::Break);
+                    return;
+                }
+            }
+            if self.emitted[b] {
+                out.push(Stmt::Goto(self.cfg.blocks[b].start));
+                return;
+            }
+            let next = match self.loops.get(&b) {
+                Some(&exit) => {
+                    self.stack.push((b, exit));
+                    let mut body = Vec::new();
+                    if let Some(next) = self.block(&mut body, b) {
+                        self.region(&mut body, next, None);
+                    }
+                    self.stack.pop();
+                    out.push(Stmt::Loop(body));
+                    exit
+                }
+                None => self.block(out, b),
+            };
+            match next {
+                Some(next) => b = next,
+                None => return,
+            }
+        }
+    }
+
+    /// Emits a single block, returning the block to continue at.
+    fn block(&mut self, out: &mut Vec<Stmt>, b: usize) -> Option<usize> {
+        self.emitted[b] = true;
+        let cfg = self.cfg;
+        let block = &cfg.blocks[b];
+        out.push(Stmt::Label(block.start));
+        out.extend(block.stmts.iter().map(|(r, e)| Stmt::Assign(*r, e.clone())));
+        match &block.term {
+            &Term::Goto(t) => Some(t),
+            Term::Computed(e) => {
+                out.push(Stmt::Jump(e.clone()));
+                None
+            }
+            Term::Branch(cond, t, f) => {
+                let (cond, t, f) = (cond.clone().truthy(), *t, *f);
+                if self.is_edge(t) || self.is_edge(f) {
+                    let (cond, t, f) = match self.is_edge(t) {
+                        true => (cond, t, f),
+                        false => (cond.not(), f, t),
+                    };
+                    let mut then = Vec::new();
+                    self.region(&mut then, t, None);
+                    out.push(Stmt::If(cond, then, Vec::new()));
+                    return Some(f);
+                }
+                let merge = self.ipdom[b];
+                let mut then = Vec::new();
+                self.region(&mut then, t, merge);
+                let mut other = Vec::new();
+                self.region(&mut other, f, merge);
+                out.push(Stmt::If(cond, then, other));
+                merge
+            }
+        }
+    }
+}
+
+/// Collects the targets of all gotos.
+fn gotos(stmts: &[Stmt], labels: &mut HashSet<usize>) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::Goto(l) => {
+                labels.insert(*l);
+            }
+            Stmt::If(_, a, b) => {
+                gotos(a, labels);
+                gotos(b, labels);
+            }
+            Stmt::Loop(body) | Stmt::While(_, body) | Stmt::DoWhile(body, _) => gotos(body, labels),
+            _ => {}
+        }
+    }
+}
+
+/// Whether the statements continue the loop they are directly in.
+fn continues(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+        Stmt::Continue => true,
+        Stmt::If(_, a, b) => continues(a) || continues(b),
+        _ => false,
+    })
+}
+
+/// Removes unused labels and turns loops that check a condition at the start
+/// or end into `while` and `do while` loops.
+fn simplify(stmts: Vec<Stmt>, labels: &HashSet<usize>) -> Vec<Stmt> {
+    let is_break = |stmt: Option<&Stmt>| matches!(stmt, Some(Stmt::If(_, t, e)) if t == &[Stmt::Break] && e.is_empty());
+    stmts
+        .into_iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::Label(l) if !labels.contains(&l) => None,
+            Stmt::If(c, t, e) => {
+                let (t, e) = (simplify(t, labels), simplify(e, labels));
+                match (t.is_empty(), e.is_empty()) {
+                    (true, true) => None,
+                    (true, false) => Some(Stmt::If(c.not(), e, t)),
+                    _ => Some(Stmt::If(c, t, e)),
+                }
+            }
+            Stmt::Loop(body) => {
+                let mut body = simplify(body, labels);
+                if body.last() == Some(&Stmt::Continue) {
+                    body.pop();
+                }
+                if is_break(body.first()) {
+                    let Stmt::If(c, ..) = body.remove(0) else {
+                        unreachable!()
+                    };
+                    Some(Stmt::While(c.not(), body))
+                } else if is_break(body.last()) && !continues(&body) {
+                    let Some(Stmt::If(c, ..)) = body.pop() else {
+                        unreachable!()
+                    };
+                    Some(Stmt::DoWhile(body, c.not()))
+                } else {
+                    Some(Stmt::Loop(body))
+                }
+            }
+            stmt => Some(stmt),
+        })
+        .collect()
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Reg(r) => write!(f, "r{r}"),
+            Expr::Imm(v) => write!(f, "{v}"),
+            Expr::Bin(op, a, b) => {
+                let p = op.precedence();
+                let paren = |e: &Expr, right: bool| {
+                    matches!(e, Expr::Bin(o, ..)
+                        if o.precedence() < p || (o.precedence() == p && (right || op.is_cmp())))
+                };
+                match paren(a, false) {
+                    true => write!(f, "({a})")?,
+                    false => write!(f, "{a}")?,
+                }
+                write!(f, " {} ", op.symbol())?;
+                match paren(b, true) {
+                    true => write!(f, "({b})"),
+                    false => write!(f, "{b}"),
+                }
+            }
+        }
+    }
+}
+
+fn render(out: &mut String, stmts: &[Stmt], depth: usize) -> fmt::Result {
+    let pad = depth * 4;
+    for stmt in stmts {
+        match stmt {
+            Stmt::Label(l) => writeln!(out, "{:pad$}L{l}:", "")?,
+            Stmt::Assign(r, e) => match e {
+                Expr::Bin(op, a, b) if !op.is_cmp() && **a == Expr::Reg(*r) => {
+                    writeln!(out, "{:pad$}r{r} {}= {b}", "", op.symbol())?
+                }
+                Expr::Bin(op, a, b) if !op.is_cmp() && **b == Expr::Reg(*r) => {
+                    writeln!(out, "{:pad$}r{r} {}= {a}", "", op.symbol())?
+                }
+                e => writeln!(out, "{:pad$}r{r} = {e}", "")?,
+            },
+            Stmt::If(c, t, e) => {
+                writeln!(out, "{:pad$}if {c} {{", "")?;
+                render(out, t, depth + 1)?;
+                if !e.is_empty() {
+                    writeln!(out, "{:pad$}}} else {{", "")?;
+                    render(out, e, depth + 1)?;
+                }
+                writeln!(out, "{:pad$}}}", "")?;
+            }
+            Stmt::Loop(body) => {
+                writeln!(out, "{:pad$}loop {{", "")?;
+                render(out, body, depth + 1)?;
+                writeln!(out, "{:pad$}}}", "")?;
+            }
+            Stmt::While(c, body) => {
+                writeln!(out, "{:pad$}while {c} {{", "")?;
+                render(out, body, depth + 1)?;
+                writeln!(out, "{:pad$}}}", "")?;
+            }
+            Stmt::DoWhile(body, c) => {
+                writeln!(out, "{:pad$}do {{", "")?;
+                render(out, body, depth + 1)?;
+                writeln!(out, "{:pad$}}} while {c}", "")?;
+            }
+            Stmt::Break => writeln!(out, "{:pad$}break", "")?,
+            Stmt::Continue => writeln!(out, "{:pad$}continue", "")?,
+            Stmt::Goto(l) => writeln!(out, "{:pad$}goto L{l}", "")?,
+            Stmt::Jump(e) => writeln!(out, "{:pad$}goto {e}", "")?,
+            Stmt::Halt => writeln!(out, "{:pad$}halt", "")?,
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Decompiled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        render(&mut out, &self.stmts, 0)?;
+        f.write_str(&out)
+    }
+}
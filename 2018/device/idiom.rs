@@ -0,0 +1,339 @@
+//! Recognise hot inner loops in elfcode programs and run them natively.
+//!
+//! Elfcode has no subtraction, division or remainder so programs build them
+//! out of counting loops, which are far too slow to run one instruction at a
+//! time. Each idiom here matches the exact instructions of such a loop and
+//! computes the state of the registers after the loop exits in closed form,
+//! including the number of instructions that would have been executed.
+
+use std::cmp::max;
+
+use super::{Op, Program};
+
+/// A loop that can be run natively, registers are given by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idiom {
+    /// Multiplication by repeated addition.
+    ///
+    /// ```text
+    /// do {
+    ///     acc += x
+    ///     counter += 1
+    /// } while counter <= bound
+    /// ```
+    Multiply {
+        acc: usize,
+        x: Operand,
+        counter: usize,
+        bound: usize,
+        tmp: usize,
+    },
+    /// A scan for a divisor, adding the factor to the accumulator if it
+    /// divides the target.
+    ///
+    /// ```text
+    /// do {
+    ///     if factor * counter == target {
+    ///         acc += factor
+    ///     }
+    ///     counter += 1
+    /// } while counter <= target
+    /// ```
+    DivisorScan {
+        acc: usize,
+        factor: usize,
+        counter: usize,
+        target: usize,
+        tmp: usize,
+        cond: usize,
+    },
+    /// Division by counting up until the divisor no longer fits, the counting
+    /// stand-in for a countdown loop.
+    ///
+    /// ```text
+    /// while (quotient + 1) * divisor <= dividend {
+    ///     quotient += 1
+    /// }
+    /// ```
+    Divide {
+        quotient: usize,
+        divisor: Operand,
+        dividend: usize,
+        tmp: usize,
+        exit: usize,
+    },
+}
+
+/// A register or immediate value read by an idiom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Reg(usize),
+    Imm(usize),
+}
+
+impl Operand {
+    fn get(self, regs: &[usize; 6]) -> usize {
+        match self {
+            Operand::Reg(r) => regs[r],
+            Operand::Imm(v) => v,
+        }
+    }
+}
+
+/// Finds the idioms in the program, indexed by the instruction they start at.
+pub fn find(program: &Program) -> Vec<Option<Idiom>> {
+    (0..program.instrs.len())
+        .map(|at| {
+            let m = Matcher { program, at };
+            m.multiply()
+                .or_else(|| m.divisor_scan())
+                .or_else(|| m.divide())
+        })
+        .collect()
+}
+
+/// Whether all the registers are different.
+fn distinct(regs: &[usize]) -> bool {
+    regs.iter()
+        .enumerate()
+        .all(|(i, r)| !regs[i + 1..].contains(r))
+}
+
+struct Matcher<'a> {
+    program: &'a Program,
+    at: usize,
+}
+
+impl Matcher<'_> {
+    /// Returns the operands of the instruction at the given offset if it has
+    /// the given operation.
+    fn op(&self, offset: usize, op: Op) -> Option<[usize; 3]> {
+        self.program
+            .instrs
+            .get(self.at + offset)
+            .filter(|(o, _)| *o == op)
+            .map(|&(_, args)| args)
+    }
+
+    /// Matches a commutative operation writing to `c` with `key` as one of the
+    /// operands, returning the other operand.
+    fn commutative(&self, offset: usize, op: Op, key: usize, c: usize) -> Option<usize> {
+        let [a, b, w] = self.op(offset, op)?;
+        match (w == c, a == key, b == key) {
+            (true, true, _) => Some(b),
+            (true, _, true) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Matches `ip += reg`, a skip over the next instruction if the register
+    /// is set, returning the register.
+    fn skip_if(&self, offset: usize) -> Option<usize> {
+        let ip = self.program.ip;
+        self.commutative(offset, Op::Addr, ip, ip)
+    }
+
+    /// Matches `ip += 1`, an unconditional skip over the next instruction.
+    fn skip(&self, offset: usize) -> Option<()> {
+        let ip = self.program.ip;
+        (self.op(offset, Op::Addi)? == [ip, 1, ip]).then_some(())
+    }
+
+    /// Matches a jump to an absolute address, returning the address.
+    fn goto(&self, offset: usize) -> Option<usize> {
+        let [a, _, c] = self.op(offset, Op::Seti)?;
+        (c == self.program.ip).then_some(a + 1)
+    }
+
+    /// Matches `reg += 1`.
+    fn incr(&self, offset: usize, reg: usize) -> Option<()> {
+        (self.op(offset, Op::Addi)? == [reg, 1, reg]).then_some(())
+    }
+
+    fn multiply(&self) -> Option<Idiom> {
+        let ip = self.program.ip;
+        let (acc, x) = match *self.program.instrs.get(self.at)? {
+            (Op::Addr, [a, b, c]) if a == c => (c, Operand::Reg(b)),
+            (Op::Addr, [a, b, c]) if b == c => (c, Operand::Reg(a)),
+            (Op::Addi, [a, b, c]) if a == c => (c, Operand::Imm(b)),
+            _ => return None,
+        };
+        let [counter, _, _] = self.op(1, Op::Addi)?;
+        self.incr(1, counter)?;
+        let [c, bound, tmp] = self.op(2, Op::Gtrr)?;
+        (c == counter).then_some(())?;
+        (self.skip_if(3)? == tmp).then_some(())?;
+        (self.goto(4)? == self.at).then_some(())?;
+        distinct(&[acc, counter, bound, tmp, ip]).then_some(())?;
+        if let Operand::Reg(x) = x {
+            distinct(&[x, acc, counter, tmp, ip]).then_some(())?;
+        }
+        Some(Idiom::Multiply {
+            acc,
+            x,
+            counter,
+            bound,
+            tmp,
+        })
+    }
+
+    fn divisor_scan(&self) -> Option<Idiom> {
+        let ip = self.program.ip;
+        let [a, b, tmp] = self.op(0, Op::Mulr)?;
+        let target = self.commutative(1, Op::Eqrr, tmp, tmp)?;
+        (self.skip_if(2)? == tmp).then_some(())?;
+        self.skip(3)?;
+        // The counter is the one that is incremented
+        let [counter, _, _] = self.op(5, Op::Addi)?;
+        self.incr(5, counter)?;
+        let factor = match counter {
+            c if c == a => b,
+            c if c == b => a,
+            _ => return None,
+        };
+        let [x, y, acc] = self.op(4, Op::Addr)?;
+        (x == factor && y == acc || y == factor && x == acc).then_some(())?;
+        let [_, _, cond] = self.op(6, Op::Gtrr)?;
+        (self.op(6, Op::Gtrr)? == [counter, target, cond]).then_some(())?;
+        (self.skip_if(7)? == cond).then_some(())?;
+        (self.goto(8)? == self.at).then_some(())?;
+        distinct(&[acc, factor, counter, target, tmp, ip]).then_some(())?;
+        distinct(&[acc, factor, counter, target, cond, ip]).then_some(())?;
+        Some(Idiom::DivisorScan {
+            acc,
+            factor,
+            counter,
+            target,
+            tmp,
+            cond,
+        })
+    }
+
+    fn divide(&self) -> Option<Idiom> {
+        let ip = self.program.ip;
+        let [quotient, _, tmp] = self.op(0, Op::Addi)?;
+        (self.op(0, Op::Addi)? == [quotient, 1, tmp]).then_some(())?;
+        let divisor = match *self.program.instrs.get(self.at + 1)? {
+            (Op::Muli, [a, b, c]) if a == tmp && c == tmp => Operand::Imm(b),
+            (Op::Mulr, [a, b, c]) if a == tmp && c == tmp => Operand::Reg(b),
+            (Op::Mulr, [a, b, c]) if b == tmp && c == tmp => Operand::Reg(a),
+            _ => return None,
+        };
+        let [_, dividend, _] = self.op(2, Op::Gtrr)?;
+        (self.op(2, Op::Gtrr)? == [tmp, dividend, tmp]).then_some(())?;
+        (self.skip_if(3)? == tmp).then_some(())?;
+        self.skip(4)?;
+        let exit = self.goto(5)?;
+        self.incr(6, quotient)?;
+        (self.goto(7)? == self.at).then_some(())?;
+        distinct(&[quotient, dividend, tmp, ip]).then_some(())?;
+        if let Operand::Reg(d) = divisor {
+            distinct(&[d, quotient, tmp, ip]).then_some(())?;
+        }
+        Some(Idiom::Divide {
+            quotient,
+            divisor,
+            dividend,
+            tmp,
+            exit,
+        })
+    }
+}
+
+impl Idiom {
+    /// Returns the number of instructions in the loop.
+    pub fn len(&self) -> usize {
+        match self {
+            Idiom::Multiply { .. } => 5,
+            Idiom::DivisorScan { .. } => 9,
+            Idiom::Divide { .. } => 8,
+        }
+    }
+
+    /// Returns a bit set of the registers written by the loop, not including
+    /// the instruction pointer.
+    pub fn writes(&self) -> u32 {
+        match *self {
+            Idiom::Multiply {
+                acc, counter, tmp, ..
+            } => 1 << acc | 1 << counter | 1 << tmp,
+            Idiom::DivisorScan {
+                acc,
+                counter,
+                tmp,
+                cond,
+                ..
+            } => 1 << acc | 1 << counter | 1 << tmp | 1 << cond,
+            Idiom::Divide { quotient, tmp, .. } => 1 << quotient | 1 << tmp,
+        }
+    }
+
+    /// Runs the loop starting at the given instruction to completion,
+    /// returning the number of instructions executed or `None` if the loop
+    /// would never exit.
+    pub fn apply(&self, regs: &mut [usize; 6], ip: usize) -> Option<u64> {
+        let start = regs[ip];
+        match *self {
+            Idiom::Multiply {
+                acc,
+                x,
+                counter,
+                bound,
+                tmp,
+            } => {
+                let n = iterations(regs[counter], regs[bound]);
+                regs[acc] += x.get(regs) * n;
+                regs[counter] += n;
+                regs[tmp] = 1;
+                regs[ip] = start + 5;
+                Some(5 * n as u64 - 1)
+            }
+            Idiom::DivisorScan {
+                acc,
+                factor,
+                counter,
+                target,
+                tmp,
+                cond,
+            } => {
+                let (f, c, t) = (regs[factor], regs[counter], regs[target]);
+                let n = iterations(c, t);
+                if f > 0 && t % f == 0 && (c..c + n).contains(&(t / f)) {
+                    regs[acc] += f;
+                }
+                regs[tmp] = (f * (c + n - 1) == t) as usize;
+                regs[counter] = c + n;
+                regs[cond] = 1;
+                regs[ip] = start + 9;
+                Some(8 * n as u64 - 1)
+            }
+            Idiom::Divide {
+                quotient,
+                divisor,
+                dividend,
+                tmp,
+                exit,
+            } => {
+                let d = divisor.get(regs);
+                if d == 0 {
+                    return None;
+                }
+                let q = max(regs[quotient], regs[dividend] / d);
+                let n = (q - regs[quotient]) as u64;
+                regs[quotient] = q;
+                regs[tmp] = 1;
+                regs[ip] = exit;
+                Some(7 * n + 5)
+            }
+        }
+    }
+}
+
+/// Returns the number of iterations of a loop that increments the counter
+/// until it passes the bound, the body is always run at least once.
+fn iterations(counter: usize, bound: usize) -> usize {
+    match counter <= bound {
+        true => bound - counter + 1,
+        false => 1,
+    }
+}
@@ -0,0 +1,187 @@
+//! Infer which opcode number is which operation from samples.
+//!
+//! Each sample narrows the operations its opcode could be to the ones that
+//! produce the observed registers. Then constraint propagation resolves the
+//! rest: an opcode with a single candidate claims that operation, and, when
+//! every opcode appears in the samples, an operation that only one opcode could
+//! be is claimed by that opcode.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use thiserror::Error;
+
+use super::{compute, Instr, Op};
+
+/// An observation of a single instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample<const N: usize> {
+    pub before: [usize; N],
+    /// The opcode number followed by the operands.
+    pub instr: [usize; 4],
+    pub after: [usize; N],
+}
+
+/// Decodes numeric instructions using an inferred opcode mapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoder {
+    ops: BTreeMap<usize, Op>,
+}
+
+/// An error inferring the opcode mapping.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InferError {
+    #[error("sample {sample} does not behave like any operation")]
+    NoMatch { sample: usize },
+    #[error("sample {sample} rules out the last candidate for opcode {opcode}")]
+    Contradiction { opcode: usize, sample: usize },
+    #[error("no operation is left for opcode {opcode} once the others are resolved")]
+    Unresolvable { opcode: usize },
+    #[error("ambiguous opcodes: {}", Candidates(.candidates))]
+    Ambiguous {
+        candidates: BTreeMap<usize, Vec<Op>>,
+    },
+}
+
+/// An error decoding an instruction.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown opcode `{opcode}`")]
+pub struct DecodeError {
+    pub opcode: usize,
+}
+
+/// A set of operations, as a bit set over [`Op::ALL`].
+type Set = u16;
+
+const ALL: Set = Set::MAX;
+
+fn ops(set: Set) -> Vec<Op> {
+    Op::ALL
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| set & 1 << i != 0)
+        .map(|(_, op)| op)
+        .collect()
+}
+
+/// Which operands of the operation are registers.
+fn registers(op: Op) -> [bool; 2] {
+    match op {
+        Op::Addr | Op::Mulr | Op::Banr | Op::Borr | Op::Gtrr | Op::Eqrr => [true, true],
+        Op::Addi | Op::Muli | Op::Bani | Op::Bori | Op::Setr | Op::Gtri | Op::Eqri => [true, false],
+        Op::Gtir | Op::Eqir => [false, true],
+        Op::Seti => [false, false],
+    }
+}
+
+impl<const N: usize> Sample<N> {
+    /// Returns the operations the sample behaves like.
+    pub fn candidates(&self) -> Vec<Op> {
+        ops(self.set())
+    }
+
+    fn set(&self) -> Set {
+        let [_, a, b, c] = self.instr;
+        Op::ALL
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, op)| {
+                // Out of range registers would panic, they can't match anyway
+                let [ra, rb] = registers(op);
+                (!ra || a < N) && (!rb || b < N) && c < N
+            })
+            .filter(|&(_, op)| compute(self.before, (op, [a, b, c])) == self.after)
+            .fold(0, |set, (i, _)| set | 1 << i)
+    }
+}
+
+/// Infers the opcode mapping from the samples.
+pub fn infer<const N: usize>(samples: &[Sample<N>]) -> Result<Decoder, InferError> {
+    let mut candidates: BTreeMap<usize, Set> = BTreeMap::new();
+    for (sample, s) in samples.iter().enumerate() {
+        let set = s.set();
+        if set == 0 {
+            return Err(InferError::NoMatch { sample });
+        }
+        let opcode = s.instr[0];
+        let c = candidates.entry(opcode).or_insert(ALL);
+        *c &= set;
+        if *c == 0 {
+            return Err(InferError::Contradiction { opcode, sample });
+        }
+    }
+
+    let mut resolved = BTreeMap::new();
+    while !candidates.is_empty() {
+        // An opcode that can only be one operation, or an operation that only
+        // one opcode can be. The latter only holds if every opcode has been
+        // seen, otherwise the operation could belong to an unseen one.
+        let complete = candidates.len() + resolved.len() == Op::ALL.len();
+        let single = candidates
+            .iter()
+            .find(|(_, c)| c.count_ones() == 1)
+            .map(|(&opcode, &c)| (opcode, c))
+            .or_else(|| {
+                if !complete {
+                    return None;
+                }
+                (0..Op::ALL.len()).find_map(|i| {
+                    let mut it = candidates.iter().filter(|(_, &c)| c & 1 << i != 0);
+                    match (it.next(), it.next()) {
+                        (Some((&opcode, _)), None) => Some((opcode, 1 << i)),
+                        _ => None,
+                    }
+                })
+            });
+        let Some((opcode, set)) = single else {
+            return Err(InferError::Ambiguous {
+                candidates: candidates.into_iter().map(|(o, c)| (o, ops(c))).collect(),
+            });
+        };
+        candidates.remove(&opcode);
+        resolved.insert(opcode, ops(set)[0]);
+        for (&other, c) in &mut candidates {
+            *c &= !set;
+            if *c == 0 {
+                return Err(InferError::Unresolvable { opcode: other });
+            }
+        }
+    }
+    Ok(Decoder { ops: resolved })
+}
+
+impl Decoder {
+    /// Returns the operation for the opcode number.
+    pub fn get(&self, opcode: usize) -> Option<Op> {
+        self.ops.get(&opcode).copied()
+    }
+
+    pub fn decode(&self, [opcode, a, b, c]: [usize; 4]) -> Result<Instr, DecodeError> {
+        let op = self.get(opcode).ok_or(DecodeError { opcode })?;
+        Ok((op, [a, b, c]))
+    }
+
+    pub fn decode_program(&self, program: &[[usize; 4]]) -> Result<Vec<Instr>, DecodeError> {
+        program.iter().map(|&instr| self.decode(instr)).collect()
+    }
+}
+
+struct Candidates<'a>(&'a BTreeMap<usize, Vec<Op>>);
+
+impl fmt::Display for Candidates<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (opcode, ops)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{opcode} could be ")?;
+            for (j, op) in ops.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(" or ")?;
+                }
+                write!(f, "{op}")?;
+            }
+        }
+        Ok(())
+    }
+}
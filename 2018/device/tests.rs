@@ -0,0 +1,259 @@
+//! Differential tests for the idioms, running each program one instruction at
+//! a time and natively and checking that they agree.
+
+use std::ops::Range;
+
+use super::*;
+
+/// Runs the program from each of the initial registers with and without
+/// idioms, checking the final state and the number of instructions executed.
+#[track_caller]
+fn check(src: &str, regs: impl IntoIterator<Item = [usize; 6]>) {
+    let program = parse_program(src);
+    assert!(
+        idiom::find(&program).iter().any(Option::is_some),
+        "no idiom found in\n{src}"
+    );
+    for regs in regs {
+        let mut plain = Device::new(program.clone()).with_regs(regs);
+        plain.set_budget(Some(10_000));
+        let mut fast = plain.clone();
+        fast.optimize();
+        assert_eq!(plain.run(), fast.run(), "{regs:?}");
+        assert_eq!(plain.regs(), fast.regs(), "{regs:?}");
+        assert_eq!(plain.executed(), fast.executed(), "{regs:?}");
+    }
+}
+
+/// Returns every combination of register values from the given ranges.
+fn grid(ranges: [Range<usize>; 6]) -> Vec<[usize; 6]> {
+    let mut all = vec![[0; 6]];
+    for (i, range) in ranges.into_iter().enumerate() {
+        all = all
+            .into_iter()
+            .flat_map(|regs| {
+                range.clone().map(move |v| {
+                    let mut regs = regs;
+                    regs[i] = v;
+                    regs
+                })
+            })
+            .collect();
+    }
+    all
+}
+
+#[test]
+fn idiom_multiply() {
+    let regs = grid([0..2, 0..4, 0..6, 0..6, 0..2, 0..1]);
+    check(
+        "\
+#ip 5
+setr 0 0 0
+addr 0 1 0
+addi 2 1 2
+gtrr 2 3 4
+addr 4 5 5
+seti 0 0 5",
+        regs.clone(),
+    );
+    check(
+        "\
+#ip 5
+setr 0 0 0
+addi 0 3 0
+addi 2 1 2
+gtrr 2 3 4
+addr 5 4 5
+seti 0 0 5",
+        regs,
+    );
+}
+
+#[test]
+fn idiom_divisor_scan() {
+    check(
+        "\
+#ip 4
+setr 0 0 0
+mulr 1 3 5
+eqrr 5 2 5
+addr 5 4 4
+addi 4 1 4
+addr 1 0 0
+addi 3 1 3
+gtrr 3 2 5
+addr 4 5 4
+seti 0 0 4",
+        grid([0..2, 0..6, 0..12, 0..8, 0..1, 0..2]),
+    );
+}
+
+#[test]
+fn idiom_divide() {
+    check(
+        "\
+#ip 2
+setr 0 0 0
+addi 5 1 3
+muli 3 3 3
+gtrr 3 4 3
+addr 3 2 2
+addi 2 1 2
+seti 8 0 2
+addi 5 1 5
+seti 0 0 2",
+        grid([0..1, 0..1, 0..1, 0..2, 0..20, 0..8]),
+    );
+    // A zero divisor never exits so the budget runs out
+    check(
+        "\
+#ip 2
+setr 0 0 0
+addi 5 1 3
+mulr 1 3 3
+gtrr 3 4 3
+addr 2 3 2
+addi 2 1 2
+seti 8 0 2
+addi 5 1 5
+seti 0 0 2",
+        grid([0..1, 0..4, 0..1, 0..1, 0..20, 0..6]),
+    );
+}
+
+#[test]
+fn idiom_respects_breakpoints_and_watches() {
+    let program = parse_program(
+        "\
+#ip 5
+setr 0 0 0
+addr 0 1 0
+addi 2 1 2
+gtrr 2 3 4
+addr 4 5 5
+seti 0 0 5",
+    );
+    let mut device = Device::new(program).with_regs([0, 2, 0, 3, 0, 0]);
+    device.optimize();
+    device.add_breakpoint(3);
+    device.watch(0);
+    assert_eq!(
+        device.run(),
+        Halt::Watch {
+            reg: 0,
+            old: 0,
+            new: 2
+        }
+    );
+    assert_eq!(device.run(), Halt::Breakpoint(3));
+    device.remove_breakpoint(3);
+    device.unwatch(0);
+    assert_eq!(device.run(), Halt::Exited);
+    assert_eq!(device.regs(), [8, 2, 4, 3, 1, 6]);
+    assert_eq!(device.executed(), 20);
+}
+
+/// Returns samples for every opcode using the given mapping, with registers
+/// and operands from a simple linear congruential generator.
+fn samples(mapping: impl Fn(usize) -> Op) -> Vec<Sample<4>> {
+    let mut state = 1u64;
+    let mut rand = move |n: u64| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) % n) as usize
+    };
+    let mut samples = Vec::new();
+    for _ in 0..8 {
+        for opcode in 0..16 {
+            let before = [(); 4].map(|_| rand(8));
+            let [a, b, c] = [(); 3].map(|_| rand(4));
+            let after = compute(before, (mapping(opcode), [a, b, c]));
+            samples.push(Sample {
+                before,
+                instr: [opcode, a, b, c],
+                after,
+            });
+        }
+    }
+    samples
+}
+
+#[test]
+fn infer_resolves_mapping() {
+    let mapping = |opcode: usize| Op::ALL[opcode * 7 % 16];
+    let decoder = infer(&samples(mapping)).unwrap();
+    for opcode in 0..16 {
+        assert_eq!(decoder.get(opcode), Some(mapping(opcode)));
+    }
+    assert_eq!(
+        decoder.decode_program(&[[7, 1, 2, 3], [1, 0, 0, 0]]),
+        Ok(vec![(Op::Addi, [1, 2, 3]), (Op::Bori, [0, 0, 0])])
+    );
+    assert_eq!(
+        decoder.decode([16, 0, 0, 0]).unwrap_err().to_string(),
+        "unknown opcode `16`"
+    );
+}
+
+#[test]
+fn infer_reports_ambiguity() {
+    let sample = |opcode, before, after| Sample {
+        before,
+        instr: [opcode, 1, 2, 0],
+        after,
+    };
+    // Both opcodes could be either addition
+    let samples = [
+        sample(0, [0, 3, 2, 0], [5, 3, 2, 0]),
+        sample(1, [0, 3, 2, 0], [5, 3, 2, 0]),
+        sample(2, [0, 3, 2, 0], [6, 3, 2, 0]),
+        sample(2, [0, 3, 3, 0], [6, 3, 3, 0]),
+    ];
+    let err = infer(&samples).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "ambiguous opcodes: 0 could be addr or addi, 1 could be addr or addi"
+    );
+
+    // Only one opcode is sampled, so the others could be addr or addi too
+    let samples = [sample(0, [0, 3, 2, 0], [5, 3, 2, 0])];
+    assert_eq!(
+        infer(&samples),
+        Err(InferError::Ambiguous {
+            candidates: [(0, vec![Op::Addr, Op::Addi])].into_iter().collect()
+        })
+    );
+}
+
+#[test]
+fn infer_reports_contradictions() {
+    let sample = |opcode, before, after| Sample {
+        before,
+        instr: [opcode, 1, 2, 0],
+        after,
+    };
+    let samples = [
+        sample(0, [0, 3, 2, 0], [5, 3, 2, 0]),
+        sample(0, [0, 3, 2, 0], [6, 3, 2, 0]),
+    ];
+    assert_eq!(
+        infer(&samples),
+        Err(InferError::Contradiction {
+            opcode: 0,
+            sample: 1
+        })
+    );
+    let samples = [sample(0, [0, 3, 2, 0], [7, 3, 2, 0])];
+    assert_eq!(
+        infer(&samples).unwrap_err().to_string(),
+        "sample 0 does not behave like any operation"
+    );
+    // Only addi behaves like this so both opcodes can't be resolved
+    let samples = [
+        sample(0, [0, 3, 5, 0], [5, 3, 5, 0]),
+        sample(1, [0, 3, 5, 0], [5, 3, 5, 0]),
+    ];
+    assert_eq!(infer(&samples), Err(InferError::Unresolvable { opcode: 1 }));
+}
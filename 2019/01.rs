@@ -30,14 +30,16 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    assert_eq!(fuel_for_mass(100756), 50346);
+advent::example! {
+    fn example() {
+        assert_eq!(fuel_for_mass(100756), 50346);
+    }
 }
 
-#[test]
-fn default() {
-    let masses = default_input();
-    assert_eq!(part1(masses.clone()), 3432671);
-    assert_eq!(part2(masses), 5146132);
+advent::answer! {
+    fn default() {
+        let masses = default_input();
+        assert_eq!(part1(masses.clone()), 3432671);
+        assert_eq!(part2(masses), 5146132);
+    }
 }
@@ -77,16 +77,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let mut computer = Computer::new(vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]);
-    computer.run();
-    assert_eq!(computer.mem[0], 3500);
+advent::example! {
+    fn example() {
+        let mut computer = Computer::new(vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]);
+        computer.run();
+        assert_eq!(computer.mem[0], 3500);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3850704);
-    assert_eq!(part2(input), 6718);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3850704);
+        assert_eq!(part2(input), 6718);
+    }
 }
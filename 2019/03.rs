@@ -70,42 +70,46 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "\
 R8,U5,L5,D3
 U7,R6,D4,L4",
-    );
-    assert_eq!(part1(input.clone()), 6);
-    assert_eq!(part2(input), 30);
+        );
+        assert_eq!(part1(input.clone()), 6);
+        assert_eq!(part2(input), 30);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "\
 R75,D30,R83,U83,L12,D49,R71,U7,L72
 U62,R66,U55,R34,D71,R55,D58,R83",
-    );
-    assert_eq!(part1(input.clone()), 159);
-    assert_eq!(part2(input), 610);
+        );
+        assert_eq!(part1(input.clone()), 159);
+        assert_eq!(part2(input), 610);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example3() {
+        let input = parse_input(
+            "\
 R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
 U98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
-    );
-    assert_eq!(part1(input.clone()), 135);
-    assert_eq!(part2(input), 410);
+        );
+        assert_eq!(part1(input.clone()), 135);
+        assert_eq!(part2(input), 410);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 248);
-    assert_eq!(part2(input), 28580);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 248);
+        assert_eq!(part2(input), 28580);
+    }
 }
@@ -51,9 +51,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 2220);
-    assert_eq!(part2(input), 1515);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 2220);
+        assert_eq!(part2(input), 1515);
+    }
 }
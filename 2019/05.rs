@@ -107,21 +107,23 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_program(
-        "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+advent::example! {
+    fn example() {
+        let input = parse_program(
+            "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
          1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
          999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99",
-    );
-    assert_eq!(Computer::new(input.clone()).run(7), 999);
-    assert_eq!(Computer::new(input.clone()).run(8), 1000);
-    assert_eq!(Computer::new(input).run(9), 1001);
+        );
+        assert_eq!(Computer::new(input.clone()).run(7), 999);
+        assert_eq!(Computer::new(input.clone()).run(8), 1000);
+        assert_eq!(Computer::new(input).run(9), 1001);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 11933517);
-    assert_eq!(part2(input), 10428568);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 11933517);
+        assert_eq!(part2(input), 10428568);
+    }
 }
@@ -35,22 +35,25 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L");
-    assert_eq!(part1(input), 42);
-}
-
-#[test]
-fn example2() {
-    let input =
-        parse_input("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN");
-    assert_eq!(part2(input), 4);
-}
-
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 106065);
-    assert_eq!(part2(input), 253);
+advent::example! {
+    fn example1() {
+        let input = parse_input("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L");
+        assert_eq!(part1(input), 42);
+    }
+}
+
+advent::example! {
+    fn example2() {
+        let input =
+            parse_input("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN");
+        assert_eq!(part2(input), 4);
+    }
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 106065);
+        assert_eq!(part2(input), 253);
+    }
 }
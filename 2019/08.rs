@@ -1,4 +1,5 @@
 use advent::prelude::*;
+use utilities::Screen;
 
 fn parse_input(input: &str, width: usize, height: usize) -> Image {
     let data = input
@@ -44,26 +45,25 @@ fn part1(img: Image) -> usize {
         .unwrap()
 }
 
+/// Stacks the layers, the first pixel that isn't transparent is visible.
+fn render(img: Image) -> Screen {
+    let mut screen = Screen::new(img.width, img.height);
+    for i in 0..img.size() {
+        let pixel = (0..)
+            .map(|layer| img.data[layer * img.size() + i])
+            .find(|&pixel| pixel != 2)
+            .unwrap();
+        match pixel {
+            0 => {}
+            1 => screen.set(i % img.width, i / img.width),
+            _ => panic!("unrecognized pixel value"),
+        }
+    }
+    screen
+}
+
 fn part2(img: Image) -> String {
-    (0..img.size())
-        .map(|i| {
-            (0..)
-                .map(|layer| img.data[layer * img.size() + i])
-                .find(|&layer| layer != 2)
-                .unwrap()
-        })
-        .enumerate()
-        .fold(String::new(), |mut s, (i, pixel)| {
-            if i % img.width == 0 {
-                s.push('\n');
-            }
-            s.push_str(match pixel {
-                0 => "  ",
-                1 => "██",
-                _ => panic!("unrecognized pixel value"),
-            });
-            s
-        })
+    render(img).decode().unwrap()
 }
 
 fn main() {
@@ -71,14 +71,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("123456789012", 3, 2);
-    assert_eq!(part1(input), 1);
+advent::example! {
+    fn example() {
+        let input = parse_input("123456789012", 3, 2);
+        assert_eq!(part1(input), 1);
+        let input = parse_input("0222112222120000", 2, 2);
+        assert_eq!(render(input).render('#', '.'), ".#\n#.\n");
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 1677);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 1677);
+    }
 }
@@ -60,22 +60,23 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_map_set(
-        r#".#..#
+advent::example! {
+    fn example1() {
+        let input = parse_map_set(
+            r#".#..#
 .....
 #####
 ....#
 ...##"#,
-    );
-    assert_eq!(part1(input), 8);
+        );
+        assert_eq!(part1(input), 8);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_map_set(
-        r#"......#.#.
+advent::example! {
+    fn example2() {
+        let input = parse_map_set(
+            r#"......#.#.
 #..#.#....
 ..#######.
 .#.#.###..
@@ -85,14 +86,15 @@ fn example2() {
 .##.#..###
 ##...#..#.
 .#....####"#,
-    );
-    assert_eq!(part1(input), 33);
+        );
+        assert_eq!(part1(input), 33);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_map_set(
-        r#"#.#...#.#.
+advent::example! {
+    fn example3() {
+        let input = parse_map_set(
+            r#"#.#...#.#.
 .###....#.
 .#....#...
 ##.#.#.#.#
@@ -102,14 +104,15 @@ fn example3() {
 ..##....##
 ......#...
 .####.###."#,
-    );
-    assert_eq!(part1(input), 35);
+        );
+        assert_eq!(part1(input), 35);
+    }
 }
 
-#[test]
-fn example4() {
-    let input = parse_map_set(
-        r#".#..#..###
+advent::example! {
+    fn example4() {
+        let input = parse_map_set(
+            r#".#..#..###
 ####.###.#
 ....###.#.
 ..###.##.#
@@ -119,14 +122,15 @@ fn example4() {
 #..#.#.###
 .##...##.#
 .....#.#.."#,
-    );
-    assert_eq!(part1(input), 41);
+        );
+        assert_eq!(part1(input), 41);
+    }
 }
 
-#[test]
-fn example5() {
-    let input = parse_map_set(
-        r#".#..##.###...#######
+advent::example! {
+    fn example5() {
+        let input = parse_map_set(
+            r#".#..##.###...#######
 ##.############..##.
 .#.######.########.#
 .###.#######.####.#.
@@ -146,14 +150,16 @@ fn example5() {
 .#.#.###########.###
 #.#.#.#####.####.###
 ###.##.####.##.#..##"#,
-    );
-    assert_eq!(part1(input.clone()), 210);
-    assert_eq!(part2(input), 802);
+        );
+        assert_eq!(part1(input.clone()), 210);
+        assert_eq!(part2(input), 802);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 319);
-    assert_eq!(part2(input), 517);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 319);
+        assert_eq!(part2(input), 517);
+    }
 }
@@ -1,4 +1,5 @@
 use advent::prelude::*;
+use utilities::Screen;
 use intcode::{Computer, parse_program};
 
 mod intcode;
@@ -58,23 +59,12 @@ fn part1(program: Vec<i64>) -> usize {
 
 fn part2(program: Vec<i64>) -> String {
     let map = paint(program, Color::White);
-
-    let min_x = map.keys().map(|v| v.x).min().unwrap();
-    let max_x = map.keys().map(|v| v.x).max().unwrap();
-    let min_y = map.keys().map(|v| v.y).min().unwrap();
-    let max_y = map.keys().map(|v| v.y).max().unwrap();
-
-    let mut result = String::new();
-    for y in (min_y..=max_y).rev() {
-        for x in min_x..=max_x {
-            result += match map.get(&vector![x, y]) {
-                Some(Color::Black) | None => "░░",
-                Some(Color::White) => "██",
-            };
-        }
-        result += "\n"
-    }
-    result
+    // The robot starts facing up but screen rows go down
+    let white = map
+        .into_iter()
+        .filter(|(_, c)| matches!(c, Color::White))
+        .map(|(p, _)| (p.x, -p.y));
+    Screen::from_points(white).decode().unwrap()
 }
 
 fn main() {
@@ -82,19 +72,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1930);
-    assert_eq!(
-        part2(input),
-        "\
-░░██████░░░░████████░░██░░░░██░░██░░░░██░░████████░░░░████░░░░████████░░██░░░░██░░░░░░
-░░██░░░░██░░██░░░░░░░░██░░██░░░░██░░░░██░░██░░░░░░░░██░░░░██░░░░░░░░██░░██░░░░██░░░░░░
-░░██░░░░██░░██████░░░░████░░░░░░████████░░██████░░░░██░░░░░░░░░░░░██░░░░██░░░░██░░░░░░
-░░██████░░░░██░░░░░░░░██░░██░░░░██░░░░██░░██░░░░░░░░██░░░░░░░░░░██░░░░░░██░░░░██░░░░░░
-░░██░░░░░░░░██░░░░░░░░██░░██░░░░██░░░░██░░██░░░░░░░░██░░░░██░░██░░░░░░░░██░░░░██░░░░░░
-░░██░░░░░░░░██░░░░░░░░██░░░░██░░██░░░░██░░████████░░░░████░░░░████████░░░░████░░░░░░░░
-"
-    )
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1930);
+        assert_eq!(part2(input), "PFKHECZU");
+    }
 }
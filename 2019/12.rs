@@ -105,33 +105,36 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "<x=-1, y=0, z=2>
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "<x=-1, y=0, z=2>
          <x=2, y=-10, z=-7>
          <x=4, y=-8, z=8>
          <x=3, y=5, z=-1>",
-    );
-    assert_eq!(part1(input.clone(), 10), 179);
-    assert_eq!(part2(input), 2772);
+        );
+        assert_eq!(part1(input.clone(), 10), 179);
+        assert_eq!(part2(input), 2772);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "<x=-8, y=-10, z=0>
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "<x=-8, y=-10, z=0>
          <x=5, y=5, z=10>
          <x=2, y=-7, z=3>
          <x=9, y=-8, z=-3>",
-    );
-    assert_eq!(part1(input.clone(), 100), 1940);
-    assert_eq!(part2(input), 4686774924);
+        );
+        assert_eq!(part1(input.clone(), 100), 1940);
+        assert_eq!(part2(input), 4686774924);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone(), 1000), 7988);
-    assert_eq!(part2(input), 337721412394184);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone(), 1000), 7988);
+        assert_eq!(part2(input), 337721412394184);
+    }
 }
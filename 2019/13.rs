@@ -76,9 +76,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 398);
-    assert_eq!(part2(input), 19447);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 398);
+        assert_eq!(part2(input), 19447);
+    }
 }
@@ -79,37 +79,39 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "10 ORE => 10 A
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "10 ORE => 10 A
 1 ORE => 1 B
 7 A, 1 B => 1 C
 7 A, 1 C => 1 D
 7 A, 1 D => 1 E
 7 A, 1 E => 1 FUEL",
-    );
-    assert_eq!(part1(input), 31);
+        );
+        assert_eq!(part1(input), 31);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "9 ORE => 2 A
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "9 ORE => 2 A
 8 ORE => 3 B
 7 ORE => 5 C
 3 A, 4 B => 1 AB
 5 B, 7 C => 1 BC
 4 C, 1 A => 1 CA
 2 AB, 3 BC, 4 CA => 1 FUEL",
-    );
-    assert_eq!(part1(input), 165);
+        );
+        assert_eq!(part1(input), 165);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input(
-        "157 ORE => 5 NZVS
+advent::example! {
+    fn example3() {
+        let input = parse_input(
+            "157 ORE => 5 NZVS
 165 ORE => 6 DCFZ
 44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
 12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
@@ -118,15 +120,16 @@ fn example3() {
 7 DCFZ, 7 PSHF => 2 XJWVT
 165 ORE => 2 GPVTF
 3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT",
-    );
-    assert_eq!(part1(input.clone()), 13312);
-    assert_eq!(part2(input), 82892753);
+        );
+        assert_eq!(part1(input.clone()), 13312);
+        assert_eq!(part2(input), 82892753);
+    }
 }
 
-#[test]
-fn example4() {
-    let input = parse_input(
-        "2 VPVL, 7 FWMGM, 2 CXFTF, 11 MNCFX => 1 STKFG
+advent::example! {
+    fn example4() {
+        let input = parse_input(
+            "2 VPVL, 7 FWMGM, 2 CXFTF, 11 MNCFX => 1 STKFG
 17 NVRVD, 3 JNWZP => 8 VPVL
 53 STKFG, 6 MNCFX, 46 VJHF, 81 HVMC, 68 CXFTF, 25 GNMV => 1 FUEL
 22 VJHF, 37 MNCFX => 5 FWMGM
@@ -138,15 +141,16 @@ fn example4() {
 1 NVRVD => 8 CXFTF
 1 VJHF, 6 MNCFX => 4 RFSQX
 176 ORE => 6 VJHF",
-    );
-    assert_eq!(part1(input.clone()), 180697);
-    assert_eq!(part2(input), 5586022);
+        );
+        assert_eq!(part1(input.clone()), 180697);
+        assert_eq!(part2(input), 5586022);
+    }
 }
 
-#[test]
-fn example5() {
-    let input = parse_input(
-        "171 ORE => 8 CNZTR
+advent::example! {
+    fn example5() {
+        let input = parse_input(
+            "171 ORE => 8 CNZTR
 7 ZLQW, 3 BMBT, 9 XCVML, 26 XMNCP, 1 WPTQ, 2 MZWV, 1 RJRHP => 4 PLWSL
 114 ORE => 4 BHXH
 14 VRPVC => 6 BMBT
@@ -163,14 +167,16 @@ fn example5() {
 121 ORE => 7 VRPVC
 7 XCVML => 6 RJRHP
 5 BHXH, 4 VRPVC => 5 LTCX",
-    );
-    assert_eq!(part1(input.clone()), 2210736);
-    assert_eq!(part2(input), 460664);
+        );
+        assert_eq!(part1(input.clone()), 2210736);
+        assert_eq!(part2(input), 460664);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 158482);
-    assert_eq!(part2(input), 7993831);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 158482);
+        assert_eq!(part2(input), 7993831);
+    }
 }
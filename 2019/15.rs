@@ -43,39 +43,6 @@ impl Computer {
     }
 }
 
-fn shortest(
-    computer: &mut Computer,
-    map: &mut HashMap<Vector2, Tile>,
-    mut path: HashSet<Vector2>,
-    pos: Vector2,
-) -> usize {
-    let mut min = usize::MAX;
-    path.insert(pos);
-    for d in CARDINALS {
-        let next = pos + d;
-        if map.contains_key(&next) || path.contains(&next) {
-            continue;
-        }
-        match computer.next_status(d).unwrap() {
-            Status::Wall => {
-                map.insert(next, Tile::Wall);
-                continue;
-            }
-            Status::Found => {
-                map.insert(next, Tile::OxygenTank);
-                min = cmp::min(min, path.len());
-            }
-            Status::Moved => {
-                map.insert(next, Tile::Floor);
-                let dist = shortest(computer, map, path.clone(), next);
-                min = cmp::min(min, dist);
-            }
-        }
-        computer.next_status(-d).unwrap();
-    }
-    min
-}
-
 fn longest(map: &HashMap<Vector2, Tile>, mut path: HashSet<Vector2>, pos: Vector2) -> usize {
     let mut max = path.len();
     path.insert(pos);
@@ -90,11 +57,36 @@ fn longest(map: &HashMap<Vector2, Tile>, mut path: HashSet<Vector2>, pos: Vector
     max
 }
 
+/// Explores the whole map with a breadth first search, forking the droid's
+/// computer to try each direction, and returns the map and the distance to
+/// the oxygen tank.
 fn solve(program: Vec<i64>) -> (HashMap<Vector2, Tile>, usize) {
-    let mut computer = Computer::new(program);
     let mut map = HashMap::new();
-    let min = shortest(&mut computer, &mut map, HashSet::new(), Vector2::zero());
-    (map, min)
+    map.insert(Vector2::zero(), Tile::Floor);
+    let mut min = None;
+    let mut q = VecDeque::from([(Vector2::zero(), Computer::new(program), 0)]);
+    while let Some((pos, computer, dist)) = q.pop_front() {
+        for d in CARDINALS {
+            let next = pos + d;
+            if map.contains_key(&next) {
+                continue;
+            }
+            let mut computer = computer.fork();
+            let tile = match computer.next_status(d).unwrap() {
+                Status::Wall => Tile::Wall,
+                Status::Moved => Tile::Floor,
+                Status::Found => {
+                    min.get_or_insert(dist + 1);
+                    Tile::OxygenTank
+                }
+            };
+            if !matches!(tile, Tile::Wall) {
+                q.push_back((next, computer, dist + 1));
+            }
+            map.insert(next, tile);
+        }
+    }
+    (map, min.unwrap())
 }
 
 fn part1(program: Vec<i64>) -> usize {
@@ -116,9 +108,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 228);
-    assert_eq!(part2(input), 348);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 228);
+        assert_eq!(part2(input), 348);
+    }
 }
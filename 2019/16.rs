@@ -78,33 +78,36 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input("80871224585914546619083218645595");
-    assert_eq!(part1(input), "24176176");
+advent::example! {
+    fn example1() {
+        let input = parse_input("80871224585914546619083218645595");
+        assert_eq!(part1(input), "24176176");
 
-    let input = parse_input("19617804207202209144916044189917");
-    assert_eq!(part1(input), "73745418");
+        let input = parse_input("19617804207202209144916044189917");
+        assert_eq!(part1(input), "73745418");
 
-    let input = parse_input("69317163492948606335995924319873");
-    assert_eq!(part1(input), "52432133");
+        let input = parse_input("69317163492948606335995924319873");
+        assert_eq!(part1(input), "52432133");
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input("03036732577212944063491565474664");
-    assert_eq!(part2(input), "84462026");
+advent::example! {
+    fn example2() {
+        let input = parse_input("03036732577212944063491565474664");
+        assert_eq!(part2(input), "84462026");
 
-    let input = parse_input("02935109699940807407585447034323");
-    assert_eq!(part2(input), "78725270");
+        let input = parse_input("02935109699940807407585447034323");
+        assert_eq!(part2(input), "78725270");
 
-    let input = parse_input("03081770884921959731165446850517");
-    assert_eq!(part2(input), "53553731");
+        let input = parse_input("03081770884921959731165446850517");
+        assert_eq!(part2(input), "53553731");
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), "22122816");
-    assert_eq!(part2(input), "41402171");
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), "22122816");
+        assert_eq!(part2(input), "41402171");
+    }
 }
@@ -1,5 +1,5 @@
 use advent::prelude::*;
-use intcode::{Computer, parse_program};
+use intcode::{parse_program, AsciiSession, Computer};
 
 mod intcode;
 
@@ -7,23 +7,17 @@ fn default_input() -> Vec<i64> {
     parse_program(include_input!(2019 / 17))
 }
 
-impl Computer {
-    fn read_image(&mut self) -> Option<HashMap<Vector2, char>> {
-        let mut map = HashMap::new();
-        let mut y = 0;
-        loop {
-            match self.read_line().as_deref()? {
-                "" => break Some(map),
-                line => {
-                    map.extend(line.chars().enumerate().filter_map(|(x, c)| match c {
-                        '.' => None,
-                        c => Some((vector![x as i64, y], c)),
-                    }));
-                    y += 1;
-                }
-            }
-        }
+/// Parses the camera image at the start of the text, up to the first empty
+/// line.
+fn parse_image(text: &str) -> HashMap<Vector2, char> {
+    let mut map = HashMap::new();
+    for (y, line) in text.lines().take_while(|line| !line.is_empty()).enumerate() {
+        map.extend(line.chars().enumerate().filter_map(|(x, c)| match c {
+            '.' => None,
+            c => Some((vector![x as i64, y as i64], c)),
+        }));
     }
+    map
 }
 
 const NORTH: Vector2 = vector![0, -1];
@@ -182,8 +176,8 @@ fn routine(moves: &[Move]) -> (String, Vec<String>) {
 }
 
 fn part1(program: Vec<i64>) -> i64 {
-    let mut c = Computer::new(program);
-    let image = c.read_image().unwrap();
+    let mut s = AsciiSession::new(Computer::new(program));
+    let image = parse_image(&s.read().text);
     let scaffolds = scaffolds(&image);
     scaffolds
         .iter()
@@ -194,21 +188,18 @@ fn part1(program: Vec<i64>) -> i64 {
 
 fn part2(mut program: Vec<i64>) -> i64 {
     program[0] = 2;
-    let mut c = Computer::new(program);
-    let image = c.read_image().unwrap();
+    let mut s = AsciiSession::new(Computer::new(program));
+    let image = parse_image(&s.read_until("Main:").text);
     let moves = navigate(&image);
     let (routine, functions) = routine(&moves);
-    c.read_line().unwrap();
-    c.write_line(&routine);
-    c.read_line().unwrap();
-    for f in functions {
-        c.write_line(&f);
-        c.read_line().unwrap();
+    s.write_line(&routine);
+    for (name, f) in "ABC".chars().zip(functions) {
+        s.read_until(&format!("Function {name}:"));
+        s.write_line(&f);
     }
-    c.write_line("n");
-    c.read_line().unwrap();
-    c.read_image().unwrap();
-    c.next().unwrap()
+    s.read_until("Continuous video feed?");
+    let reply = s.send("n");
+    reply.values[0]
 }
 
 fn main() {
@@ -216,9 +207,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 2508);
-    assert_eq!(part2(input), 799463);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 2508);
+        assert_eq!(part2(input), 799463);
+    }
 }
@@ -208,47 +208,50 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "\
 #########
 #b.A.@.a#
 #########",
-    );
-    assert_eq!(part1(input), 8);
+        );
+        assert_eq!(part1(input), 8);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "\
 ########################
 #f.D.E.e.C.b.A.@.a.B.c.#
 ######################.#
 #d.....................#
 ########################",
-    );
-    assert_eq!(part1(input), 86);
+        );
+        assert_eq!(part1(input), 86);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example3() {
+        let input = parse_input(
+            "\
 ########################
 #...............b.C.D.f#
 #.######################
 #.....@.a.B.c.d.A.e.F.g#
 ########################",
-    );
-    assert_eq!(part1(input), 132);
+        );
+        assert_eq!(part1(input), 132);
+    }
 }
 
-#[test]
-fn example4() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example4() {
+        let input = parse_input(
+            "\
 #################
 #i.G..c...e..H.p#
 ########.########
@@ -258,28 +261,30 @@ fn example4() {
 ########.########
 #l.F..d...h..C.m#
 #################",
-    );
-    assert_eq!(part1(input), 136);
+        );
+        assert_eq!(part1(input), 136);
+    }
 }
 
-#[test]
-fn example5() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example5() {
+        let input = parse_input(
+            "\
 ########################
 #@..............ac.GI.b#
 ###d#e#f################
 ###A#B#C################
 ###g#h#i################
 ########################",
-    );
-    assert_eq!(part1(input), 81);
+        );
+        assert_eq!(part1(input), 81);
+    }
 }
 
-#[test]
-fn example6() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example6() {
+        let input = parse_input(
+            "\
 #######
 #a.#Cd#
 ##...##
@@ -287,15 +292,16 @@ fn example6() {
 ##...##
 #cB#Ab#
 #######",
-    );
-    assert_eq!(part1(input.clone()), 26);
-    assert_eq!(part2(input), 8);
+        );
+        assert_eq!(part1(input.clone()), 26);
+        assert_eq!(part2(input), 8);
+    }
 }
 
-#[test]
-fn example7() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example7() {
+        let input = parse_input(
+            "\
 ###############
 #d.ABC.#.....a#
 ######...######
@@ -303,15 +309,16 @@ fn example7() {
 ######...######
 #b.....#.....c#
 ###############",
-    );
-    assert_eq!(part1(input.clone()), 52);
-    assert_eq!(part2(input), 24);
+        );
+        assert_eq!(part1(input.clone()), 52);
+        assert_eq!(part2(input), 24);
+    }
 }
 
-#[test]
-fn example8() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example8() {
+        let input = parse_input(
+            "\
 #############
 #g#f.D#..h#l#
 #F###e#E###.#
@@ -321,14 +328,16 @@ fn example8() {
 #M###N#H###.#
 #o#m..#i#jk.#
 #############",
-    );
-    assert_eq!(part1(input.clone()), 118);
-    assert_eq!(part2(input), 72);
+        );
+        assert_eq!(part1(input.clone()), 118);
+        assert_eq!(part2(input), 72);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3646);
-    assert_eq!(part2(input), 1730);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3646);
+        assert_eq!(part2(input), 1730);
+    }
 }
@@ -52,9 +52,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 192);
-    assert_eq!(part2(input), 8381082);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 192);
+        assert_eq!(part2(input), 8381082);
+    }
 }
@@ -193,10 +193,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "         A
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "         A
          A
   #######.#########
   #######.........#
@@ -215,15 +215,16 @@ FG..#########.....#
   ###########.#####
              Z
              Z",
-    );
-    assert_eq!(part1(input.clone()), 23);
-    assert_eq!(part2(input), 26);
+        );
+        assert_eq!(part1(input.clone()), 23);
+        assert_eq!(part2(input), 26);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "                   A
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "                   A
                    A
   #################.#############
   #.#...#...................#.#.#
@@ -260,14 +261,15 @@ YN......#               VT..#....QG
   #########.###.###.#############
            B   J   C
            U   P   P",
-    );
-    assert_eq!(part1(input), 58);
+        );
+        assert_eq!(part1(input), 58);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input(
-        "             Z L X W       C
+advent::example! {
+    fn example3() {
+        let input = parse_input(
+            "             Z L X W       C
              Z P Q B       K
   ###########.#.#.#.#######.###############
   #...#.......#.#.......#.#.......#.#.#...#
@@ -304,13 +306,15 @@ RE....#.#                           #......RF
   #############.#.#.###.###################
                A O F   N
                A A D   M",
-    );
-    assert_eq!(part2(input), 396);
+        );
+        assert_eq!(part2(input), 396);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 490);
-    assert_eq!(part2(input), 5648);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 490);
+        assert_eq!(part2(input), 5648);
+    }
 }
@@ -1,5 +1,5 @@
 use advent::prelude::*;
-use intcode::{Computer, parse_program};
+use intcode::{parse_program, AsciiSession, Computer};
 
 mod intcode;
 
@@ -8,26 +8,19 @@ fn default_input() -> Vec<i64> {
 }
 
 fn run_script(input: Vec<i64>, script: &str) -> Option<i64> {
-    let mut c = Computer::new(input);
+    let mut s = AsciiSession::new(Computer::new(input));
 
-    assert_eq!(c.read_line()?, "Input instructions:");
-    c.write_line(script);
+    assert_eq!(s.read_line()?, "Input instructions:");
+    let reply = s.send(script);
 
-    assert_eq!(c.read_line()?, "");
-    assert!(matches!(&*c.read_line()?, "Running..." | "Walking..."));
-    assert_eq!(c.read_line()?, "");
-
-    match c.next()? {
-        v @ 0..=127 => {
-            // If the computer returns an ASCII value then we assume that the
-            // script failed and output all the images.
-            eprint!("{}", v as u8 as char);
-            while let Some(line) = c.read_line().as_deref() {
-                eprintln!("{line}")
-            }
+    match reply.values[..] {
+        [v] => Some(v),
+        _ => {
+            // If the computer only returns ASCII then we assume that the script
+            // failed and output all the images.
+            eprint!("{}", reply.text);
             panic!("script failed:\n{script}")
         }
-        v => Some(v),
     }
 }
 
@@ -114,9 +107,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 19356081);
-    assert_eq!(part2(input), 1141901823);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 19356081);
+        assert_eq!(part2(input), 1141901823);
+    }
 }
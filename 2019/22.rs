@@ -171,32 +171,34 @@ fn shuffle(methods: Vec<Method>, m: i128) -> Vec<i128> {
     tree.into_values().collect()
 }
 
-#[test]
-fn example1() {
-    let methods = parse_input(
-        "\
+advent::example! {
+    fn example1() {
+        let methods = parse_input(
+            "\
 deal with increment 7
 deal into new stack
 deal into new stack",
-    );
-    assert_eq!(shuffle(methods, 10), [0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+        );
+        assert_eq!(shuffle(methods, 10), [0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+    }
 }
 
-#[test]
-fn example2() {
-    let methods = parse_input(
-        "\
+advent::example! {
+    fn example2() {
+        let methods = parse_input(
+            "\
 cut 6
 deal with increment 7
 deal into new stack",
-    );
-    assert_eq!(shuffle(methods, 10), [3, 0, 7, 4, 1, 8, 5, 2, 9, 6]);
+        );
+        assert_eq!(shuffle(methods, 10), [3, 0, 7, 4, 1, 8, 5, 2, 9, 6]);
+    }
 }
 
-#[test]
-fn example3() {
-    let methods = parse_input(
-        "\
+advent::example! {
+    fn example3() {
+        let methods = parse_input(
+            "\
 deal into new stack
 cut -2
 deal with increment 7
@@ -207,13 +209,15 @@ cut 3
 deal with increment 9
 deal with increment 3
 cut -1",
-    );
-    assert_eq!(shuffle(methods, 10), [9, 2, 5, 8, 1, 4, 7, 0, 3, 6]);
+        );
+        assert_eq!(shuffle(methods, 10), [9, 2, 5, 8, 1, 4, 7, 0, 3, 6]);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3293);
-    assert_eq!(part2(input), 54168121233945);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3293);
+        assert_eq!(part2(input), 54168121233945);
+    }
 }
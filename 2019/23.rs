@@ -1,5 +1,5 @@
 use advent::prelude::*;
-use intcode::{Computer, parse_program, State};
+use intcode::{parse_program, Computer, Network, Packet, Status};
 
 mod intcode;
 
@@ -7,115 +7,43 @@ fn default_input() -> Vec<i64> {
     parse_program(include_input!(2019 / 23))
 }
 
-#[derive(Debug, Clone)]
-struct Packet {
-    x: i64,
-    y: i64,
-}
-
-struct Network {
-    nics: BTreeMap<i64, Computer>,
-    nat: Option<Packet>,
-}
-
-impl Computer {
-    fn feed_packets(&mut self, ps: impl IntoIterator<Item=Packet>) {
-        self.feed(ps.into_iter().flat_map(|Packet { x, y }| [x, y]));
-    }
-
-    fn poll(&mut self) -> Option<i64> {
-        match match self.try_next_state() {
-            Ok(state) => state,
-            Err(err) => panic!("oops `{err}`"),
-        } {
-            State::Yielded(v) => Some(v),
-            State::Waiting => None,
-            state => panic!("unexpected state `{state:?}`"),
-        }
-    }
-
-    fn poll_packet(&mut self) -> Option<(i64, Packet)> {
-        let addr = self.poll()?;
-        let x = self.poll().unwrap();
-        let y = self.poll().unwrap();
-        Some((addr, Packet { x, y }))
-    }
-
-    fn poll_packets(&mut self) -> impl Iterator<Item=(i64, Packet)> + '_ {
-        iter::from_fn(|| self.poll_packet())
-    }
-}
-
-impl Network {
-    /// Initialize a new network with 50 NICs running the provided intcode.
-    fn new(input: Vec<i64>) -> Self {
-        let nics = (0..50)
-            .map(|addr| {
-                let mut c = Computer::new(input.clone());
-                c.feed([addr, -1]);
-                (addr, c)
-            })
-            .collect();
-        Self { nics, nat: None }
-    }
-
-    /// Poll each NIC until it is `Waiting` and return all the packets.
-    fn poll(&mut self) -> BTreeMap<i64, Vec<Packet>> {
-        let mut packets = BTreeMap::new();
-        for (addr, p) in self.nics.values_mut().flat_map(Computer::poll_packets) {
-            if addr == 255 {
-                self.nat = Some(p);
-            } else {
-                packets.entry(addr).or_insert_with(Vec::new).push(p);
-            }
-        }
-        packets
-    }
-
-    /// Deliver the provided packets to NICs.
-    fn deliver(&mut self, mut packets: BTreeMap<i64, Vec<Packet>>) {
-        for (addr, nic) in self.nics.iter_mut() {
-            if let Some(ps) = packets.remove(addr) {
-                nic.feed_packets(ps);
-            }
-        }
-        debug_assert!(packets.is_empty());
-    }
-
-    fn deliver_nat_packet(&mut self) -> Packet {
-        let p = self.nat.clone().unwrap();
-        self.nics.get_mut(&0).unwrap().feed_packets([p.clone()]);
-        p
+/// Creates a network with 50 NICs running the provided intcode, each
+/// addressed by its index.
+fn network(input: Vec<i64>) -> Network {
+    let mut net = Network::packets((0..50).map(|_| Computer::new(input.clone())), 2);
+    for addr in 0..50 {
+        net.send(addr, [addr as i64, -1]);
     }
+    net
 }
 
 fn part1(input: Vec<i64>) -> i64 {
-    let mut net = Network::new(input);
-    loop {
-        let packets = net.poll();
-        if let Some(p) = net.nat {
-            break p.y;
-        }
-        net.deliver(packets);
+    let mut net = network(input);
+    match net.run() {
+        Status::Packet(Packet { addr: 255, values, .. }) => values[1],
+        status => panic!("unexpected status `{status:?}`"),
     }
 }
 
 fn part2(input: Vec<i64>) -> i64 {
-    let mut net = Network::new(input);
-    let mut prev: Option<Packet> = None;
+    let mut net = network(input);
+    let mut nat = None;
+    let mut prev = None;
     loop {
-        let packets = net.poll();
-        // We just polled all the NICs until they were all `Waiting` so if there
-        // are no packets then the network must be idle.
-        if packets.is_empty() {
-            // Check if we previously sent a packet with this `y` value.
-            // Otherwise deliver the NAT packet.
-            match (&net.nat, &prev) {
-                (Some(p), Some(prev)) if p.y == prev.y => break p.y,
-                _ => prev = Some(net.deliver_nat_packet()),
+        match net.run() {
+            Status::Packet(Packet { addr: 255, values, .. }) => nat = Some(values),
+            Status::Idle => {
+                // Check if we previously sent a packet with this `y` value.
+                // Otherwise deliver the NAT packet.
+                let values = nat.clone().unwrap();
+                if prev == Some(values[1]) {
+                    break values[1];
+                }
+                prev = Some(values[1]);
+                net.send(0, values);
             }
+            status => panic!("unexpected status `{status:?}`"),
         }
-        net.deliver(packets);
     }
 }
 
@@ -124,9 +52,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 18982);
-    assert_eq!(part2(input), 11088);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 18982);
+        assert_eq!(part2(input), 11088);
+    }
 }
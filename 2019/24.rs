@@ -231,23 +231,25 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 ....#
 #..#.
 #..##
 ..#..
 #....",
-    );
-    assert_eq!(part1(input), 2129920);
-    assert_eq!(part2(input, 10), 99);
+        );
+        assert_eq!(part1(input), 2129920);
+        assert_eq!(part2(input, 10), 99);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 28772955);
-    assert_eq!(part2(input, 200), 2023);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 28772955);
+        assert_eq!(part2(input, 200), 2023);
+    }
 }
@@ -1,5 +1,5 @@
 use advent::prelude::*;
-use intcode::{Computer, parse_program};
+use intcode::{parse_program, AsciiSession, Computer};
 
 mod intcode;
 
@@ -78,11 +78,9 @@ fn parse_list(lines: &mut Vec<String>, start: &str) -> Option<Vec<String>> {
     })
 }
 
-impl Computer {
+impl AsciiSession {
     fn read_lines(&mut self) -> Vec<String> {
-        iter::from_fn(|| self.read_line())
-            .filter(|line| !line.is_empty())
-            .collect()
+        self.read().lines().map(String::from).collect()
     }
 
     fn enter(&mut self, direction: Direction) {
@@ -175,7 +173,7 @@ const DANGEROUS: &[&str] = &[
 ];
 
 fn part1(input: Vec<i64>) -> i64 {
-    let mut c = Computer::new(input);
+    let mut c = AsciiSession::new(Computer::new(input));
     let mut path: Vec<Direction> = Vec::new();
     let mut visited: HashMap<String, HashSet<Direction>> = HashMap::new();
     let mut items = Vec::new();
@@ -216,7 +214,9 @@ fn part1(input: Vec<i64>) -> i64 {
     // Drop all the items we are holding
     c.drop_items(&items);
 
-    // Simply brute force each combination of items
+    // Simply brute force each combination of items, going back to the state
+    // with nothing held after each attempt
+    let checkpoint = c.computer().snapshot();
     for items in items.into_iter().powerset() {
         c.take_items(&items);
         c.enter(d);
@@ -225,7 +225,7 @@ fn part1(input: Vec<i64>) -> i64 {
             EnterResult::Password(pass) => return pass,
             EnterResult::Room(_) => unreachable!(),
         };
-        c.drop_items(&items);
+        c.computer_mut().restore(&checkpoint);
     }
 
     panic!("no valid combination of items worked")
@@ -236,8 +236,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 134349952);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 134349952);
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! See https://redd.it/egq9xn
 
-use crate::intcode::{Computer, parse_program, State};
+use crate::intcode::{Computer, JitComputer, parse_program, State};
 
 mod intcode;
 
@@ -21,6 +21,30 @@ fn bench(program: Vec<i64>, input: &[i64]) -> i64 {
     *output.last().unwrap()
 }
 
+#[inline(never)]
+fn bench_jit(program: Vec<i64>, input: &[i64]) -> i64 {
+    let mut c = JitComputer::new(program);
+    c.feed(input.iter().copied());
+    let mut output = Vec::new();
+    loop {
+        match c.next_state() {
+            State::Yielded(v) => output.push(v),
+            State::Complete => break,
+            State::Waiting => unreachable!(),
+        }
+    }
+    *output.last().unwrap()
+}
+
+/// Returns the number of instructions executed by the benchmark.
+fn instructions(program: Vec<i64>, input: &[i64]) -> u64 {
+    let mut c = Computer::new(program);
+    c.enable_stats();
+    c.feed(input.iter().copied());
+    while !matches!(c.next_state(), State::Complete) {}
+    c.stats().unwrap().instructions()
+}
+
 fn main() {
     let sum_of_primes = parse_program(include_str!("bench/sum-of-primes.intcode"));
     let ackermann = parse_program(include_str!("bench/ackermann.intcode"));
@@ -38,7 +62,20 @@ fn main() {
     ];
     let mut b = advent::new(|| ());
     for (name, program, input) in benches {
-        b.named(name, move |_| bench(program.to_vec(), input));
+        b.named_with_work(
+            name,
+            "instructions",
+            move |_| bench(program.to_vec(), input),
+            move |_| instructions(program.to_vec(), input),
+        );
+    }
+    for (name, program, input) in benches {
+        b.named_with_work(
+            &format!("{name} (jit)"),
+            "instructions",
+            move |_| bench_jit(program.to_vec(), input),
+            move |_| instructions(program.to_vec(), input),
+        );
     }
     let solution = b.build();
     solution.cli()
@@ -0,0 +1,39 @@
+//! Assemble an intcode program.
+//!
+//! Reads the source from the file given as the first argument, or from stdin
+//! if there is no argument, and prints the comma-separated program. See
+//! `2019/intcode/asm.rs` for the syntax.
+//!
+//! ```text
+//! cargo run --bin 2019-intcode-asm -- program.asm
+//! ```
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::process;
+
+use crate::intcode::{assemble, format_program};
+
+mod intcode;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let src = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(path)?,
+        None => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf)?;
+            buf
+        }
+    };
+    match assemble(&src) {
+        Ok(program) => println!("{}", format_program(&program)),
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
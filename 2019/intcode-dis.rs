@@ -0,0 +1,31 @@
+//! Disassemble an intcode program.
+//!
+//! Reads the program from the file given as the first argument, or from stdin
+//! if there is no argument.
+//!
+//! ```text
+//! cargo run --bin 2019-intcode-dis -- input/2019/25.txt
+//! ```
+
+use std::env;
+use std::fs;
+use std::io;
+use std::io::Read;
+
+use crate::intcode::{disassemble, parse_program};
+
+mod intcode;
+
+fn main() -> io::Result<()> {
+    let input = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(path)?,
+        None => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf)?;
+            buf
+        }
+    };
+    let program: Vec<i64> = parse_program(&input);
+    print!("{}", disassemble(&program));
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! Debug an intcode program interactively.
+//!
+//! Loads the puzzle input for the day given as the first argument, or the
+//! program in the file at the given path.
+//!
+//! ```text
+//! cargo run --bin 2019-intcode-repl -- 25
+//! cargo run --bin 2019-intcode-repl -- 2019/bench/factor.intcode
+//! ```
+//!
+//! Type `help` at the prompt for a list of commands.
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use crate::intcode::{parse_program, Computer, Debugger, Event};
+
+mod intcode;
+
+const HELP: &str = "\
+commands:
+  s, step [n]         execute n instructions (default 1)
+  c, continue         run until a breakpoint, watchpoint, input or halt
+  b, break <addr>     toggle a breakpoint
+  w, watch <addr>     toggle a watchpoint on writes to an address
+  i, input <text>     send a line of ASCII input
+  f, feed <n>...      send raw values as input
+  x <addr> [n]        show n values of memory (default 8)
+  set <addr> <value>  write a value to memory
+  d, dis [addr] [n]   disassemble n instructions (default 10) from addr (default ip)
+  r, regs             show the instruction pointer and relative base
+  t, trace            show the most recently executed instructions
+  q, quit             exit
+an empty line repeats the last command";
+
+struct Repl {
+    dbg: Debugger,
+    /// Whether the last thing output by the program was a newline, so that
+    /// messages are printed on their own line.
+    line_start: bool,
+}
+
+impl Repl {
+    fn output(&mut self, value: i64) {
+        match u8::try_from(value) {
+            Ok(b) if b == b'\n' || b.is_ascii_graphic() || b == b' ' => {
+                print!("{}", b as char);
+                self.line_start = b == b'\n';
+            }
+            _ => {
+                self.message(format_args!("output: {value}"));
+            }
+        }
+    }
+
+    fn message(&mut self, msg: impl std::fmt::Display) {
+        if !self.line_start {
+            println!();
+            self.line_start = true;
+        }
+        println!("{msg}");
+    }
+
+    fn describe(&mut self, event: Event) {
+        let c = self.dbg.computer();
+        let here = format!("{:04}: {}", c.ip(), instr_at(c, c.ip()));
+        match event {
+            Event::Stepped => {}
+            Event::Yielded(v) => self.output(v),
+            Event::Waiting => self.message("waiting for input"),
+            Event::Complete => self.message("program halted"),
+            Event::Breakpoint { .. } => self.message(format_args!("breakpoint\n{here}")),
+            Event::Watchpoint { addr, old, new } => {
+                self.message(format_args!("watchpoint {addr:04}: {old} -> {new}\n{here}"))
+            }
+        }
+    }
+
+    fn exec(&mut self, cmd: &str, args: &[&str]) -> Result<bool, Box<dyn Error>> {
+        let arg = |i: usize, default: Option<usize>| -> Result<usize, Box<dyn Error>> {
+            match args.get(i) {
+                Some(a) => Ok(a.parse()?),
+                None => default.ok_or_else(|| "missing argument".into()),
+            }
+        };
+        match cmd {
+            "help" | "h" => println!("{HELP}"),
+            "step" | "s" => {
+                for _ in 0..arg(0, Some(1))? {
+                    let c = self.dbg.computer();
+                    let here = format!("{:04}: {}", c.ip(), instr_at(c, c.ip()));
+                    self.message(here);
+                    let event = self.dbg.step()?;
+                    let stop = !matches!(event, Event::Stepped | Event::Yielded(_));
+                    self.describe(event);
+                    if stop {
+                        break;
+                    }
+                }
+            }
+            "continue" | "c" => loop {
+                match self.dbg.run()? {
+                    Event::Yielded(v) => self.output(v),
+                    event => break self.describe(event),
+                }
+            },
+            "break" | "b" => {
+                let addr = arg(0, None)?;
+                if self.dbg.add_breakpoint(addr) {
+                    println!("breakpoint added at {addr:04}");
+                } else {
+                    self.dbg.remove_breakpoint(addr);
+                    println!("breakpoint removed at {addr:04}");
+                }
+            }
+            "watch" | "w" => {
+                let addr = arg(0, None)?;
+                if self.dbg.add_watchpoint(addr) {
+                    println!("watchpoint added at {addr:04}");
+                } else {
+                    self.dbg.remove_watchpoint(addr);
+                    println!("watchpoint removed at {addr:04}");
+                }
+            }
+            "input" | "i" => {
+                let line = args.join(" ");
+                if !line.is_ascii() {
+                    return Err("input must be ASCII".into());
+                }
+                self.dbg.computer_mut().write_line(&line);
+            }
+            "feed" | "f" => {
+                let values = args.iter().map(|a| a.parse()).collect::<Result<Vec<i64>, _>>()?;
+                self.dbg.computer_mut().feed(values);
+            }
+            "x" => {
+                let addr = arg(0, None)?;
+                let n = arg(1, Some(8))?;
+                let c = self.dbg.computer();
+                let values: Vec<_> = (addr..addr + n).map(|a| c.peek(a).to_string()).collect();
+                println!("{addr:04}: {}", values.join(", "));
+            }
+            "set" => {
+                let addr = arg(0, None)?;
+                let value = args.get(1).ok_or("missing argument")?.parse()?;
+                self.dbg.computer_mut().poke(addr, value);
+            }
+            "dis" | "d" => {
+                let c = self.dbg.computer();
+                let mut addr = arg(0, Some(c.ip()))?;
+                for _ in 0..arg(1, Some(10))? {
+                    let marker = match (addr == c.ip(), self.dbg.breakpoints().any(|b| b == addr)) {
+                        (true, _) => "=>",
+                        (false, true) => " *",
+                        (false, false) => "  ",
+                    };
+                    println!("{marker} {addr:04}: {}", instr_at(c, addr));
+                    addr += c.instr_at(addr).map_or(1, |i| i.len());
+                }
+            }
+            "regs" | "r" => {
+                let c = self.dbg.computer();
+                println!("ip = {:04}, rb = {}", c.ip(), c.relative_base());
+            }
+            "trace" | "t" => {
+                for t in self.dbg.trace() {
+                    println!("{:04}: {:<32} ; rb = {}", t.ip, t.instr.to_string(), t.relative_base);
+                }
+            }
+            "quit" | "q" => return Ok(false),
+            cmd => return Err(format!("unknown command `{cmd}`, try `help`").into()),
+        }
+        Ok(true)
+    }
+}
+
+/// Formats the instruction at the address, or the raw value if it isn't a
+/// valid instruction.
+fn instr_at(c: &Computer, addr: usize) -> String {
+    match c.instr_at(addr) {
+        Ok(instr) => instr.to_string(),
+        Err(_) => format!("data {}", c.peek(addr)),
+    }
+}
+
+fn load(arg: &str) -> Result<String, Box<dyn Error>> {
+    let path = match arg.parse::<u32>() {
+        Ok(day) => [env!("CARGO_MANIFEST_DIR"), "input", "2019", &format!("{day:02}.txt")]
+            .iter()
+            .collect(),
+        Err(_) => PathBuf::from(arg),
+    };
+    fs::read_to_string(&path).map_err(|err| format!("failed to read `{}`: {err}", path.display()).into())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let arg = env::args().nth(1).ok_or("usage: 2019-intcode-repl <day | path>")?;
+    let program = parse_program(&load(&arg)?);
+    let mut repl = Repl {
+        dbg: Debugger::new(Computer::new(program)),
+        line_start: true,
+    };
+
+    let mut last = String::new();
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        if !repl.line_start {
+            println!();
+            repl.line_start = true;
+        }
+        print!("(intcode) ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            break;
+        };
+        let line = if line.trim().is_empty() { last.clone() } else { line };
+        let mut words = line.split_whitespace();
+        let Some(cmd) = words.next() else {
+            continue;
+        };
+        let args: Vec<_> = words.collect();
+        match repl.exec(cmd, &args) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(err) => repl.message(format_args!("error: {err}")),
+        }
+        last = line;
+    }
+    Ok(())
+}
@@ -2,14 +2,39 @@
 
 #![allow(dead_code)]
 
-use std::cmp::max;
+use std::array;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::VecDeque;
+use std::fmt;
+use std::iter;
+use std::mem;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::result;
 use std::str::FromStr;
 
 use thiserror::Error;
 
+pub use ascii::{AsciiSession, End, Reply};
+pub use asm::{assemble, AsmError, AsmErrorKind};
+pub use debug::{Debugger, Event, Trace};
+pub use disasm::{disassemble, Disassembly, Line};
+pub use jit::JitComputer;
+use memory::Memory;
+pub use network::{Link, Network, Packet, Status};
+pub use stats::Stats;
+
+mod ascii;
+mod asm;
+mod debug;
+mod disasm;
+mod jit;
+mod memory;
+mod network;
+mod stats;
+#[cfg(test)]
+mod tests;
+
 pub fn parse_program<T>(input: &str) -> Vec<T>
     where
         T: FromStr,
@@ -23,16 +48,314 @@ pub fn parse_program<T>(input: &str) -> Vec<T>
         .collect()
 }
 
+/// Formats a program in the comma-separated form read by [`parse_program`].
+pub fn format_program(program: &[i64]) -> String {
+    let values: Vec<_> = program.iter().map(i64::to_string).collect();
+    values.join(",")
+}
+
 pub type Result<T> = result::Result<T, Error>;
 
-#[derive(Debug, Error)]
-pub enum Error {
+/// The number of disassembled lines shown either side of the instruction
+/// pointer in an error.
+const CONTEXT_LINES: usize = 3;
+
+/// An error, with the state of the computer if it happened while running.
+#[derive(Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub context: Option<Box<Context>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
     #[error("unknown mode `{}`", .mode)]
     UnknownMode { mode: i64 },
     #[error("unknown opcode `{}`", .opcode)]
     UnknownOpcode { opcode: i64 },
-    #[error("failed to cast `{}` as `usize`", .num)]
-    BadConversion { num: i64 },
+    #[error("negative address `{}`", .addr)]
+    NegativeAddress { addr: i64 },
+    #[error("read after program execution has finished")]
+    ReadPastComplete,
+    #[error("unexpected non-ascii value `{}`", .value)]
+    NotAscii { value: i64 },
+}
+
+/// The state of the computer when an error happened.
+#[derive(Debug, Clone)]
+pub struct Context {
+    pub ip: usize,
+    /// The values at the instruction pointer.
+    pub raw: [i64; 4],
+    pub relative_base: i64,
+    /// The disassembled lines around the instruction pointer.
+    pub lines: Vec<Line>,
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self { kind, context: None }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(context) = &self.context {
+            write!(f, "\n{context}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let raw: Vec<_> = self.raw.iter().map(i64::to_string).collect();
+        write!(
+            f,
+            "at {:04}: {} (relative base {})",
+            self.ip,
+            raw.join(", "),
+            self.relative_base
+        )?;
+        for line in &self.lines {
+            let marker = if line.addrs().contains(&self.ip) { "=>" } else { "  " };
+            write!(f, "\n{marker} {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An intcode operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Mul,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustBase,
+    Halt,
+}
+
+/// A parameter mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+/// A parameter of an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Param {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+/// An operation and its parameter modes, decoded from the first value of an
+/// instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Opcode {
+    op: Op,
+    modes: [Mode; 3],
+}
+
+/// A decoded instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instr {
+    pub op: Op,
+    params: [Param; 3],
+}
+
+impl Op {
+    const ALL: [Op; 10] = [
+        Op::Add,
+        Op::Mul,
+        Op::Input,
+        Op::Output,
+        Op::JumpIfTrue,
+        Op::JumpIfFalse,
+        Op::LessThan,
+        Op::Equals,
+        Op::AdjustBase,
+        Op::Halt,
+    ];
+
+    pub fn from_opcode(opcode: i64) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.opcode() == opcode)
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.mnemonic() == mnemonic)
+    }
+
+    pub fn opcode(self) -> i64 {
+        match self {
+            Self::Add => 1,
+            Self::Mul => 2,
+            Self::Input => 3,
+            Self::Output => 4,
+            Self::JumpIfTrue => 5,
+            Self::JumpIfFalse => 6,
+            Self::LessThan => 7,
+            Self::Equals => 8,
+            Self::AdjustBase => 9,
+            Self::Halt => 99,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Mul => "mul",
+            Self::Input => "in",
+            Self::Output => "out",
+            Self::JumpIfTrue => "jnz",
+            Self::JumpIfFalse => "jz",
+            Self::LessThan => "lt",
+            Self::Equals => "eq",
+            Self::AdjustBase => "arb",
+            Self::Halt => "hlt",
+        }
+    }
+
+    /// The number of parameters this operation takes.
+    pub fn arity(self) -> usize {
+        match self {
+            Self::Add | Self::Mul | Self::LessThan | Self::Equals => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::AdjustBase => 1,
+            Self::Halt => 0,
+        }
+    }
+
+    /// Whether the last parameter is written to.
+    pub fn writes(self) -> bool {
+        matches!(
+            self,
+            Self::Add | Self::Mul | Self::Input | Self::LessThan | Self::Equals
+        )
+    }
+}
+
+impl Mode {
+    fn from_digit(mode: i64) -> result::Result<Self, ErrorKind> {
+        match mode {
+            0 => Ok(Self::Position),
+            1 => Ok(Self::Immediate),
+            2 => Ok(Self::Relative),
+            mode => Err(ErrorKind::UnknownMode { mode }),
+        }
+    }
+
+    fn digit(self) -> i64 {
+        match self {
+            Self::Position => 0,
+            Self::Immediate => 1,
+            Self::Relative => 2,
+        }
+    }
+
+    /// Returns the address a parameter refers to given its raw value, the
+    /// address it is stored at, and the relative base. Immediate parameters
+    /// refer to themselves.
+    #[inline]
+    fn resolve(self, value: i64, at: usize, relative_base: i64) -> i64 {
+        match self {
+            Self::Position => value,
+            Self::Immediate => at as i64,
+            Self::Relative => relative_base + value,
+        }
+    }
+}
+
+impl Opcode {
+    fn decode(raw: i64) -> result::Result<Self, ErrorKind> {
+        let op = Op::from_opcode(raw % 100).ok_or(ErrorKind::UnknownOpcode { opcode: raw % 100 })?;
+        let mut modes = [Mode::Position; 3];
+        for (i, mode) in modes.iter_mut().enumerate().take(op.arity()) {
+            *mode = Mode::from_digit(raw / (10i64.pow((2 + i) as u32)) % 10)?;
+        }
+        Ok(Self { op, modes })
+    }
+}
+
+impl Instr {
+    pub fn new(op: Op, params: &[Param]) -> Self {
+        assert_eq!(params.len(), op.arity(), "wrong number of parameters for `{}`", op.mnemonic());
+        let mut p = [Param { mode: Mode::Position, value: 0 }; 3];
+        p[..params.len()].copy_from_slice(params);
+        Self { op, params: p }
+    }
+
+    /// Decodes the instruction at the given address. Memory past the end of
+    /// the slice is treated as zero, just like the computer does.
+    pub fn decode(mem: &[i64], addr: usize) -> Result<Self> {
+        let get = |addr: usize| mem.get(addr).copied().unwrap_or(0);
+        let Opcode { op, modes } = Opcode::decode(get(addr))?;
+        let mut params = [Param { mode: Mode::Position, value: 0 }; 3];
+        for (i, param) in params.iter_mut().enumerate().take(op.arity()) {
+            *param = Param {
+                mode: modes[i],
+                value: get(addr + i + 1),
+            };
+        }
+        Ok(Self { op, params })
+    }
+
+    /// Encodes the instruction back into intcode.
+    pub fn encode(&self) -> Vec<i64> {
+        let mut raw = self.op.opcode();
+        for (i, param) in self.params().iter().enumerate() {
+            raw += param.mode.digit() * 10i64.pow((2 + i) as u32);
+        }
+        iter::once(raw).chain(self.params().iter().map(|p| p.value)).collect()
+    }
+
+    pub fn params(&self) -> &[Param] {
+        &self.params[..self.op.arity()]
+    }
+
+    /// The number of memory cells this instruction takes up.
+    pub fn len(&self) -> usize {
+        1 + self.op.arity()
+    }
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.mode, self.value) {
+            (Mode::Position, v) => write!(f, "[{v}]"),
+            (Mode::Immediate, v) => write!(f, "#{v}"),
+            (Mode::Relative, 0) => write!(f, "[rb]"),
+            (Mode::Relative, v) if v < 0 => write!(f, "[rb{v}]"),
+            (Mode::Relative, v) => write!(f, "[rb+{v}]"),
+        }
+    }
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.op.mnemonic())?;
+        let (inputs, output) = match self.params() {
+            [inputs @ .., output] if self.op.writes() => (inputs, Some(output)),
+            inputs => (inputs, None),
+        };
+        for (i, param) in inputs.iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(f, "{sep}{param}")?;
+        }
+        if let Some(output) = output {
+            write!(f, " -> {output}")?;
+        }
+        Ok(())
+    }
 }
 
 /// The state of the computer.
@@ -46,25 +369,88 @@ pub enum State {
     Complete,
 }
 
-#[derive(Debug)]
+/// Two computers are equal if they would behave identically from now on, i.e.
+/// they have the same memory, instruction pointer, relative base and pending
+/// input, and have both completed or not.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Computer {
-    mem: Vec<i64>,
+    mem: Memory,
     ptr: usize,
     relative_base: i64,
     input: VecDeque<i64>,
+    /// Whether the halt instruction has been executed.
+    complete: bool,
+    hooks: Hooks,
+}
+
+/// Optional instrumentation of a computer, see [`Computer::enable_stats`] and
+/// [`Computer::set_tracer`].
+///
+/// This isn't part of the state of the computer so it is ignored when
+/// comparing and hashing.
+#[derive(Default)]
+struct Hooks(Option<Box<Instruments>>);
+
+#[derive(Default)]
+struct Instruments {
+    stats: Option<Stats>,
+    tracer: Option<Tracer>,
 }
 
-fn cast(num: i64) -> Result<usize> {
-    usize::try_from(num).map_err(|_| Error::BadConversion { num })
+type Tracer = Box<dyn FnMut(&Trace)>;
+
+/// A saved state of a computer, see [`Computer::snapshot`].
+#[derive(Debug, Clone)]
+pub struct Snapshot(Computer);
+
+/// Forks keep the stats but not the tracer.
+impl Clone for Hooks {
+    fn clone(&self) -> Self {
+        Self(self.0.as_ref().and_then(|i| {
+            let stats = i.stats.clone()?;
+            Some(Box::new(Instruments {
+                stats: Some(stats),
+                tracer: None,
+            }))
+        }))
+    }
+}
+
+impl Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let i = self.0.as_deref();
+        f.debug_struct("Hooks")
+            .field("stats", &i.and_then(|i| i.stats.as_ref()))
+            .field("tracer", &i.map_or(false, |i| i.tracer.is_some()))
+            .finish()
+    }
+}
+
+impl PartialEq for Hooks {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Hooks {}
+
+impl Hash for Hooks {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+fn cast(addr: i64) -> result::Result<usize, ErrorKind> {
+    usize::try_from(addr).map_err(|_| ErrorKind::NegativeAddress { addr })
 }
 
 impl Computer {
     pub fn new(program: Vec<i64>) -> Self {
         Self {
-            mem: program,
+            mem: Memory::new(program),
             ptr: 0,
             relative_base: 0,
             input: VecDeque::new(),
+            complete: false,
+            hooks: Hooks::default(),
         }
     }
 
@@ -77,86 +463,286 @@ impl Computer {
         self.input.extend(iter);
     }
 
-    fn mem_get(&self, addr: usize) -> i64 {
-        self.mem.get(addr).copied().unwrap_or(0)
+    /// Returns the address of the `i`th parameter of the current instruction
+    /// given its mode and raw value.
+    #[inline]
+    fn addr(&self, mode: Mode, i: usize, param: i64) -> result::Result<usize, ErrorKind> {
+        cast(mode.resolve(param, self.ptr + i, self.relative_base))
+    }
+
+    #[inline]
+    fn read(&self, mode: Mode, i: usize, param: i64) -> result::Result<i64, ErrorKind> {
+        match mode {
+            Mode::Immediate => Ok(param),
+            mode => self.addr(mode, i, param).map(|addr| self.mem.get(addr)),
+        }
+    }
+
+    #[inline]
+    fn write(&mut self, mode: Mode, i: usize, param: i64, value: i64) -> result::Result<(), ErrorKind> {
+        let addr = self.addr(mode, i, param)?;
+        self.mem.set(addr, value);
+        Ok(())
+    }
+
+    /// Returns the address of the `i`th parameter of the current instruction.
+    fn param_ptr(&self, i: usize) -> result::Result<usize, ErrorKind> {
+        let Opcode { modes, .. } = Opcode::decode(self.mem.get(self.ptr))?;
+        self.addr(modes[i - 1], i, self.mem.get(self.ptr + i))
+    }
+
+    /// The address of the next instruction.
+    pub fn ip(&self) -> usize {
+        self.ptr
+    }
+
+    /// Moves execution to the given address.
+    pub fn set_ip(&mut self, ip: usize) {
+        self.ptr = ip;
+        self.complete = false;
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
     }
 
-    fn mem_get_mut(&mut self, addr: usize) -> &mut i64 {
-        self.mem.resize(max(self.mem.len(), addr + 1), 0);
-        &mut self.mem[addr]
+    /// Returns a copy of the program's memory and anything written just past
+    /// it. Memory past the end is zero, except for values written to far away
+    /// addresses which are only available using [`peek`][Self::peek].
+    pub fn memory(&self) -> Vec<i64> {
+        self.mem.to_vec()
     }
 
-    fn param_ptr(&self, i: usize) -> Result<usize> {
-        let opcode = self.mem_get(self.ptr);
-        let ptr = self.ptr + i;
-        match opcode / (10i64.pow((1 + i) as u32)) % 10 {
-            0 => Ok(cast(self.mem_get(ptr))?),
-            1 => Ok(ptr),
-            2 => Ok(cast(self.relative_base + self.mem_get(ptr))?),
-            mode => Err(Error::UnknownMode { mode }),
+    /// Reads the value at the given address.
+    pub fn peek(&self, addr: usize) -> i64 {
+        self.mem.get(addr)
+    }
+
+    /// Writes a value to the given address.
+    pub fn poke(&mut self, addr: usize, value: i64) {
+        self.mem.set(addr, value);
+    }
+
+    /// Saves the current state of the computer. This is cheap, memory is
+    /// shared until either computer writes to it.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.clone())
+    }
+
+    /// Restores the state saved in the snapshot. Any stats and tracer are
+    /// kept as they are.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        let hooks = mem::take(&mut self.hooks);
+        self.clone_from(&snapshot.0);
+        self.hooks = hooks;
+    }
+
+    /// Returns a copy of this computer that can be run independently. Like
+    /// [`snapshot`][Self::snapshot] this is cheap. The fork gets a copy of
+    /// the stats but not the tracer.
+    pub fn fork(&self) -> Self {
+        self.clone()
+    }
+
+    fn instruments(&mut self) -> &mut Instruments {
+        self.hooks.0.get_or_insert_with(Default::default)
+    }
+
+    /// Removes the instrumentation if nothing is using it, so that execution
+    /// is full speed again.
+    fn prune_hooks(&mut self) {
+        if let Some(Instruments { stats: None, tracer: None }) = self.hooks.0.as_deref() {
+            self.hooks.0 = None;
         }
     }
 
-    fn param(&self, i: usize) -> Result<i64> {
-        self.param_ptr(i).map(move |ptr| self.mem_get(ptr))
+    /// Starts counting executed instructions, clearing any previous counts.
+    pub fn enable_stats(&mut self) {
+        self.instruments().stats = Some(Stats::default());
     }
 
-    fn param_mut(&mut self, i: usize) -> Result<&mut i64> {
-        self.param_ptr(i).map(move |ptr| self.mem_get_mut(ptr))
+    /// Stops counting executed instructions, returning the counts.
+    pub fn disable_stats(&mut self) -> Option<Stats> {
+        let stats = self.hooks.0.as_mut().and_then(|i| i.stats.take());
+        self.prune_hooks();
+        stats
     }
 
-    pub fn try_next_state(&mut self) -> Result<State> {
-        loop {
-            match self.mem_get(self.ptr) % 100 {
-                1 => {
-                    *self.param_mut(3)? = self.param(1)? + self.param(2)?;
-                    self.ptr += 4;
-                }
-                2 => {
-                    *self.param_mut(3)? = self.param(1)? * self.param(2)?;
-                    self.ptr += 4;
-                }
-                3 => {
-                    if let Some(input) = self.input.pop_front() {
-                        *self.param_mut(1)? = input;
-                        self.ptr += 2;
-                    } else {
-                        break Ok(State::Waiting);
-                    }
-                }
-                4 => {
-                    let output = self.param(1)?;
+    pub fn stats(&self) -> Option<&Stats> {
+        self.hooks.0.as_ref().and_then(|i| i.stats.as_ref())
+    }
+
+    /// Calls the function after each instruction is executed, with the
+    /// address, instruction and relative base from before it was executed.
+    pub fn set_tracer(&mut self, f: impl FnMut(&Trace) + 'static) {
+        self.instruments().tracer = Some(Box::new(f));
+    }
+
+    pub fn clear_tracer(&mut self) {
+        if let Some(i) = &mut self.hooks.0 {
+            i.tracer = None;
+        }
+        self.prune_hooks();
+    }
+
+    /// Returns a hash of the state of the computer, computers with the same
+    /// hash are almost certainly equal.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Decodes the next instruction.
+    pub fn current_instr(&self) -> Result<Instr> {
+        self.instr_at(self.ptr)
+    }
+
+    /// Decodes the instruction at the given address.
+    pub fn instr_at(&self, addr: usize) -> Result<Instr> {
+        let mem: [i64; 4] = array::from_fn(|i| self.mem.get(addr + i));
+        Instr::decode(&mem, 0)
+    }
+
+    /// Returns an error with the current state of the computer.
+    fn error(&self, kind: ErrorKind) -> Error {
+        let lines = disassemble(&self.mem.to_vec()).lines;
+        let i = lines
+            .iter()
+            .position(|line| line.addrs().contains(&self.ptr))
+            .unwrap_or(lines.len());
+        let lines = lines[i.saturating_sub(CONTEXT_LINES)..(i + CONTEXT_LINES + 1).min(lines.len())].to_vec();
+        let context = Context {
+            ip: self.ptr,
+            raw: array::from_fn(|i| self.mem.get(self.ptr + i)),
+            relative_base: self.relative_base,
+            lines,
+        };
+        Error {
+            kind,
+            context: Some(Box::new(context)),
+        }
+    }
+
+    /// Executes a single instruction, returning the new state if it output a
+    /// value, or if the computer is waiting for input or complete. In the last
+    /// two cases nothing is executed.
+    #[inline]
+    pub fn try_step(&mut self) -> Result<Option<State>> {
+        if self.hooks.0.is_some() {
+            return self.try_step_hooked();
+        }
+        self.step_inner().map_err(|kind| self.error(kind))
+    }
+
+    /// Like [`try_step`][Self::try_step] but records stats and calls the
+    /// tracer if the instruction is executed.
+    #[inline(never)]
+    fn try_step_hooked(&mut self) -> Result<Option<State>> {
+        let trace = self.current_instr().ok().map(|instr| Trace {
+            ip: self.ptr,
+            relative_base: self.relative_base,
+            instr,
+        });
+        let was_complete = self.complete;
+        let state = self.step_inner().map_err(|kind| self.error(kind))?;
+        let executed = match state {
+            None | Some(State::Yielded(_)) => true,
+            Some(State::Waiting) => false,
+            Some(State::Complete) => !was_complete,
+        };
+        if let (true, Some(trace), Some(i)) = (executed, trace, self.hooks.0.as_deref_mut()) {
+            if let Some(stats) = &mut i.stats {
+                stats.record(&trace, stats::accessed(&trace));
+            }
+            if let Some(tracer) = &mut i.tracer {
+                tracer(&trace);
+            }
+        }
+        Ok(state)
+    }
+
+    #[inline(always)]
+    fn step_inner(&mut self) -> result::Result<Option<State>, ErrorKind> {
+        let (Opcode { op, modes: [m1, m2, m3] }, [p1, p2, p3]) = self.mem.fetch(self.ptr)?;
+        match op {
+            Op::Add => {
+                self.write(m3, 3, p3, self.read(m1, 1, p1)? + self.read(m2, 2, p2)?)?;
+                self.ptr += 4;
+            }
+            Op::Mul => {
+                self.write(m3, 3, p3, self.read(m1, 1, p1)? * self.read(m2, 2, p2)?)?;
+                self.ptr += 4;
+            }
+            Op::Input => {
+                if let Some(input) = self.input.pop_front() {
+                    self.write(m1, 1, p1, input)?;
                     self.ptr += 2;
-                    break Ok(State::Yielded(output));
-                }
-                5 => {
-                    if self.param(1)? != 0 {
-                        self.ptr = cast(self.param(2)?)?;
-                    } else {
-                        self.ptr += 3;
-                    }
+                } else {
+                    return Ok(Some(State::Waiting));
                 }
-                6 => {
-                    if self.param(1)? == 0 {
-                        self.ptr = cast(self.param(2)?)?;
-                    } else {
-                        self.ptr += 3;
-                    }
+            }
+            Op::Output => {
+                let output = self.read(m1, 1, p1)?;
+                self.ptr += 2;
+                return Ok(Some(State::Yielded(output)));
+            }
+            Op::JumpIfTrue => {
+                if self.read(m1, 1, p1)? != 0 {
+                    self.ptr = cast(self.read(m2, 2, p2)?)?;
+                } else {
+                    self.ptr += 3;
                 }
-                7 => {
-                    *self.param_mut(3)? = (self.param(1)? < self.param(2)?) as i64;
-                    self.ptr += 4;
+            }
+            Op::JumpIfFalse => {
+                if self.read(m1, 1, p1)? == 0 {
+                    self.ptr = cast(self.read(m2, 2, p2)?)?;
+                } else {
+                    self.ptr += 3;
                 }
-                8 => {
-                    *self.param_mut(3)? = (self.param(1)? == self.param(2)?) as i64;
-                    self.ptr += 4;
+            }
+            Op::LessThan => {
+                self.write(m3, 3, p3, (self.read(m1, 1, p1)? < self.read(m2, 2, p2)?) as i64)?;
+                self.ptr += 4;
+            }
+            Op::Equals => {
+                self.write(m3, 3, p3, (self.read(m1, 1, p1)? == self.read(m2, 2, p2)?) as i64)?;
+                self.ptr += 4;
+            }
+            Op::AdjustBase => {
+                self.relative_base += self.read(m1, 1, p1)?;
+                self.ptr += 2;
+            }
+            Op::Halt => {
+                self.complete = true;
+                return Ok(Some(State::Complete));
+            }
+        }
+        Ok(None)
+    }
+
+    #[track_caller]
+    pub fn step(&mut self) -> Option<State> {
+        match self.try_step() {
+            Ok(state) => state,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_next_state(&mut self) -> Result<State> {
+        if self.hooks.0.is_some() {
+            loop {
+                if let Some(state) = self.try_step_hooked()? {
+                    break Ok(state);
                 }
-                9 => {
-                    self.relative_base += self.param(1)?;
-                    self.ptr += 2;
+            }
+        } else {
+            loop {
+                match self.step_inner() {
+                    Ok(Some(state)) => break Ok(state),
+                    Ok(None) => {}
+                    Err(kind) => break Err(self.error(kind)),
                 }
-                99 => break Ok(State::Complete),
-                opcode => break Err(Error::UnknownOpcode { opcode }),
             }
         }
     }
@@ -165,7 +751,42 @@ impl Computer {
     pub fn next_state(&mut self) -> State {
         match self.try_next_state() {
             Ok(state) => state,
-            Err(err) => panic!("oops `{err}`"),
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Runs until the next output. Returns `None` if the computer is waiting
+    /// for input or has just completed, and an error if it had already
+    /// completed.
+    pub fn try_next(&mut self) -> Result<Option<i64>> {
+        if self.complete {
+            return Err(self.error(ErrorKind::ReadPastComplete));
+        }
+        match self.try_next_state()? {
+            State::Yielded(v) => Ok(Some(v)),
+            State::Waiting | State::Complete => Ok(None),
+        }
+    }
+
+    /// Reads the next line of ASCII output, without the newline. Returns
+    /// `None` if the computer is waiting for input or has just completed, and
+    /// an error if it had already completed or outputs a value that isn't
+    /// ASCII.
+    ///
+    /// If the computer stops partway through a line, e.g. after a prompt, the
+    /// part of the line output so far is returned rather than dropped. So
+    /// after a final line without a newline the next call is an error.
+    pub fn try_read_line(&mut self) -> Result<Option<String>> {
+        let mut line = String::new();
+        loop {
+            let Some(v) = self.try_next()? else {
+                break Ok((!line.is_empty()).then_some(line));
+            };
+            match u8::try_from(v) {
+                Ok(b'\n') => break Ok(Some(line)),
+                Ok(b) if b.is_ascii() => line.push(b as char),
+                _ => break Err(self.error(ErrorKind::NotAscii { value: v })),
+            }
         }
     }
 
@@ -0,0 +1,269 @@
+//! Talk to intcode programs that use ASCII.
+//!
+//! [`AsciiSession`] wraps a [`Computer`], collecting text output a line at a
+//! time while keeping any values outside the ASCII range, which are usually
+//! the puzzle answer, separate from the text. A session can be driven by code,
+//! by a script of input lines, or interactively, and can record a transcript
+//! of everything that was said.
+
+use std::io;
+use std::io::{BufRead, Write};
+
+use super::{Computer, State};
+
+/// Why a session stopped reading output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum End {
+    /// A line that was being waited for was output.
+    Prompt,
+    /// The program is waiting for input.
+    Waiting,
+    /// Program execution has finished.
+    Complete,
+}
+
+/// Output read from the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    /// The ASCII output, including newlines.
+    pub text: String,
+    /// Any values output that aren't ASCII.
+    pub values: Vec<i64>,
+    pub end: End,
+}
+
+#[derive(Debug)]
+pub struct AsciiSession {
+    computer: Computer,
+    /// Everything output and input so far, if it is being recorded.
+    transcript: Option<String>,
+}
+
+impl Reply {
+    /// The lines of text, skipping empty lines.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines().filter(|line| !line.is_empty())
+    }
+}
+
+impl AsciiSession {
+    pub fn new(computer: Computer) -> Self {
+        Self {
+            computer,
+            transcript: None,
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    pub fn into_computer(self) -> Computer {
+        self.computer
+    }
+
+    /// Starts recording a transcript. Input lines are recorded prefixed with
+    /// `> ` and values that aren't ASCII are recorded on their own line.
+    pub fn record_transcript(&mut self) {
+        self.transcript.get_or_insert_with(String::new);
+    }
+
+    pub fn transcript(&self) -> Option<&str> {
+        self.transcript.as_deref()
+    }
+
+    /// Reads output until the program waits for input or completes.
+    #[track_caller]
+    pub fn read(&mut self) -> Reply {
+        self.read_while(|_| true)
+    }
+
+    /// Reads output until the given line is output, or until the program
+    /// waits for input or completes.
+    #[track_caller]
+    pub fn read_until(&mut self, prompt: &str) -> Reply {
+        self.read_while(|line| line != prompt)
+    }
+
+    /// Reads the next line of output, without the newline. Returns `None` if
+    /// the program waits for input or completes first. Values that aren't
+    /// ASCII are skipped, use [`read`][Self::read] if they are needed.
+    #[track_caller]
+    pub fn read_line(&mut self) -> Option<String> {
+        let reply = self.read_while(|_| false);
+        match reply.end {
+            End::Prompt => {
+                let mut line = reply.text;
+                line.pop();
+                Some(line)
+            }
+            End::Waiting | End::Complete => None,
+        }
+    }
+
+    /// Sends a line of input, the newline is added.
+    pub fn write_line(&mut self, line: &str) {
+        if let Some(transcript) = &mut self.transcript {
+            transcript.push_str("> ");
+            transcript.push_str(line);
+            transcript.push('\n');
+        }
+        self.computer.write_line(line);
+    }
+
+    /// Sends a line of input and reads the reply to it.
+    #[track_caller]
+    pub fn send(&mut self, line: &str) -> Reply {
+        self.write_line(line);
+        self.read()
+    }
+
+    /// Reads the output up to the first prompt for input, then sends each
+    /// line and reads the reply to it. Returns the first output followed by
+    /// each reply.
+    #[track_caller]
+    pub fn script<'a>(&mut self, lines: impl IntoIterator<Item = &'a str>) -> Vec<Reply> {
+        let mut replies = vec![self.read()];
+        for line in lines {
+            replies.push(self.send(line));
+        }
+        replies
+    }
+
+    /// Plays interactively, writing output to `output` and sending each line
+    /// read from `input`, until the program completes or the input ends.
+    pub fn play(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        let mut lines = input.lines();
+        loop {
+            let reply = self.read();
+            write!(output, "{}", reply.text)?;
+            for value in &reply.values {
+                writeln!(output, "{value}")?;
+            }
+            output.flush()?;
+            if reply.end == End::Complete {
+                break Ok(());
+            }
+            let Some(line) = lines.next().transpose()? else {
+                break Ok(());
+            };
+            if !line.is_ascii() {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "input must be ASCII"));
+            }
+            self.write_line(&line);
+        }
+    }
+
+    /// Reads output until a line for which `more` returns false, or the
+    /// program waits for input or completes.
+    #[track_caller]
+    fn read_while(&mut self, mut more: impl FnMut(&str) -> bool) -> Reply {
+        let mut text = String::new();
+        let mut values = Vec::new();
+        let mut line_start = 0;
+        let end = loop {
+            let value = match self.computer.next_state() {
+                State::Yielded(value) => value,
+                State::Waiting => break End::Waiting,
+                State::Complete => break End::Complete,
+            };
+            match u8::try_from(value) {
+                Ok(b) if b.is_ascii() => {
+                    let c = b as char;
+                    text.push(c);
+                    if let Some(transcript) = &mut self.transcript {
+                        transcript.push(c);
+                    }
+                    if c == '\n' {
+                        if !more(&text[line_start..text.len() - 1]) {
+                            break End::Prompt;
+                        }
+                        line_start = text.len();
+                    }
+                }
+                _ => {
+                    values.push(value);
+                    if let Some(transcript) = &mut self.transcript {
+                        transcript.push_str(&format!("{value}\n"));
+                    }
+                }
+            }
+        };
+        Reply { text, values, end }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::assemble;
+
+    /// Returns assembly that outputs the text.
+    fn print(text: &str) -> String {
+        let mut src = String::new();
+        for b in text.bytes() {
+            src.push_str(&format!("out #{b}\n"));
+        }
+        src
+    }
+
+    /// A program that asks for a name, greets it, outputs 1000 and halts.
+    fn greeter() -> AsciiSession {
+        let src = format!(
+            "\
+{}
+loop:   in -> [c]
+        eq [c], #10 -> [t]
+        jnz [t], #done
+        out [c]
+        jnz #1, #loop
+done:   {}
+        out #1000
+        hlt
+c:      data 0
+t:      data 0
+",
+            print("Welcome\nName?\n"),
+            print("!\n"),
+        );
+        AsciiSession::new(Computer::new(assemble(&src).unwrap()))
+    }
+
+    #[test]
+    fn ascii_read() {
+        let mut s = greeter();
+        assert_eq!(s.read_line().as_deref(), Some("Welcome"));
+        let reply = s.read_until("Name?");
+        assert_eq!(reply.text, "Name?\n");
+        assert_eq!(reply.end, End::Prompt);
+        assert_eq!(s.read_line(), None);
+        let reply = s.send("Ferris");
+        assert_eq!(reply.text, "Ferris!\n");
+        assert_eq!(reply.values, [1000]);
+        assert_eq!(reply.end, End::Complete);
+    }
+
+    #[test]
+    fn ascii_script_and_transcript() {
+        let mut s = greeter();
+        s.record_transcript();
+        let replies = s.script(["Ferris"]);
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0].lines().collect::<Vec<_>>(), ["Welcome", "Name?"]);
+        assert_eq!(replies[0].end, End::Waiting);
+        assert_eq!(replies[1].values, [1000]);
+        assert_eq!(s.transcript(), Some("Welcome\nName?\n> Ferris\nFerris!\n1000\n"));
+    }
+
+    #[test]
+    fn ascii_play() {
+        let mut s = greeter();
+        let mut output = Vec::new();
+        s.play("Ferris\nunused\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "Welcome\nName?\nFerris!\n1000\n");
+    }
+}
@@ -0,0 +1,361 @@
+//! Assemble intcode programs from a mnemonic syntax.
+//!
+//! The syntax is the same as the output of the disassembler, so anything it
+//! prints can be assembled back into the original program.
+//!
+//! ```text
+//!         arb #stack          ; set up the stack
+//!         in -> [rb]
+//! loop:   jz [rb], #done
+//!         out [rb]
+//!         add [rb], #-1 -> [rb]
+//!         jnz #1, #loop
+//! done:   hlt
+//! stack:  data 0
+//! ```
+//!
+//! - Each line may start with an address `0012:` which must match the
+//!   address the line is assembled at, and any number of labels `name:`.
+//! - Parameters are written as `[addr]` for position mode, `#value` for
+//!   immediate mode and `[rb]`, `[rb+offset]` or `[rb-offset]` for relative
+//!   mode.
+//! - The parameter that is written to can be separated with `->` instead of a
+//!   comma.
+//! - `data` emits the given values as is.
+//! - Anywhere a value is expected a label can be used, optionally with
+//!   integers or other labels added or subtracted, e.g. `[table+2]`.
+//! - Everything after a `;` is a comment.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::{Instr, Mode, Op, Param};
+
+#[derive(Debug, Error)]
+#[error("line {}: {}", .line, .kind)]
+pub struct AsmError {
+    pub line: usize,
+    pub kind: AsmErrorKind,
+}
+
+#[derive(Debug, Error)]
+pub enum AsmErrorKind {
+    #[error("unknown mnemonic `{}`", .mnemonic)]
+    UnknownMnemonic { mnemonic: String },
+    #[error("`{}` takes {} parameters but {} were given", .mnemonic, .expected, .found)]
+    WrongArity {
+        mnemonic: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("`{}` doesn't write to a parameter", .mnemonic)]
+    UnexpectedArrow { mnemonic: String },
+    #[error("invalid parameter `{}`", .param)]
+    BadParam { param: String },
+    #[error("invalid expression `{}`", .expr)]
+    BadExpr { expr: String },
+    #[error("undefined label `{}`", .label)]
+    UndefinedLabel { label: String },
+    #[error("label `{}` is already defined", .label)]
+    DuplicateLabel { label: String },
+    #[error("line is at address {} but is labelled {}", .expected, .found)]
+    AddressMismatch { expected: usize, found: usize },
+}
+
+type Result<T> = std::result::Result<T, AsmErrorKind>;
+
+/// A sum of integers and labels.
+#[derive(Debug)]
+struct Expr {
+    terms: Vec<(i64, Term)>,
+}
+
+#[derive(Debug)]
+enum Term {
+    Num(i64),
+    Label(String),
+}
+
+#[derive(Debug)]
+enum Item {
+    Instr { op: Op, params: Vec<(Mode, Expr)> },
+    Data { values: Vec<Expr> },
+}
+
+impl Item {
+    fn len(&self) -> usize {
+        match self {
+            Self::Instr { op, .. } => 1 + op.arity(),
+            Self::Data { values } => values.len(),
+        }
+    }
+}
+
+/// Assembles the given source into an intcode program.
+pub fn assemble(src: &str) -> std::result::Result<Vec<i64>, AsmError> {
+    let mut labels = HashMap::new();
+    let mut items = Vec::new();
+    let mut addr = 0;
+
+    for (i, line) in src.lines().enumerate() {
+        let err = |kind| AsmError { line: i + 1, kind };
+        let line = line.split_once(';').map_or(line, |(code, _)| code);
+        let rest = parse_prefixes(line, addr, |label| {
+            match labels.insert(label.to_owned(), addr) {
+                Some(_) => Err(AsmErrorKind::DuplicateLabel {
+                    label: label.to_owned(),
+                }),
+                None => Ok(()),
+            }
+        })
+        .map_err(err)?;
+        if rest.is_empty() {
+            continue;
+        }
+        let item = parse_item(rest).map_err(err)?;
+        addr += item.len();
+        items.push((i + 1, item));
+    }
+
+    let mut program = Vec::with_capacity(addr);
+    for (line, item) in items {
+        let err = |kind| AsmError { line, kind };
+        match item {
+            Item::Instr { op, params } => {
+                let params = params
+                    .iter()
+                    .map(|(mode, expr)| {
+                        let value = expr.eval(&labels)?;
+                        Ok(Param { mode: *mode, value })
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .map_err(err)?;
+                program.extend(Instr::new(op, &params).encode());
+            }
+            Item::Data { values } => {
+                for expr in values {
+                    program.push(expr.eval(&labels).map_err(err)?);
+                }
+            }
+        }
+    }
+    Ok(program)
+}
+
+/// Strips any address and labels from the start of the line, returning the
+/// rest of it.
+fn parse_prefixes(
+    mut line: &str,
+    addr: usize,
+    mut define: impl FnMut(&str) -> Result<()>,
+) -> Result<&str> {
+    loop {
+        line = line.trim();
+        let Some((prefix, rest)) = line.split_once(':') else {
+            return Ok(line);
+        };
+        if is_label(prefix) {
+            define(prefix)?;
+        } else if let Ok(found) = prefix.parse() {
+            if found != addr {
+                return Err(AsmErrorKind::AddressMismatch {
+                    expected: addr,
+                    found,
+                });
+            }
+        } else {
+            return Ok(line);
+        }
+        line = rest;
+    }
+}
+
+fn parse_item(s: &str) -> Result<Item> {
+    fn args(s: &str) -> Vec<&str> {
+        match s.trim() {
+            "" => Vec::new(),
+            s => s.split(',').map(str::trim).collect(),
+        }
+    }
+
+    let (mnemonic, rest) = s.split_once(char::is_whitespace).unwrap_or((s, ""));
+
+    if mnemonic == "data" {
+        let values = args(rest).into_iter().map(parse_expr).collect::<Result<_>>()?;
+        return Ok(Item::Data { values });
+    }
+
+    let op = Op::from_mnemonic(mnemonic).ok_or_else(|| AsmErrorKind::UnknownMnemonic {
+        mnemonic: mnemonic.to_owned(),
+    })?;
+    let mut params = match rest.split_once("->") {
+        Some(_) if !op.writes() => {
+            return Err(AsmErrorKind::UnexpectedArrow {
+                mnemonic: mnemonic.to_owned(),
+            })
+        }
+        Some((inputs, output)) => {
+            let mut params = args(inputs);
+            params.push(output.trim());
+            params
+        }
+        None => args(rest),
+    };
+    if params.len() != op.arity() {
+        return Err(AsmErrorKind::WrongArity {
+            mnemonic: mnemonic.to_owned(),
+            expected: op.arity(),
+            found: params.len(),
+        });
+    }
+    let params = params.drain(..).map(parse_param).collect::<Result<_>>()?;
+    Ok(Item::Instr { op, params })
+}
+
+fn parse_param(s: &str) -> Result<(Mode, Expr)> {
+    if let Some(value) = s.strip_prefix('#') {
+        return Ok((Mode::Immediate, parse_expr(value)?));
+    }
+    let Some(inner) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) else {
+        return Err(AsmErrorKind::BadParam { param: s.to_owned() });
+    };
+    match inner.trim().strip_prefix("rb") {
+        Some("") => Ok((Mode::Relative, Expr { terms: Vec::new() })),
+        Some(offset) if offset.trim_start().starts_with(['+', '-']) => {
+            Ok((Mode::Relative, parse_expr(offset)?))
+        }
+        _ => Ok((Mode::Position, parse_expr(inner)?)),
+    }
+}
+
+/// Parses a sum like `-1`, `label`, or `table+2-start`.
+fn parse_expr(s: &str) -> Result<Expr> {
+    let bad = || AsmErrorKind::BadExpr { expr: s.to_owned() };
+
+    let mut terms = Vec::new();
+    let mut rest = s.trim();
+    let mut sign = 1;
+    if let Some(r) = rest.strip_prefix('-') {
+        (sign, rest) = (-1, r);
+    } else if let Some(r) = rest.strip_prefix('+') {
+        rest = r;
+    }
+    loop {
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let term = rest[..end].trim();
+        let term = if let Ok(n) = term.parse() {
+            Term::Num(n)
+        } else if is_label(term) {
+            Term::Label(term.to_owned())
+        } else {
+            return Err(bad());
+        };
+        terms.push((sign, term));
+        match rest[end..].chars().next() {
+            None => break,
+            Some(c) => {
+                sign = if c == '-' { -1 } else { 1 };
+                rest = &rest[end + 1..];
+            }
+        }
+    }
+    Ok(Expr { terms })
+}
+
+fn is_label(s: &str) -> bool {
+    let mut chars = s.chars();
+    s != "rb"
+        && chars.next().map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Expr {
+    fn eval(&self, labels: &HashMap<String, usize>) -> Result<i64> {
+        self.terms.iter().try_fold(0, |acc, (sign, term)| {
+            let value = match term {
+                Term::Num(n) => *n,
+                Term::Label(label) => match labels.get(label) {
+                    Some(&addr) => addr as i64,
+                    None => {
+                        return Err(AsmErrorKind::UndefinedLabel {
+                            label: label.clone(),
+                        })
+                    }
+                },
+            };
+            Ok(acc + sign * value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::{disassemble, parse_program};
+
+    #[test]
+    fn assemble_basic() {
+        let src = "\
+        arb #stack          ; set up the stack
+        in -> [rb]
+loop:   jz [rb], #done
+        out [rb]
+        add [rb], #-1 -> [rb]
+        jnz #1, #loop
+done:   hlt
+stack:  data 0
+";
+        assert_eq!(
+            assemble(src).unwrap(),
+            [109, 17, 203, 0, 1206, 0, 16, 204, 0, 21201, 0, -1, 0, 1105, 1, 4, 99, 0]
+        );
+    }
+
+    #[test]
+    fn assemble_expressions() {
+        let src = "\
+0000:   add [table+1], #end-table -> [rb-2]
+0004:   mul [rb], #-3, [rb+table]
+        hlt
+table:  data 1, -2, table, end-1
+end:
+";
+        assert_eq!(
+            assemble(src).unwrap(),
+            [21001, 10, 4, -2, 21202, 0, -3, 9, 99, 1, -2, 9, 12]
+        );
+    }
+
+    #[test]
+    fn assemble_errors() {
+        let kind = |src: &str| assemble(src).unwrap_err().kind;
+        assert!(matches!(kind("nop"), AsmErrorKind::UnknownMnemonic { .. }));
+        assert!(matches!(kind("add #1, #2"), AsmErrorKind::WrongArity { .. }));
+        assert!(matches!(kind("out #1 -> [0]"), AsmErrorKind::UnexpectedArrow { .. }));
+        assert!(matches!(kind("out 1"), AsmErrorKind::BadParam { .. }));
+        assert!(matches!(kind("out #1+"), AsmErrorKind::BadExpr { .. }));
+        assert!(matches!(kind("out [x]"), AsmErrorKind::UndefinedLabel { .. }));
+        assert!(matches!(kind("x: x: hlt"), AsmErrorKind::DuplicateLabel { .. }));
+        assert!(matches!(
+            kind("hlt\n0002: hlt"),
+            AsmErrorKind::AddressMismatch { expected: 1, found: 2 }
+        ));
+        assert_eq!(assemble("hlt\n\nnop").unwrap_err().line, 3);
+    }
+
+    #[test]
+    fn round_trip() {
+        for program in [
+            include_str!("../bench/ackermann.intcode"),
+            include_str!("../bench/divmod.intcode"),
+            include_str!("../bench/factor.intcode"),
+            include_str!("../bench/isqrt.intcode"),
+            include_str!("../bench/sum-of-primes.intcode"),
+        ] {
+            let program: Vec<i64> = parse_program(program);
+            let src = disassemble(&program).to_string();
+            assert_eq!(assemble(&src).unwrap(), program);
+        }
+    }
+}
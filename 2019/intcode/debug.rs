@@ -0,0 +1,247 @@
+//! Debug intcode programs.
+//!
+//! [`Debugger`] wraps a [`Computer`] and executes it one instruction at a
+//! time, checking breakpoints and watchpoints and recording the most recently
+//! executed instructions. Nothing here slows down the computer itself.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use super::{Computer, Instr, Result, State};
+
+/// The default number of instructions kept in the trace.
+const TRACE_LEN: usize = 64;
+
+/// Something that happened while debugging.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// An instruction was executed.
+    Stepped,
+    /// An instruction output a value.
+    Yielded(i64),
+    /// Waiting for input, nothing was executed.
+    Waiting,
+    /// Program execution has finished.
+    Complete,
+    /// Execution stopped at a breakpoint, before executing the instruction.
+    Breakpoint { addr: usize },
+    /// A watched address was written to.
+    Watchpoint { addr: usize, old: i64, new: i64 },
+}
+
+/// An executed instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trace {
+    pub ip: usize,
+    pub relative_base: i64,
+    pub instr: Instr,
+}
+
+#[derive(Debug)]
+pub struct Debugger {
+    computer: Computer,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+    trace: VecDeque<Trace>,
+    trace_len: usize,
+    /// Whether execution is stopped at a breakpoint, so that continuing
+    /// doesn't immediately stop at it again.
+    at_breakpoint: bool,
+}
+
+impl Debugger {
+    pub fn new(computer: Computer) -> Self {
+        Self {
+            computer,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            trace: VecDeque::new(),
+            trace_len: TRACE_LEN,
+            at_breakpoint: false,
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    pub fn into_computer(self) -> Computer {
+        self.computer
+    }
+
+    /// Stops execution before the instruction at the given address. Returns
+    /// false if there was already a breakpoint there.
+    pub fn add_breakpoint(&mut self, addr: usize) -> bool {
+        self.breakpoints.insert(addr)
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: usize) -> bool {
+        self.breakpoints.remove(&addr)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Stops execution after any instruction that writes to the given
+    /// address. Returns false if the address was already watched.
+    pub fn add_watchpoint(&mut self, addr: usize) -> bool {
+        self.watchpoints.insert(addr)
+    }
+
+    pub fn remove_watchpoint(&mut self, addr: usize) -> bool {
+        self.watchpoints.remove(&addr)
+    }
+
+    pub fn watchpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.watchpoints.iter().copied()
+    }
+
+    /// The most recently executed instructions, oldest first.
+    pub fn trace(&self) -> impl Iterator<Item = &Trace> {
+        self.trace.iter()
+    }
+
+    /// Sets the number of instructions kept in the trace.
+    pub fn set_trace_len(&mut self, len: usize) {
+        self.trace_len = len;
+        while self.trace.len() > len {
+            self.trace.pop_front();
+        }
+    }
+
+    /// Executes a single instruction, ignoring breakpoints.
+    pub fn step(&mut self) -> Result<Event> {
+        self.at_breakpoint = false;
+        let c = &self.computer;
+        let instr = c.current_instr().map_err(|err| c.error(err.kind))?;
+        let trace = Trace {
+            ip: c.ip(),
+            relative_base: c.relative_base(),
+            instr,
+        };
+        let watched = if instr.op.writes() {
+            let addr = c.param_ptr(instr.op.arity()).map_err(|kind| c.error(kind))?;
+            Some(addr).filter(|a| self.watchpoints.contains(a))
+        } else {
+            None
+        };
+        let old = watched.map(|addr| c.peek(addr));
+
+        let state = self.computer.try_step()?;
+        if matches!(state, Some(State::Waiting | State::Complete)) {
+            // Nothing was executed
+        } else {
+            if self.trace.len() == self.trace_len {
+                self.trace.pop_front();
+            }
+            if self.trace_len > 0 {
+                self.trace.push_back(trace);
+            }
+        }
+
+        Ok(match (state, watched, old) {
+            (Some(State::Yielded(v)), _, _) => Event::Yielded(v),
+            (Some(State::Waiting), _, _) => Event::Waiting,
+            (Some(State::Complete), _, _) => Event::Complete,
+            (None, Some(addr), Some(old)) => Event::Watchpoint {
+                addr,
+                old,
+                new: self.computer.peek(addr),
+            },
+            (None, _, _) => Event::Stepped,
+        })
+    }
+
+    /// Executes instructions until something other than a plain step
+    /// happens: an output, waiting for input, completion, or a breakpoint or
+    /// watchpoint is hit.
+    pub fn run(&mut self) -> Result<Event> {
+        loop {
+            let ip = self.computer.ip();
+            if !self.at_breakpoint && self.breakpoints.contains(&ip) {
+                self.at_breakpoint = true;
+                break Ok(Event::Breakpoint { addr: ip });
+            }
+            match self.step()? {
+                Event::Stepped => {}
+                event => break Ok(event),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::assemble;
+
+    fn debugger(src: &str) -> Debugger {
+        Debugger::new(Computer::new(assemble(src).unwrap()))
+    }
+
+    #[test]
+    fn debugger_step() {
+        let mut dbg = debugger("add #1, #2 -> [7]\nout [7]\nhlt\ndata 0");
+        assert_eq!(dbg.step().unwrap(), Event::Stepped);
+        assert_eq!(dbg.computer().peek(7), 3);
+        assert_eq!(dbg.step().unwrap(), Event::Yielded(3));
+        assert_eq!(dbg.step().unwrap(), Event::Complete);
+        assert_eq!(dbg.computer().ip(), 6);
+        let ips: Vec<_> = dbg.trace().map(|t| t.ip).collect();
+        assert_eq!(ips, [0, 4]);
+    }
+
+    #[test]
+    fn debugger_breakpoints() {
+        let src = "\
+        arb #x
+loop:   add [rb], #1 -> [rb]
+        jnz #1, #loop
+x:      data 0
+";
+        let mut dbg = debugger(src);
+        dbg.add_breakpoint(2);
+        for _ in 0..3 {
+            assert_eq!(dbg.run().unwrap(), Event::Breakpoint { addr: 2 });
+        }
+        assert_eq!(dbg.computer().peek(9), 2);
+        assert!(dbg.remove_breakpoint(2));
+        dbg.add_breakpoint(6);
+        assert_eq!(dbg.run().unwrap(), Event::Breakpoint { addr: 6 });
+        assert_eq!(dbg.computer().peek(9), 3);
+    }
+
+    #[test]
+    fn debugger_watchpoints() {
+        let src = "\
+        in -> [x]
+        add [x], #0 -> [x]
+        mul [x], #2 -> [x]
+        out [x]
+        hlt
+x:      data 0
+";
+        let mut dbg = debugger(src);
+        dbg.add_watchpoint(13);
+        dbg.computer_mut().input(5);
+        assert_eq!(dbg.run().unwrap(), Event::Watchpoint { addr: 13, old: 0, new: 5 });
+        assert_eq!(dbg.run().unwrap(), Event::Watchpoint { addr: 13, old: 5, new: 5 });
+        assert_eq!(dbg.run().unwrap(), Event::Watchpoint { addr: 13, old: 5, new: 10 });
+        assert_eq!(dbg.run().unwrap(), Event::Yielded(10));
+        assert_eq!(dbg.run().unwrap(), Event::Complete);
+    }
+
+    #[test]
+    fn debugger_trace_len() {
+        let mut dbg = debugger("loop: jnz #1, #loop");
+        dbg.set_trace_len(3);
+        for _ in 0..10 {
+            dbg.step().unwrap();
+        }
+        assert_eq!(dbg.trace().count(), 3);
+    }
+}
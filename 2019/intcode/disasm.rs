@@ -0,0 +1,274 @@
+//! Disassemble intcode programs into something a human can read.
+//!
+//! Code is found by following execution from address 0. Jumps with immediate
+//! targets are followed, and so are immediate values pushed onto the stack
+//! (using relative mode) since that is how return addresses are passed to
+//! functions. Everything that isn't reached is shown as data.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::ops::Range;
+
+use super::{Instr, Mode, Op};
+
+/// A disassembled program.
+#[derive(Debug, Clone)]
+pub struct Disassembly {
+    pub lines: Vec<Line>,
+}
+
+/// A single line of a disassembled program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    /// An instruction and the addresses of the instructions that write into
+    /// it, if any.
+    Code {
+        addr: usize,
+        instr: Instr,
+        modified_by: Vec<usize>,
+    },
+    /// Values that are never executed.
+    Data { addr: usize, values: Vec<i64> },
+}
+
+/// The maximum number of values on a single data line.
+const DATA_WIDTH: usize = 8;
+
+/// Disassembles the given program.
+pub fn disassemble(program: &[i64]) -> Disassembly {
+    let code = find_code(program);
+
+    // Find all the static writes into code
+    let mut covered = BTreeMap::new();
+    for (&addr, instr) in &code {
+        for a in addr..addr + instr.len() {
+            covered.insert(a, addr);
+        }
+    }
+    let mut modified_by: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (&addr, instr) in &code {
+        if let Some(start) = write_target(addr, instr).and_then(|t| covered.get(&t)) {
+            modified_by.entry(*start).or_default().push(addr);
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut addr = 0;
+    while addr < program.len() {
+        match code.get(&addr) {
+            Some(&instr) => {
+                lines.push(Line::Code {
+                    addr,
+                    instr,
+                    modified_by: modified_by.remove(&addr).unwrap_or_default(),
+                });
+                addr += instr.len();
+            }
+            None => {
+                let end = (addr..program.len())
+                    .take(DATA_WIDTH)
+                    .find(|a| code.contains_key(a))
+                    .unwrap_or_else(|| (addr + DATA_WIDTH).min(program.len()));
+                lines.push(Line::Data {
+                    addr,
+                    values: program[addr..end].to_vec(),
+                });
+                addr = end;
+            }
+        }
+    }
+    Disassembly { lines }
+}
+
+/// Returns the instructions reachable from address 0, keyed by address.
+///
+/// Immediate parameters of jumps can be overwritten by the program itself, so
+/// this is repeated until the set of addresses written to by the code found so
+/// far stops growing, treating those parameters as unknown.
+fn find_code(program: &[i64]) -> BTreeMap<usize, Instr> {
+    let mut written = BTreeSet::new();
+    loop {
+        let code = walk(program, &written);
+        let len = written.len();
+        written.extend(code.iter().filter_map(|(&addr, instr)| write_target(addr, instr)));
+        if written.len() == len {
+            break code;
+        }
+    }
+}
+
+fn walk(program: &[i64], written: &BTreeSet<usize>) -> BTreeMap<usize, Instr> {
+    let mut code = BTreeMap::new();
+    let mut covered = BTreeSet::new();
+    let mut pushed = Vec::new();
+
+    let mut walk_from = |start: usize, pushed: &mut Vec<usize>| {
+        let mut stack = vec![start];
+        while let Some(addr) = stack.pop() {
+            if covered.contains(&addr) {
+                continue;
+            }
+            let Some(instr) = decode(program, addr) else {
+                continue;
+            };
+            if (addr..addr + instr.len()).any(|a| covered.contains(&a)) {
+                continue;
+            }
+            covered.extend(addr..addr + instr.len());
+            code.insert(addr, instr);
+
+            // The value of an immediate parameter, if it is never overwritten
+            let imm = |i: usize| {
+                let p = instr.params()[i];
+                (p.mode == Mode::Immediate && !written.contains(&(addr + i + 1))).then_some(p.value)
+            };
+            let target = |v: i64| usize::try_from(v).ok().filter(|&t| t < program.len());
+            let next = addr + instr.len();
+            match instr.op {
+                Op::Halt => {}
+                Op::JumpIfTrue | Op::JumpIfFalse => {
+                    let jumps = imm(0).map(|v| (v != 0) == (instr.op == Op::JumpIfTrue));
+                    if jumps != Some(true) {
+                        stack.push(next);
+                    }
+                    if jumps != Some(false) {
+                        stack.extend(imm(1).and_then(target));
+                    }
+                }
+                Op::Add | Op::Mul => {
+                    // Pushing a constant onto the stack is most likely a
+                    // return address.
+                    if instr.params()[2].mode == Mode::Relative {
+                        let value = match (instr.op, imm(0), imm(1)) {
+                            (Op::Add, Some(a), Some(b)) => Some(a + b),
+                            (Op::Mul, Some(a), Some(b)) => Some(a * b),
+                            _ => None,
+                        };
+                        pushed.extend(value.and_then(target));
+                    }
+                    stack.push(next);
+                }
+                _ => stack.push(next),
+            }
+        }
+    };
+
+    walk_from(0, &mut pushed);
+    // Return addresses are less trustworthy than control flow so they are
+    // only walked once everything else has been found.
+    while let Some(addr) = pushed.pop() {
+        walk_from(addr, &mut pushed);
+    }
+    code
+}
+
+/// Returns the address written to by the instruction if it is known without
+/// running the program.
+fn write_target(addr: usize, instr: &Instr) -> Option<usize> {
+    if !instr.op.writes() {
+        return None;
+    }
+    let i = instr.op.arity();
+    let param = instr.params()[i - 1];
+    match param.mode {
+        Mode::Relative => None,
+        mode => usize::try_from(mode.resolve(param.value, addr + i, 0)).ok(),
+    }
+}
+
+/// Decodes the instruction at the address if it is entirely inside the
+/// program and encodes back to exactly the same values.
+fn decode(program: &[i64], addr: usize) -> Option<Instr> {
+    let instr = Instr::decode(program, addr).ok()?;
+    let end = addr + instr.len();
+    (end <= program.len() && instr.encode() == program[addr..end]).then_some(instr)
+}
+
+impl Line {
+    /// The addresses covered by the line.
+    pub fn addrs(&self) -> Range<usize> {
+        match self {
+            Self::Code { addr, instr, .. } => *addr..addr + instr.len(),
+            Self::Data { addr, values } => *addr..addr + values.len(),
+        }
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Code {
+                addr,
+                instr,
+                modified_by,
+            } => {
+                write!(f, "{addr:04}: {instr}")?;
+                if !modified_by.is_empty() {
+                    let by: Vec<_> = modified_by.iter().map(|a| format!("{a:04}")).collect();
+                    write!(f, " ; modified by {}", by.join(", "))?;
+                }
+                Ok(())
+            }
+            Self::Data { addr, values } => {
+                let values: Vec<_> = values.iter().map(i64::to_string).collect();
+                write!(f, "{addr:04}: data {}", values.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Disassembly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::parse_program;
+
+    #[test]
+    fn disassemble_basic() {
+        let program = parse_program("1002,4,3,4,33");
+        assert_eq!(
+            disassemble(&program).to_string(),
+            "0000: mul [4], #3 -> [4]\n0004: data 33\n"
+        );
+    }
+
+    #[test]
+    fn disassemble_relative_and_jumps() {
+        let program = parse_program("109,5,21101,6,0,-1,1105,1,9,99");
+        assert_eq!(
+            disassemble(&program).to_string(),
+            "\
+0000: arb #5
+0002: add #6, #0 -> [rb-1]
+0006: jnz #1, #9
+0009: hlt
+"
+        );
+    }
+
+    #[test]
+    fn disassemble_self_modifying() {
+        let program = parse_program("1101,1,1,5,99,0");
+        assert_eq!(
+            disassemble(&program).to_string(),
+            "0000: add #1, #1 -> [5]\n0004: hlt\n0005: data 0\n"
+        );
+        let program = parse_program("1101,1,1,6,1101,0,0,0,99");
+        assert_eq!(
+            disassemble(&program).to_string(),
+            "\
+0000: add #1, #1 -> [6] ; modified by 0004
+0004: add #0, #0 -> [0] ; modified by 0000
+0008: hlt
+"
+        );
+    }
+}
@@ -0,0 +1,642 @@
+//! Run intcode programs by translating them into bytecode first.
+//!
+//! [`JitComputer`] translates each basic block of the program the first time
+//! it is executed into a list of instructions with their parameter modes
+//! already resolved, and then runs those instead of decoding every
+//! instruction every time. It keeps memory in a single flat vector rather than
+//! in pages.
+//!
+//! Intcode programs often keep variables in the parameters of their own
+//! instructions. A write to a parameter of translated code throws away all
+//! translations and marks the parameter as volatile, from then on it is read
+//! from memory when the instruction is executed. A comparison writing to the
+//! condition of the jump after it is translated together with the jump.
+//!
+//! A write to an opcode also throws away all translations, and a program that
+//! keeps doing that, or that writes far past the end of its memory, is handed
+//! over to the interpreter.
+
+use std::collections::VecDeque;
+use std::mem;
+use std::result;
+
+use super::{cast, Computer, ErrorKind, Instr, Mode, Op, Result, State};
+
+/// The number of times translations are thrown away because an opcode was
+/// written to before giving up and interpreting the program instead.
+const MAX_INVALIDATIONS: usize = 64;
+
+/// Writes past the end of memory grow it if they are within this many values,
+/// or twice its length, whichever is larger.
+const GROW_MIN: usize = 1 << 16;
+
+/// A parameter that is read from.
+#[derive(Debug, Clone, Copy)]
+enum Src {
+    Imm(i64),
+    Pos(usize),
+    Rel(i64),
+    /// A volatile parameter in position mode, at the address.
+    PosAt(usize),
+    /// A volatile parameter in relative mode, at the address.
+    RelAt(usize),
+}
+
+/// A parameter that is written to.
+#[derive(Debug, Clone, Copy)]
+enum Dst {
+    Pos(usize),
+    Rel(i64),
+    PosAt(usize),
+    RelAt(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Insn {
+    Add(Src, Src, Dst),
+    Mul(Src, Src, Dst),
+    LessThan(Src, Src, Dst),
+    Equals(Src, Src, Dst),
+    AdjustBase(Src),
+    JumpIfTrue(Src, Src),
+    JumpIfFalse(Src, Src),
+    /// A comparison writing to the volatile condition at the address of the
+    /// jump after it, and the jump, which is taken if the condition equals
+    /// the flag.
+    LessThanJump(Src, Src, usize, bool, usize),
+    EqualsJump(Src, Src, usize, bool, usize),
+    Input(Dst),
+    Output(Src),
+    Halt,
+}
+
+/// What to do after executing an instruction.
+enum Flow {
+    Next,
+    Jump(usize),
+    /// Translated code was written to, or memory was written to far past the
+    /// end.
+    Leave,
+    Yield(i64),
+    Wait,
+    Halt,
+}
+
+/// What an address holds as far as translated code is concerned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Cell {
+    #[default]
+    Data,
+    Opcode,
+    /// A parameter whose value was translated.
+    Param,
+    /// A parameter that is read when the instruction is executed.
+    Volatile,
+}
+
+/// A translated basic block.
+#[derive(Debug, Clone)]
+struct Block {
+    /// Each instruction and its address.
+    insns: Vec<(usize, Insn)>,
+    /// The address after the last instruction.
+    end: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Code {
+    blocks: Vec<Block>,
+    /// The index of the block starting at each address, plus one.
+    starts: Vec<u32>,
+    /// The parameters that have been written to, these are kept when
+    /// translations are thrown away.
+    volatile: Vec<bool>,
+}
+
+/// The state translated code runs on.
+#[derive(Debug, Clone)]
+struct Cpu {
+    mem: Vec<i64>,
+    /// What each address in memory holds.
+    cells: Vec<Cell>,
+    relative_base: i64,
+    input: VecDeque<i64>,
+    /// The translated opcode or parameter that was written to.
+    modified: Option<usize>,
+    /// A write too far past the end of memory, left to the interpreter.
+    far: Option<(usize, i64)>,
+}
+
+/// A computer that translates the program before running it.
+///
+/// It behaves the same as [`Computer`] but doesn't support snapshots, stats
+/// or tracing, use [`into_computer`][Self::into_computer] for those.
+#[derive(Debug, Clone)]
+pub struct JitComputer {
+    cpu: Cpu,
+    ptr: usize,
+    complete: bool,
+    code: Code,
+    invalidations: usize,
+    /// The interpreter, once execution has been handed over to it.
+    interpreter: Option<Computer>,
+}
+
+impl Src {
+    /// Returns the source given the mode and value of the parameter at the
+    /// address, and whether it is volatile.
+    fn new(mode: Mode, value: i64, addr: usize, volatile: bool) -> Option<Self> {
+        Some(match (mode, volatile) {
+            (Mode::Position, false) => Self::Pos(usize::try_from(value).ok()?),
+            (Mode::Immediate, false) => Self::Imm(value),
+            (Mode::Relative, false) => Self::Rel(value),
+            (Mode::Position, true) => Self::PosAt(addr),
+            (Mode::Immediate, true) => Self::Pos(addr),
+            (Mode::Relative, true) => Self::RelAt(addr),
+        })
+    }
+}
+
+impl Dst {
+    /// Returns the destination given the mode and value of the parameter at
+    /// the address, and whether it is volatile.
+    fn new(mode: Mode, value: i64, addr: usize, volatile: bool) -> Option<Self> {
+        Some(match (mode, volatile) {
+            (Mode::Position, false) => Self::Pos(usize::try_from(value).ok()?),
+            (Mode::Immediate, _) => Self::Pos(addr),
+            (Mode::Relative, false) => Self::Rel(value),
+            (Mode::Position, true) => Self::PosAt(addr),
+            (Mode::Relative, true) => Self::RelAt(addr),
+        })
+    }
+}
+
+impl Insn {
+    /// Translates the instruction at the address. Returns `None` for
+    /// instructions that always fail, these are left to the interpreter.
+    fn translate(addr: usize, instr: &Instr, volatile: impl Fn(usize) -> bool) -> Option<Self> {
+        let p = instr.params();
+        let src = |i: usize| Src::new(p[i].mode, p[i].value, addr + i + 1, volatile(addr + i + 1));
+        let dst = |i: usize| Dst::new(p[i].mode, p[i].value, addr + i + 1, volatile(addr + i + 1));
+        Some(match instr.op {
+            Op::Add => Self::Add(src(0)?, src(1)?, dst(2)?),
+            Op::Mul => Self::Mul(src(0)?, src(1)?, dst(2)?),
+            Op::LessThan => Self::LessThan(src(0)?, src(1)?, dst(2)?),
+            Op::Equals => Self::Equals(src(0)?, src(1)?, dst(2)?),
+            Op::AdjustBase => Self::AdjustBase(src(0)?),
+            Op::JumpIfTrue => Self::JumpIfTrue(src(0)?, src(1)?),
+            Op::JumpIfFalse => Self::JumpIfFalse(src(0)?, src(1)?),
+            Op::Input => Self::Input(dst(0)?),
+            Op::Output => Self::Output(src(0)?),
+            Op::Halt => Self::Halt,
+        })
+    }
+
+    /// Whether this instruction ends a basic block.
+    fn ends_block(&self) -> bool {
+        matches!(
+            self,
+            Self::JumpIfTrue(..)
+                | Self::JumpIfFalse(..)
+                | Self::LessThanJump(..)
+                | Self::EqualsJump(..)
+                | Self::Input(_)
+                | Self::Output(_)
+                | Self::Halt
+        )
+    }
+}
+
+impl Code {
+    /// Translates the block starting at the address if it hasn't been.
+    /// Returns false if the instruction there can't be translated.
+    fn translate(&mut self, cpu: &mut Cpu, ip: usize) -> bool {
+        if self.block(ip).is_some() {
+            return true;
+        }
+        let volatile = |a: usize| self.volatile.get(a).copied().unwrap_or(false);
+        let mut insns: Vec<(usize, Insn)> = Vec::new();
+        let mut addr = ip;
+        while let Ok(instr) = Instr::decode(&cpu.mem, addr) {
+            let Some(insn) = Insn::translate(addr, &instr, volatile) else {
+                break;
+            };
+            if addr + instr.len() > cpu.mem.len() {
+                break;
+            }
+            let jump = match insn {
+                Insn::JumpIfTrue(Src::Pos(c), Src::Imm(t)) => Some((c, true, t)),
+                Insn::JumpIfFalse(Src::Pos(c), Src::Imm(t)) => Some((c, false, t)),
+                _ => None,
+            };
+            match (insns.last_mut(), jump) {
+                (Some((_, prev)), Some((c, when, t))) if c == addr + 1 && volatile(c) && t >= 0 => {
+                    match *prev {
+                        Insn::LessThan(a, b, Dst::Pos(d)) if d == c => {
+                            *prev = Insn::LessThanJump(a, b, c, when, t as usize);
+                        }
+                        Insn::Equals(a, b, Dst::Pos(d)) if d == c => {
+                            *prev = Insn::EqualsJump(a, b, c, when, t as usize);
+                        }
+                        _ => insns.push((addr, insn)),
+                    }
+                }
+                _ => insns.push((addr, insn)),
+            }
+            cpu.cells[addr] = Cell::Opcode;
+            for a in addr + 1..addr + instr.len() {
+                cpu.cells[a] = if volatile(a) { Cell::Volatile } else { Cell::Param };
+            }
+            addr += instr.len();
+            if insn.ends_block() {
+                break;
+            }
+        }
+        if insns.is_empty() {
+            return false;
+        }
+        if self.starts.len() <= ip {
+            self.starts.resize(ip + 1, 0);
+        }
+        self.blocks.push(Block { insns, end: addr });
+        self.starts[ip] = self.blocks.len() as u32;
+        true
+    }
+
+    /// Returns the translated block starting at the address.
+    #[inline(always)]
+    fn block(&self, ip: usize) -> Option<&Block> {
+        match self.starts.get(ip) {
+            Some(&b) if b != 0 => Some(&self.blocks[b as usize - 1]),
+            _ => None,
+        }
+    }
+
+    /// Throws away all translations after the address was written to,
+    /// returning whether it was an opcode.
+    fn invalidate(&mut self, cells: &mut [Cell], addr: usize) -> bool {
+        let opcode = cells[addr] == Cell::Opcode;
+        if !opcode {
+            if self.volatile.len() <= addr {
+                self.volatile.resize(addr + 1, false);
+            }
+            self.volatile[addr] = true;
+        }
+        self.blocks.clear();
+        self.starts.clear();
+        cells.fill(Cell::Data);
+        opcode
+    }
+}
+
+impl Cpu {
+    #[inline(always)]
+    fn get(&self, addr: usize) -> i64 {
+        self.mem.get(addr).copied().unwrap_or(0)
+    }
+
+    #[inline(always)]
+    fn read(&self, src: Src) -> result::Result<i64, ErrorKind> {
+        match src {
+            Src::Imm(v) => Ok(v),
+            Src::Pos(addr) => Ok(self.get(addr)),
+            Src::Rel(offset) => Ok(self.get(cast(self.relative_base + offset)?)),
+            Src::PosAt(addr) => Ok(self.get(cast(self.get(addr))?)),
+            Src::RelAt(addr) => Ok(self.get(cast(self.relative_base + self.get(addr))?)),
+        }
+    }
+
+    /// Writes the value, returning whether translated code must be left
+    /// because it was written to, or because the address was too far past the
+    /// end of memory and the write has been left to the interpreter.
+    #[inline(always)]
+    fn set(&mut self, addr: usize, value: i64) -> bool {
+        match self.mem.get_mut(addr) {
+            Some(v) => {
+                *v = value;
+                if matches!(self.cells[addr], Cell::Opcode | Cell::Param) {
+                    self.modified = Some(addr);
+                    return true;
+                }
+                false
+            }
+            None => self.grow(addr, value),
+        }
+    }
+
+    #[inline(always)]
+    fn write(&mut self, dst: Dst, value: i64) -> result::Result<bool, ErrorKind> {
+        let addr = match dst {
+            Dst::Pos(addr) => addr,
+            Dst::Rel(offset) => cast(self.relative_base + offset)?,
+            Dst::PosAt(addr) => cast(self.get(addr))?,
+            Dst::RelAt(addr) => cast(self.relative_base + self.get(addr))?,
+        };
+        Ok(self.set(addr, value))
+    }
+
+    /// Writes past the end of memory, returning whether the write was too far
+    /// away and has been left to the interpreter.
+    #[cold]
+    #[inline(never)]
+    fn grow(&mut self, addr: usize, value: i64) -> bool {
+        if addr >= GROW_MIN.max(2 * self.mem.len()) {
+            self.far = Some((addr, value));
+            return true;
+        }
+        self.mem.resize(addr + 1, 0);
+        self.cells.resize(addr + 1, Cell::Data);
+        self.mem[addr] = value;
+        false
+    }
+
+    /// Writes the result of a comparison to the condition of the jump after
+    /// it and returns whether the jump is taken.
+    #[inline(always)]
+    fn compare_jump(&mut self, result: bool, cond: usize, when: bool, target: usize) -> Flow {
+        self.mem[cond] = result as i64;
+        if result == when {
+            Flow::Jump(target)
+        } else {
+            Flow::Next
+        }
+    }
+
+    #[inline(always)]
+    fn exec(&mut self, insn: Insn) -> result::Result<Flow, ErrorKind> {
+        let leave = match insn {
+            Insn::Add(a, b, d) => self.write(d, self.read(a)? + self.read(b)?)?,
+            Insn::Mul(a, b, d) => self.write(d, self.read(a)? * self.read(b)?)?,
+            Insn::LessThan(a, b, d) => self.write(d, (self.read(a)? < self.read(b)?) as i64)?,
+            Insn::Equals(a, b, d) => self.write(d, (self.read(a)? == self.read(b)?) as i64)?,
+            Insn::AdjustBase(a) => {
+                self.relative_base += self.read(a)?;
+                false
+            }
+            Insn::JumpIfTrue(a, t) => {
+                if self.read(a)? != 0 {
+                    return Ok(Flow::Jump(cast(self.read(t)?)?));
+                }
+                false
+            }
+            Insn::JumpIfFalse(a, t) => {
+                if self.read(a)? == 0 {
+                    return Ok(Flow::Jump(cast(self.read(t)?)?));
+                }
+                false
+            }
+            Insn::LessThanJump(a, b, cond, when, target) => {
+                let result = self.read(a)? < self.read(b)?;
+                return Ok(self.compare_jump(result, cond, when, target));
+            }
+            Insn::EqualsJump(a, b, cond, when, target) => {
+                let result = self.read(a)? == self.read(b)?;
+                return Ok(self.compare_jump(result, cond, when, target));
+            }
+            Insn::Input(d) => match self.input.pop_front() {
+                Some(v) => self.write(d, v)?,
+                None => return Ok(Flow::Wait),
+            },
+            Insn::Output(a) => return Ok(Flow::Yield(self.read(a)?)),
+            Insn::Halt => return Ok(Flow::Halt),
+        };
+        Ok(if leave { Flow::Leave } else { Flow::Next })
+    }
+}
+
+impl JitComputer {
+    pub fn new(program: Vec<i64>) -> Self {
+        Self {
+            cpu: Cpu {
+                cells: vec![Cell::Data; program.len()],
+                mem: program,
+                relative_base: 0,
+                input: VecDeque::new(),
+                modified: None,
+                far: None,
+            },
+            ptr: 0,
+            complete: false,
+            code: Code::default(),
+            invalidations: 0,
+            interpreter: None,
+        }
+    }
+
+    /// Returns an interpreting computer in the same state.
+    pub fn to_computer(&self) -> Computer {
+        match &self.interpreter {
+            Some(computer) => computer.clone(),
+            None => self.clone().into_computer(),
+        }
+    }
+
+    pub fn into_computer(self) -> Computer {
+        if let Some(computer) = self.interpreter {
+            return computer;
+        }
+        let Cpu { mem, relative_base, input, far, .. } = self.cpu;
+        let mut computer = Computer::new(mem);
+        computer.ptr = self.ptr;
+        computer.relative_base = relative_base;
+        computer.input = input;
+        computer.complete = self.complete;
+        if let Some((addr, value)) = far {
+            computer.poke(addr, value);
+        }
+        computer
+    }
+
+    /// Whether execution has been handed over to the interpreter.
+    pub fn is_interpreted(&self) -> bool {
+        self.interpreter.is_some()
+    }
+
+    pub fn input(&mut self, value: i64) -> &mut Self {
+        match &mut self.interpreter {
+            Some(computer) => {
+                computer.input(value);
+            }
+            None => self.cpu.input.push_back(value),
+        }
+        self
+    }
+
+    pub fn feed(&mut self, iter: impl IntoIterator<Item = i64>) {
+        match &mut self.interpreter {
+            Some(computer) => computer.feed(iter),
+            None => self.cpu.input.extend(iter),
+        }
+    }
+
+    /// The address of the next instruction.
+    pub fn ip(&self) -> usize {
+        match &self.interpreter {
+            Some(computer) => computer.ip(),
+            None => self.ptr,
+        }
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        match &self.interpreter {
+            Some(computer) => computer.relative_base(),
+            None => self.cpu.relative_base,
+        }
+    }
+
+    /// Reads the value at the given address.
+    pub fn peek(&self, addr: usize) -> i64 {
+        match &self.interpreter {
+            Some(computer) => computer.peek(addr),
+            None => self.cpu.get(addr),
+        }
+    }
+
+    /// Writes a value to the given address.
+    pub fn poke(&mut self, addr: usize, value: i64) {
+        match &mut self.interpreter {
+            Some(computer) => computer.poke(addr, value),
+            None => {
+                if self.cpu.set(addr, value) {
+                    self.left();
+                }
+            }
+        }
+    }
+
+    /// Deals with a write that made translated code be left, if any.
+    fn left(&mut self) {
+        if self.cpu.far.is_some() {
+            self.interpret();
+        } else if let Some(addr) = self.cpu.modified.take() {
+            self.invalidate(addr);
+        }
+    }
+
+    /// Throws away all translations after the address was written to,
+    /// handing over to the interpreter if opcodes are written to too often.
+    fn invalidate(&mut self, addr: usize) {
+        if self.code.invalidate(&mut self.cpu.cells, addr) {
+            self.invalidations += 1;
+            if self.invalidations > MAX_INVALIDATIONS {
+                self.interpret();
+            }
+        }
+    }
+
+    /// Hands execution over to the interpreter.
+    fn interpret(&mut self) {
+        if self.interpreter.is_none() {
+            let computer = mem::replace(self, Self::new(Vec::new())).into_computer();
+            self.interpreter = Some(computer);
+        }
+    }
+
+    /// Runs translated blocks until the program stops, or reaches code that
+    /// hasn't been translated, or translated code must be left.
+    fn run_blocks(&mut self) -> Result<Option<State>> {
+        let Self { cpu, ptr, code, .. } = self;
+        while let Some(block) = code.block(*ptr) {
+            *ptr = block.end;
+            for (i, &(addr, insn)) in block.insns.iter().enumerate() {
+                match cpu.exec(insn) {
+                    Ok(Flow::Next) => {}
+                    Ok(Flow::Jump(target)) => {
+                        *ptr = target;
+                        break;
+                    }
+                    Ok(Flow::Leave) => {
+                        *ptr = block.insns.get(i + 1).map_or(block.end, |&(a, _)| a);
+                        return Ok(None);
+                    }
+                    Ok(Flow::Yield(v)) => return Ok(Some(State::Yielded(v))),
+                    Ok(Flow::Wait) => {
+                        *ptr = addr;
+                        return Ok(Some(State::Waiting));
+                    }
+                    Ok(Flow::Halt) => {
+                        *ptr = addr;
+                        self.complete = true;
+                        return Ok(Some(State::Complete));
+                    }
+                    Err(kind) => {
+                        *ptr = addr;
+                        return Err(self.to_computer().error(kind));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn try_next_state(&mut self) -> Result<State> {
+        loop {
+            if let Some(computer) = &mut self.interpreter {
+                return computer.try_next_state();
+            }
+            if !self.code.translate(&mut self.cpu, self.ptr) {
+                // The instruction will almost certainly fail, leave it to the
+                // interpreter to report
+                self.interpret();
+                continue;
+            }
+            if let Some(state) = self.run_blocks()? {
+                return Ok(state);
+            }
+            self.left();
+        }
+    }
+
+    #[track_caller]
+    pub fn next_state(&mut self) -> State {
+        match self.try_next_state() {
+            Ok(state) => state,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    #[track_caller]
+    pub fn next(&mut self) -> Option<i64> {
+        match self.next_state() {
+            State::Yielded(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    #[track_caller]
+    pub fn read_line(&mut self) -> Option<String> {
+        let mut line = String::new();
+        loop {
+            let v = self.next()?;
+            assert!(v < 127, "unexpected non-ascii value `{v}`");
+            match v as u8 as char {
+                '\n' => break Some(line),
+                c => line.push(c),
+            }
+        }
+    }
+
+    pub fn write_line(&mut self, line: &str) {
+        assert!(line.is_ascii());
+        self.feed(line.bytes().map(i64::from));
+        self.input(b'\n' as i64);
+    }
+}
+
+/// Computers with stats, a tracer or memory far past the end of the program
+/// stay with the interpreter.
+impl From<Computer> for JitComputer {
+    fn from(computer: Computer) -> Self {
+        if computer.hooks.0.is_some() || !computer.mem.is_dense() {
+            let mut jit = Self::new(Vec::new());
+            jit.interpreter = Some(computer);
+            return jit;
+        }
+        let mut jit = Self::new(computer.mem.to_vec());
+        jit.ptr = computer.ptr;
+        jit.cpu.relative_base = computer.relative_base;
+        jit.cpu.input = computer.input;
+        jit.complete = computer.complete;
+        jit
+    }
+}
@@ -0,0 +1,317 @@
+//! The memory of the computer.
+//!
+//! Memory is split into fixed size pages covering the program and anything
+//! written near it, and a sparse map for addresses far past the end, so a
+//! program writing to a huge address doesn't allocate everything before it.
+//! Pages are reference counted and copied on write, so cloning memory is cheap
+//! and clones only pay for the pages they change.
+//!
+//! Decoded opcodes are cached alongside the pages, an entry is cleared whenever
+//! the address is written to. The cache belongs to a single memory and isn't
+//! cloned with it, so running code never copies a page shared with a clone.
+
+use std::cmp::max;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use super::{ErrorKind, Opcode};
+
+const PAGE_BITS: usize = 9;
+const PAGE_SIZE: usize = 1 << PAGE_BITS;
+const PAGE_MASK: usize = PAGE_SIZE - 1;
+
+/// Writes past the end of dense memory grow it if they are within this many
+/// values, or twice its length, whichever is larger.
+const DENSE_MIN: usize = 1 << 16;
+
+#[derive(Debug, Clone)]
+struct Page {
+    values: [i64; PAGE_SIZE],
+}
+
+/// The decoded opcodes for a page.
+type Decoded = Box<[Option<Opcode>; PAGE_SIZE]>;
+
+#[derive(Debug)]
+pub(super) struct Memory {
+    pages: Vec<Rc<Page>>,
+    /// The length of the dense part of memory.
+    len: usize,
+    sparse: HashMap<usize, i64>,
+    /// The decoded opcodes for each page, allocated when code on the page is
+    /// first run.
+    decoded: Vec<Option<Decoded>>,
+}
+
+impl Page {
+    fn new() -> Rc<Self> {
+        Rc::new(Self {
+            values: [0; PAGE_SIZE],
+        })
+    }
+}
+
+impl Memory {
+    pub fn new(program: Vec<i64>) -> Self {
+        let pages = program
+            .chunks(PAGE_SIZE)
+            .map(|chunk| {
+                let mut page = Page::new();
+                Rc::make_mut(&mut page).values[..chunk.len()].copy_from_slice(chunk);
+                page
+            })
+            .collect();
+        Self {
+            pages,
+            len: program.len(),
+            sparse: HashMap::new(),
+            decoded: Vec::new(),
+        }
+    }
+
+    /// Returns a copy of the dense part of memory.
+    pub fn to_vec(&self) -> Vec<i64> {
+        self.pages
+            .iter()
+            .flat_map(|page| page.values.iter().copied())
+            .take(self.len)
+            .collect()
+    }
+
+    /// Whether nothing has been written far past the end of dense memory.
+    pub fn is_dense(&self) -> bool {
+        self.sparse.is_empty()
+    }
+
+    #[inline]
+    pub fn get(&self, addr: usize) -> i64 {
+        match self.pages.get(addr >> PAGE_BITS) {
+            Some(page) => page.values[addr & PAGE_MASK],
+            None => self.get_sparse(addr),
+        }
+    }
+
+    #[cold]
+    fn get_sparse(&self, addr: usize) -> i64 {
+        if self.sparse.is_empty() {
+            return 0;
+        }
+        self.sparse.get(&addr).copied().unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn set(&mut self, addr: usize, value: i64) {
+        if addr < self.len {
+            if let Some(page) = self.pages.get_mut(addr >> PAGE_BITS).and_then(Rc::get_mut) {
+                page.values[addr & PAGE_MASK] = value;
+                self.forget(addr);
+                return;
+            }
+        }
+        self.set_slow(addr, value);
+    }
+
+    /// Writes to a shared page, past the end of dense memory, or to sparse
+    /// memory.
+    #[cold]
+    fn set_slow(&mut self, addr: usize, value: i64) {
+        if let Some(page) = self.pages.get_mut(addr >> PAGE_BITS) {
+            Rc::make_mut(page).values[addr & PAGE_MASK] = value;
+            self.forget(addr);
+            self.len = max(self.len, addr + 1);
+            return;
+        }
+        if addr >= max(DENSE_MIN, 2 * self.len) {
+            self.sparse.insert(addr, value);
+            return;
+        }
+        self.pages.resize_with((addr >> PAGE_BITS) + 1, Page::new);
+        let end = self.pages.len() * PAGE_SIZE;
+        let moved: Vec<_> = self.sparse.iter().filter(|(&a, _)| a < end).map(|(&a, &v)| (a, v)).collect();
+        for (a, v) in moved {
+            self.sparse.remove(&a);
+            self.set(a, v);
+        }
+        self.set(addr, value);
+    }
+
+    /// Clears the cached opcode for the address after it is written to.
+    #[inline]
+    fn forget(&mut self, addr: usize) {
+        if let Some(Some(decoded)) = self.decoded.get_mut(addr >> PAGE_BITS) {
+            decoded[addr & PAGE_MASK] = None;
+        }
+    }
+
+    /// Returns the opcode at the given address and the three values after it,
+    /// i.e. the parameters of the instruction there.
+    #[inline]
+    pub fn fetch(&mut self, addr: usize) -> Result<(Opcode, [i64; 3]), ErrorKind> {
+        let (p, i) = (addr >> PAGE_BITS, addr & PAGE_MASK);
+        if let (Some(page), Some(Some(decoded))) = (self.pages.get(p), self.decoded.get(p)) {
+            if let (Some(opcode), true) = (decoded[i], i + 3 < PAGE_SIZE) {
+                let params = [page.values[i + 1], page.values[i + 2], page.values[i + 3]];
+                return Ok((opcode, params));
+            }
+        }
+        let params = [self.get(addr + 1), self.get(addr + 2), self.get(addr + 3)];
+        Ok((self.opcode(addr)?, params))
+    }
+
+    /// Returns the opcode at the given address, decoding it if it hasn't been
+    /// since it was last written to.
+    fn opcode(&mut self, addr: usize) -> Result<Opcode, ErrorKind> {
+        let (p, i) = (addr >> PAGE_BITS, addr & PAGE_MASK);
+        let Some(page) = self.pages.get(p) else {
+            return Opcode::decode(self.get(addr));
+        };
+        if self.decoded.len() <= p {
+            self.decoded.resize_with(p + 1, || None);
+        }
+        let decoded = self.decoded[p].get_or_insert_with(|| Box::new([None; PAGE_SIZE]));
+        match decoded[i] {
+            Some(opcode) => Ok(opcode),
+            None => {
+                let opcode = Opcode::decode(page.values[i])?;
+                decoded[i] = Some(opcode);
+                Ok(opcode)
+            }
+        }
+    }
+
+    /// The non-zero values ordered by address, dense memory followed by sparse
+    /// memory which is always further along.
+    fn values(&self) -> impl Iterator<Item = (usize, i64)> + '_ {
+        let mut sparse: Vec<_> = self.sparse.iter().map(|(&a, &v)| (a, v)).collect();
+        sparse.sort_unstable();
+        let dense = (0..self.len).map(|a| (a, self.get(a)));
+        dense.chain(sparse).filter(|&(_, v)| v != 0)
+    }
+}
+
+/// Clones share pages with the original, but start with an empty opcode cache
+/// so that filling either cache doesn't copy anything.
+impl Clone for Memory {
+    fn clone(&self) -> Self {
+        Self {
+            pages: self.pages.clone(),
+            len: self.len,
+            sparse: self.sparse.clone(),
+            decoded: Vec::new(),
+        }
+    }
+}
+
+/// Memory is equal if it has the same values, regardless of whether they are
+/// stored in dense or sparse memory, which pages are shared, or what has been
+/// decoded.
+impl PartialEq for Memory {
+    fn eq(&self, other: &Self) -> bool {
+        self.values().eq(other.values())
+    }
+}
+
+impl Eq for Memory {}
+
+impl Hash for Memory {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for value in self.values() {
+            value.hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_sparse() {
+        let mut mem = Memory::new(vec![1, 2, 3]);
+        mem.set(10, 4);
+        assert_eq!(mem.to_vec(), [1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 4]);
+        mem.set(1 << 40, 5);
+        mem.set(DENSE_MIN + 5, 6);
+        assert_eq!(mem.pages.len(), 1);
+        assert_eq!(mem.get(1 << 40), 5);
+        assert_eq!(mem.get(DENSE_MIN + 5), 6);
+        assert_eq!(mem.get(DENSE_MIN + 6), 0);
+
+        // Growing dense memory moves sparse values into it
+        mem.set(DENSE_MIN - 1, 7);
+        assert_eq!(mem.to_vec().len(), DENSE_MIN);
+        mem.set(DENSE_MIN * 2 - 1, 8);
+        assert_eq!(mem.to_vec().len(), DENSE_MIN * 2);
+        assert_eq!(mem.to_vec()[DENSE_MIN + 5], 6);
+        assert!(mem.sparse.get(&(DENSE_MIN + 5)).is_none());
+        assert_eq!(mem.get(DENSE_MIN + 5), 6);
+        assert_eq!(mem.get(1 << 40), 5);
+    }
+
+    #[test]
+    fn memory_opcode_cache() {
+        let mut mem = Memory::new(vec![1002, 0, 0, 0]);
+        assert_eq!(mem.fetch(0).unwrap(), (Opcode::decode(1002).unwrap(), [0, 0, 0]));
+        mem.set(0, 21101);
+        mem.set(1, 5);
+        assert_eq!(mem.fetch(0).unwrap(), (Opcode::decode(21101).unwrap(), [5, 0, 0]));
+        mem.set(1 << 40, 99);
+        assert_eq!(mem.fetch(1 << 40).unwrap(), (Opcode::decode(99).unwrap(), [0, 0, 0]));
+    }
+
+    #[test]
+    fn memory_copy_on_write() {
+        let mut a = Memory::new((0..2 * PAGE_SIZE as i64).collect());
+        let mut b = a.clone();
+        b.set(PAGE_SIZE, -1);
+        assert!(Rc::ptr_eq(&a.pages[0], &b.pages[0]));
+        assert!(!Rc::ptr_eq(&a.pages[1], &b.pages[1]));
+        assert_eq!(a.get(PAGE_SIZE), PAGE_SIZE as i64);
+        assert_eq!(b.get(PAGE_SIZE), -1);
+        a.set(0, 7);
+        assert_eq!(b.get(0), 0);
+    }
+
+    #[test]
+    fn memory_eq_ignores_representation() {
+        let a = Memory::new(vec![1, 2, 3]);
+        let mut b = Memory::new(vec![1, 2, 3, 0, 0]);
+        assert_eq!(a, b);
+        b.set(1 << 40, 0);
+        b.opcode(0).unwrap();
+        assert_eq!(a, b);
+        b.set(2, 4);
+        assert_ne!(a, b);
+
+        // The same value held in sparse memory and in dense memory after it
+        // has grown
+        let hash = |mem: &Memory| {
+            let mut h = std::collections::hash_map::DefaultHasher::new();
+            mem.hash(&mut h);
+            h.finish()
+        };
+        let mut a = Memory::new(vec![1, 2, 3]);
+        a.set(DENSE_MIN + 5, 6);
+        let mut b = Memory::new(vec![1, 2, 3]);
+        b.set(DENSE_MIN - 1, 7);
+        b.set(DENSE_MIN - 1, 0);
+        b.set(DENSE_MIN + 5, 6);
+        assert!(!a.is_dense() && b.is_dense());
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn memory_decoding_keeps_pages_shared() {
+        let mut a = Memory::new(vec![1002, 0, 0, 0]);
+        a.fetch(0).unwrap();
+        let mut b = a.clone();
+        b.fetch(0).unwrap();
+        a.fetch(0).unwrap();
+        assert!(Rc::ptr_eq(&a.pages[0], &b.pages[0]));
+        b.set(0, 99);
+        assert_eq!(a.fetch(0).unwrap().0, Opcode::decode(1002).unwrap());
+        assert_eq!(b.fetch(0).unwrap().0, Opcode::decode(99).unwrap());
+    }
+}
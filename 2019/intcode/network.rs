@@ -0,0 +1,290 @@
+//! Run several computers wired together.
+//!
+//! A [`Network`] schedules its machines round-robin, running each one until it
+//! waits for input or completes, and delivers everything they output according
+//! to how each machine is [linked][Link]. Control returns to the caller when
+//! a value leaves the network, or when nothing can make progress without
+//! outside input, so the caller can act as a NAT or supervisor.
+
+use std::collections::VecDeque;
+
+use super::{Computer, Result, State};
+
+/// Where the output of a machine goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Link {
+    /// Every value is sent to the input of the machine at the index.
+    Machine(usize),
+    /// Every value is returned from [`Network::run`].
+    Output,
+    /// Values are grouped into packets of an address followed by `len`
+    /// values. The values are sent to the machine at the address, packets for
+    /// any other address are returned from [`Network::run`].
+    Packets { len: usize },
+}
+
+/// A packet sent to an address without a machine.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Packet {
+    /// The machine that sent the packet.
+    pub from: usize,
+    pub addr: i64,
+    pub values: Vec<i64>,
+}
+
+/// Why the network stopped running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// A machine linked to [`Link::Output`] output a value.
+    Output { from: usize, value: i64 },
+    /// A packet was sent to an address without a machine.
+    Packet(Packet),
+    /// Every machine is waiting for input or complete, and nothing is in
+    /// flight. In a pipeline or ring this means the machines are deadlocked.
+    Idle,
+    /// Every machine has completed.
+    Complete,
+}
+
+#[derive(Debug)]
+struct Machine {
+    computer: Computer,
+    link: Link,
+    /// Values output towards a packet that hasn't been completed yet.
+    partial: Vec<i64>,
+    /// The most recent value output.
+    last_output: Option<i64>,
+    /// Whether the machine is waiting for input it hasn't been sent yet.
+    blocked: bool,
+    complete: bool,
+}
+
+#[derive(Debug)]
+pub struct Network {
+    machines: Vec<Machine>,
+    /// The machine currently being run.
+    current: usize,
+    /// Values to return from `run` before running any more instructions.
+    pending: VecDeque<Status>,
+}
+
+impl Network {
+    /// Creates a network of machines with the given links.
+    pub fn new(machines: impl IntoIterator<Item = (Computer, Link)>) -> Self {
+        let machines: Vec<_> = machines
+            .into_iter()
+            .map(|(computer, link)| Machine {
+                computer,
+                link,
+                partial: Vec::new(),
+                last_output: None,
+                blocked: false,
+                complete: false,
+            })
+            .collect();
+        for (i, m) in machines.iter().enumerate() {
+            if let Link::Machine(to) = m.link {
+                assert!(to < machines.len(), "machine {i} is linked to missing machine {to}");
+            }
+        }
+        Self {
+            machines,
+            current: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Creates a network where each machine sends its output to the next,
+    /// and the last machine's output is the output of the network.
+    pub fn pipeline(computers: impl IntoIterator<Item = Computer>) -> Self {
+        let computers: Vec<_> = computers.into_iter().collect();
+        let n = computers.len();
+        Self::new(computers.into_iter().enumerate().map(|(i, c)| {
+            let link = if i + 1 < n { Link::Machine(i + 1) } else { Link::Output };
+            (c, link)
+        }))
+    }
+
+    /// Creates a network where each machine sends its output to the next,
+    /// and the last machine sends its output back to the first.
+    pub fn ring(computers: impl IntoIterator<Item = Computer>) -> Self {
+        let computers: Vec<_> = computers.into_iter().collect();
+        let n = computers.len();
+        Self::new(
+            computers
+                .into_iter()
+                .enumerate()
+                .map(|(i, c)| (c, Link::Machine((i + 1) % n))),
+        )
+    }
+
+    /// Creates a network where machines send each other packets of an
+    /// address followed by `len` values. Each machine's address is its index.
+    pub fn packets(computers: impl IntoIterator<Item = Computer>, len: usize) -> Self {
+        Self::new(computers.into_iter().map(|c| (c, Link::Packets { len })))
+    }
+
+    pub fn computer(&self, i: usize) -> &Computer {
+        &self.machines[i].computer
+    }
+
+    pub fn computer_mut(&mut self, i: usize) -> &mut Computer {
+        self.machines[i].blocked = false;
+        &mut self.machines[i].computer
+    }
+
+    /// The most recent value output by the machine at the index, wherever it
+    /// was sent.
+    pub fn last_output(&self, i: usize) -> Option<i64> {
+        self.machines[i].last_output
+    }
+
+    /// Sends values to the input of the machine at the index.
+    pub fn send(&mut self, i: usize, values: impl IntoIterator<Item = i64>) {
+        let m = &mut self.machines[i];
+        m.computer.feed(values);
+        m.blocked = false;
+    }
+
+    /// Runs the network until a value leaves it, or it is idle or complete.
+    pub fn try_run(&mut self) -> Result<Status> {
+        loop {
+            if let Some(status) = self.pending.pop_front() {
+                return Ok(status);
+            }
+            let i = self.current;
+            let m = &mut self.machines[i];
+            if m.blocked || m.complete {
+                if self.machines.iter().all(|m| m.complete) {
+                    return Ok(Status::Complete);
+                }
+                if self.machines.iter().all(|m| m.blocked || m.complete) {
+                    self.current = (i + 1) % self.machines.len();
+                    return Ok(Status::Idle);
+                }
+                self.current = (i + 1) % self.machines.len();
+                continue;
+            }
+            match m.computer.try_next_state()? {
+                State::Yielded(value) => {
+                    m.last_output = Some(value);
+                    self.route(i, value);
+                }
+                State::Waiting => m.blocked = true,
+                State::Complete => m.complete = true,
+            }
+        }
+    }
+
+    #[track_caller]
+    pub fn run(&mut self) -> Status {
+        match self.try_run() {
+            Ok(status) => status,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Delivers a value output by the machine at the index.
+    fn route(&mut self, from: usize, value: i64) {
+        match self.machines[from].link {
+            Link::Machine(to) => self.send(to, [value]),
+            Link::Output => self.pending.push_back(Status::Output { from, value }),
+            Link::Packets { len } => {
+                let partial = &mut self.machines[from].partial;
+                partial.push(value);
+                if partial.len() <= len {
+                    return;
+                }
+                let mut values = partial.split_off(0);
+                let addr = values.remove(0);
+                match usize::try_from(addr) {
+                    Ok(to) if to < self.machines.len() => self.send(to, values),
+                    _ => self.pending.push_back(Status::Packet(Packet { from, addr, values })),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::assemble;
+
+    fn computer(src: &str) -> Computer {
+        Computer::new(assemble(src).unwrap())
+    }
+
+    #[test]
+    fn network_pipeline() {
+        // Each machine outputs its input doubled
+        let src = "\
+loop:   in -> [x]
+        mul [x], #2 -> [x]
+        out [x]
+        jnz #1, #loop
+x:      data 0
+";
+        let mut net = Network::pipeline((0..3).map(|_| computer(src)));
+        net.send(0, [1, 5]);
+        assert_eq!(net.run(), Status::Output { from: 2, value: 8 });
+        assert_eq!(net.run(), Status::Output { from: 2, value: 40 });
+        assert_eq!(net.run(), Status::Idle);
+        assert_eq!(net.last_output(1), Some(20));
+    }
+
+    #[test]
+    fn network_ring() {
+        // Each machine passes on its input plus one, three times
+        let src = "\
+loop:   in -> [x]
+        add [x], #1 -> [x]
+        out [x]
+        add [n], #-1 -> [n]
+        jnz [n], #loop
+        hlt
+x:      data 0
+n:      data 3
+";
+        let mut net = Network::ring((0..2).map(|_| computer(src)));
+        net.send(0, [0]);
+        assert_eq!(net.run(), Status::Complete);
+        assert_eq!(net.last_output(1), Some(6));
+    }
+
+    #[test]
+    fn network_packets() {
+        // Machine 0 sends a packet to machine 1, which adds the values and
+        // sends the total to address 255
+        let src0 = "\
+        out #1
+        out #3
+        out #4
+        in -> [x]
+        hlt
+x:      data 0
+";
+        let src1 = "\
+        in -> [x]
+        in -> [y]
+        add [x], [y] -> [x]
+        out #255
+        out [x]
+        out #0
+        hlt
+x:      data 0
+y:      data 0
+";
+        let mut net = Network::packets([computer(src0), computer(src1)], 2);
+        let packet = Packet {
+            from: 1,
+            addr: 255,
+            values: vec![7, 0],
+        };
+        assert_eq!(net.run(), Status::Packet(packet));
+        assert_eq!(net.run(), Status::Idle);
+        assert_eq!(net.run(), Status::Idle);
+        net.send(0, [1]);
+        assert_eq!(net.run(), Status::Complete);
+    }
+}
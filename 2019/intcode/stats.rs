@@ -0,0 +1,100 @@
+//! Count what a computer does.
+//!
+//! Counting is optional, see [`Computer::enable_stats`], and when it is
+//! disabled the only cost is a single check per instruction.
+//!
+//! [`Computer::enable_stats`]: super::Computer::enable_stats
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{Mode, Op, Trace};
+
+/// Statistics about the instructions a computer has executed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of times each operation was executed, in the order of
+    /// `Op::ALL`.
+    ops: [u64; Op::ALL.len()],
+    /// The number of values input.
+    pub inputs: u64,
+    /// The number of values output.
+    pub outputs: u64,
+    /// The highest address read from or written to by an instruction.
+    pub max_addr: Option<usize>,
+    /// The number of times the instruction at each address was executed.
+    addrs: HashMap<usize, u64>,
+}
+
+impl Stats {
+    /// The total number of instructions executed.
+    pub fn instructions(&self) -> u64 {
+        self.ops.iter().sum()
+    }
+
+    /// The number of times the operation was executed.
+    pub fn count(&self, op: Op) -> u64 {
+        self.ops[index(op)]
+    }
+
+    /// The `n` most executed addresses and how many times the instruction at
+    /// each was executed, most executed first.
+    pub fn hot(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut addrs: Vec<_> = self.addrs.iter().map(|(&a, &c)| (a, c)).collect();
+        addrs.sort_unstable_by_key(|&(a, c)| (u64::MAX - c, a));
+        addrs.truncate(n);
+        addrs
+    }
+
+    /// Records an executed instruction and the addresses it accessed.
+    pub(super) fn record(&mut self, trace: &Trace, addrs: impl Iterator<Item = usize>) {
+        let op = trace.instr.op;
+        self.ops[index(op)] += 1;
+        match op {
+            Op::Input => self.inputs += 1,
+            Op::Output => self.outputs += 1,
+            _ => {}
+        }
+        if let Some(max) = addrs.max() {
+            self.max_addr = self.max_addr.max(Some(max));
+        }
+        *self.addrs.entry(trace.ip).or_insert(0) += 1;
+    }
+}
+
+fn index(op: Op) -> usize {
+    Op::ALL.iter().position(|&o| o == op).unwrap()
+}
+
+/// Returns the addresses the instruction reads from or writes to, given the
+/// relative base.
+pub(super) fn accessed(trace: &Trace) -> impl Iterator<Item = usize> + '_ {
+    let params = trace.instr.params().iter().enumerate();
+    params.filter(|(_, p)| p.mode != Mode::Immediate).filter_map(|(i, p)| {
+        let addr = p.mode.resolve(p.value, trace.ip + i + 1, trace.relative_base);
+        usize::try_from(addr).ok()
+    })
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "instructions: {}", self.instructions())?;
+        for op in Op::ALL {
+            let count = self.count(op);
+            if count > 0 {
+                writeln!(f, "  {:<4} {count}", op.mnemonic())?;
+            }
+        }
+        writeln!(f, "inputs: {}", self.inputs)?;
+        writeln!(f, "outputs: {}", self.outputs)?;
+        match self.max_addr {
+            Some(addr) => writeln!(f, "highest address: {addr}")?,
+            None => writeln!(f, "highest address: none")?,
+        }
+        write!(f, "hot addresses:")?;
+        for (addr, count) in self.hot(5) {
+            write!(f, "\n  {addr:04} {count}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,476 @@
+//! Tests for the computer, written using the assembler.
+//!
+//! Most programs set the relative base to the `rel` label so that relative
+//! parameters are written as an offset from it, e.g. `[rb+x-rel]`.
+
+use super::*;
+
+const MODES: [Mode; 3] = [Mode::Position, Mode::Immediate, Mode::Relative];
+const WRITE_MODES: [Mode; 2] = [Mode::Position, Mode::Relative];
+
+/// Returns a parameter reading the given value, stored at the given label
+/// for the position and relative modes.
+fn param(mode: Mode, label: &str, value: i64) -> String {
+    match mode {
+        Mode::Position => format!("[{label}]"),
+        Mode::Immediate => format!("#{value}"),
+        Mode::Relative => format!("[rb+{label}-rel]"),
+    }
+}
+
+/// Runs the program on both the interpreter and the translating computer,
+/// checking that they agree.
+#[track_caller]
+fn run(src: &str, input: &[i64]) -> Vec<i64> {
+    let program = assemble(src).unwrap_or_else(|err| panic!("{err}\n{src}"));
+    let mut computer = Computer::new(program.clone());
+    computer.feed(input.iter().copied());
+    let output = run_until_complete(src, || computer.next_state());
+    let mut jit = JitComputer::new(program);
+    jit.feed(input.iter().copied());
+    let jit_output = run_until_complete(src, || jit.next_state());
+    assert_eq!(output, jit_output, "translated output differs\n{src}");
+    assert_eq!(computer, jit.into_computer(), "translated state differs\n{src}");
+    output
+}
+
+#[track_caller]
+fn run_until_complete(src: &str, mut next_state: impl FnMut() -> State) -> Vec<i64> {
+    let mut output = Vec::new();
+    loop {
+        match next_state() {
+            State::Yielded(v) => output.push(v),
+            State::Waiting => panic!("unexpected wait for input\n{src}"),
+            State::Complete => break output,
+        }
+    }
+}
+
+#[test]
+fn computer_arithmetic_and_comparison() {
+    type BinOp = fn(i64, i64) -> i64;
+    let ops: [(&str, BinOp); 4] = [
+        ("add", |a, b| a + b),
+        ("mul", |a, b| a * b),
+        ("lt", |a, b| (a < b) as i64),
+        ("eq", |a, b| (a == b) as i64),
+    ];
+    for (op, f) in ops {
+        for m1 in MODES {
+            for m2 in MODES {
+                for m3 in WRITE_MODES {
+                    for (a, b) in [(7, 5), (5, 7), (5, 5), (-3, 4)] {
+                        let src = format!(
+                            "\
+        arb #rel
+        {op} {}, {} -> {}
+        out [res]
+        hlt
+x:      data {a}
+rel:    data 0
+y:      data {b}
+res:    data 0
+",
+                            param(m1, "x", a),
+                            param(m2, "y", b),
+                            param(m3, "res", 0),
+                        );
+                        assert_eq!(run(&src, &[]), [f(a, b)], "\n{src}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn computer_jumps() {
+    for (op, jumps) in [("jnz", true), ("jz", false)] {
+        for m1 in MODES {
+            for m2 in MODES {
+                for cond in [0, 3] {
+                    let src = format!(
+                        "\
+        arb #rel
+        {op} {}, {}
+        out #0
+        hlt
+yes:    out #1
+        hlt
+cond:   data {cond}
+rel:    data 0
+target: data yes
+",
+                        param(m1, "cond", cond),
+                        param(m2, "target", 0).replace("#0", "#yes"),
+                    );
+                    let expected = ((cond != 0) == jumps) as i64;
+                    assert_eq!(run(&src, &[]), [expected], "\n{src}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn computer_input() {
+    for mode in WRITE_MODES {
+        let src = format!(
+            "\
+        arb #rel
+        in -> {}
+        out [res]
+        hlt
+rel:    data 0
+res:    data 0
+",
+            param(mode, "res", 0)
+        );
+        assert_eq!(run(&src, &[42]), [42], "\n{src}");
+    }
+}
+
+#[test]
+fn computer_output() {
+    for mode in MODES {
+        let src = format!(
+            "\
+        arb #rel
+        out {}
+        hlt
+x:      data -17
+rel:    data 0
+",
+            param(mode, "x", -17)
+        );
+        assert_eq!(run(&src, &[]), [-17], "\n{src}");
+    }
+}
+
+#[test]
+fn computer_adjust_base() {
+    for mode in MODES {
+        for (value, expected) in [(2, 30), (-1, 5), (0, 10)] {
+            let src = format!(
+                "\
+        arb #rel
+        arb {}
+        out [rb]
+        hlt
+x:      data {value}
+        data 5
+rel:    data 10
+        data 20
+        data 30
+",
+                param(mode, "x", value)
+            );
+            assert_eq!(run(&src, &[]), [expected], "\n{src}");
+        }
+    }
+}
+
+#[test]
+fn computer_writes_past_end() {
+    let src = "\
+        arb #1000
+        add #1, #2 -> [rb+5]
+        mul [1005], #3 -> [2000]
+        out [1005]
+        out [2000]
+        out [5000]
+        hlt
+";
+    assert_eq!(run(src, &[]), [3, 9, 0]);
+}
+
+#[test]
+fn computer_self_modifying() {
+    let src = "\
+        add #4, #0 -> [patch+1]     ; turn `out #1` into `out #4`
+patch:  out #1
+        hlt
+";
+    assert_eq!(run(src, &[]), [4]);
+
+    // The opcode is decoded and cached the first time round the loop
+    let src = "\
+loop:   out #1
+        add #99, #0 -> [loop]       ; turn `out #1` into `hlt`
+        jnz #1, #loop
+";
+    assert_eq!(run(src, &[]), [1]);
+}
+
+#[test]
+fn computer_waits_for_input() {
+    let src = "\
+        in -> [x]
+        out [x]
+        hlt
+x:      data 0
+";
+    let mut computer = Computer::new(assemble(src).unwrap());
+    assert!(matches!(computer.next_state(), State::Waiting));
+    computer.input(7);
+    assert!(matches!(computer.next_state(), State::Yielded(7)));
+    assert!(matches!(computer.next_state(), State::Complete));
+    assert!(matches!(computer.next_state(), State::Complete));
+}
+
+#[test]
+fn computer_snapshot_and_fork() {
+    let src = "\
+loop:   in -> [x]
+        add [x], [total] -> [total]
+        out [total]
+        jnz #1, #loop
+x:      data 0
+total:  data 0
+";
+    let mut computer = Computer::new(assemble(src).unwrap());
+    computer.input(5);
+    assert_eq!(computer.next(), Some(5));
+    let snapshot = computer.snapshot();
+
+    let mut fork = computer.fork();
+    fork.input(10);
+    assert_eq!(fork.next(), Some(15));
+
+    computer.input(1);
+    assert_eq!(computer.next(), Some(6));
+    computer.restore(&snapshot);
+    computer.input(2);
+    assert_eq!(computer.next(), Some(7));
+}
+
+#[test]
+fn computer_state_hash() {
+    let src = "\
+loop:   in -> [x]
+        eq [x], #0 -> [x]
+        out [x]
+        jnz #1, #loop
+x:      data 0
+";
+    let mut a = Computer::new(assemble(src).unwrap());
+    let mut b = a.fork();
+    a.input(1);
+    b.input(2);
+    assert_ne!(a, b);
+    assert_eq!(a.next(), Some(0));
+    assert_eq!(b.next(), Some(0));
+    assert_eq!(a, b);
+    assert_eq!(a.state_hash(), b.state_hash());
+    a.input(0);
+    assert_ne!(a.state_hash(), b.state_hash());
+}
+
+#[test]
+fn computer_errors() {
+    let kind = |src: &str| {
+        let program = assemble(src).unwrap();
+        let err = Computer::new(program.clone()).try_next_state().unwrap_err();
+        let jit_err = JitComputer::new(program).try_next_state().unwrap_err();
+        assert_eq!(err.to_string(), jit_err.to_string());
+        err.kind
+    };
+    assert_eq!(kind("data 42"), ErrorKind::UnknownOpcode { opcode: 42 });
+    assert_eq!(kind("data 301, 0"), ErrorKind::UnknownMode { mode: 3 });
+    assert_eq!(kind("out [-1]"), ErrorKind::NegativeAddress { addr: -1 });
+    assert_eq!(kind("arb #-5\nout [rb+2]"), ErrorKind::NegativeAddress { addr: -3 });
+    assert_eq!(kind("jnz #1, #-1"), ErrorKind::NegativeAddress { addr: -1 });
+}
+
+#[test]
+fn computer_error_context() {
+    let src = "\
+        arb #3
+        add #1, #2 -> [x]
+        jnz #1, #bad
+        hlt
+bad:    data 42, 0
+x:      data 0
+";
+    let mut computer = Computer::new(assemble(src).unwrap());
+    let err = computer.try_next_state().unwrap_err();
+    let context = err.context.as_deref().unwrap();
+    assert_eq!(context.ip, 10);
+    assert_eq!(context.raw, [42, 0, 3, 0]);
+    assert_eq!(context.relative_base, 3);
+    assert_eq!(
+        err.to_string(),
+        "\
+unknown opcode `42`
+at 0010: 42, 0, 3, 0 (relative base 3)
+   0000: arb #3
+   0002: add #1, #2 -> [12]
+   0006: jnz #1, #10
+=> 0009: data 99, 42, 0, 3"
+    );
+}
+
+#[test]
+fn computer_try_next_and_read_line() {
+    let src = "\
+        out #104
+        out #105
+        out #10
+        out #1000
+        hlt
+";
+    let mut computer = Computer::new(assemble(src).unwrap());
+    assert_eq!(computer.try_read_line().unwrap().as_deref(), Some("hi"));
+    let err = computer.try_read_line().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NotAscii { value: 1000 });
+    assert_eq!(computer.try_next().unwrap(), None);
+    let err = computer.try_next().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ReadPastComplete);
+
+    let mut computer = Computer::new(assemble("in -> [0]\nhlt").unwrap());
+    assert_eq!(computer.try_next().unwrap(), None);
+    assert_eq!(computer.try_read_line().unwrap(), None);
+
+    // Partial lines are returned when the computer stops partway through
+    let src = "\
+        out #62
+        in -> [rb]
+        out #111
+        out #107
+        hlt
+";
+    let mut computer = Computer::new(assemble(src).unwrap());
+    assert_eq!(computer.try_read_line().unwrap().as_deref(), Some(">"));
+    assert_eq!(computer.try_read_line().unwrap(), None);
+    computer.input(1);
+    assert_eq!(computer.try_read_line().unwrap().as_deref(), Some("ok"));
+    let err = computer.try_read_line().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ReadPastComplete);
+}
+
+#[test]
+fn computer_stats() {
+    let src = "\
+        arb #100
+        in -> [rb+5]
+loop:   out [rb+5]
+        add [rb+5], #-1 -> [rb+5]
+        jnz [rb+5], #loop
+        hlt
+";
+    let mut computer = Computer::new(assemble(src).unwrap());
+    computer.input(3);
+    assert!(computer.stats().is_none());
+    computer.enable_stats();
+    assert_eq!(iter::from_fn(|| computer.next()).collect::<Vec<_>>(), [3, 2, 1]);
+    assert_eq!(computer.next(), None);
+
+    let stats = computer.disable_stats().unwrap();
+    assert_eq!(stats.instructions(), 12);
+    assert_eq!(stats.count(Op::Output), 3);
+    assert_eq!(stats.count(Op::Halt), 1);
+    assert_eq!((stats.inputs, stats.outputs), (1, 3));
+    assert_eq!(stats.max_addr, Some(105));
+    assert_eq!(stats.hot(2), [(4, 3), (6, 3)]);
+    assert!(computer.stats().is_none());
+}
+
+#[test]
+fn computer_tracer() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let src = "\
+        add #1, #2 -> [x]
+        out [x]
+        hlt
+x:      data 0
+";
+    let mut computer = Computer::new(assemble(src).unwrap());
+    let ips = Rc::new(RefCell::new(Vec::new()));
+    let traced = ips.clone();
+    computer.set_tracer(move |t| traced.borrow_mut().push((t.ip, t.instr.op)));
+    assert_eq!(computer, Computer::new(assemble(src).unwrap()));
+    let mut fork = computer.fork();
+    assert_eq!(computer.next(), Some(3));
+    assert_eq!(computer.next(), None);
+    assert_eq!(*ips.borrow(), [(0, Op::Add), (4, Op::Output), (6, Op::Halt)]);
+
+    // Forks and cleared tracers don't trace
+    assert_eq!(fork.next(), Some(3));
+    computer.clear_tracer();
+    computer.set_ip(0);
+    assert_eq!(computer.next(), Some(3));
+    assert_eq!(ips.borrow().len(), 3);
+}
+
+#[test]
+fn jit_waits_for_input() {
+    let src = "\
+loop:   in -> [x]
+        out [x]
+        jnz [x], #loop
+        hlt
+x:      data 0
+";
+    let mut computer = JitComputer::new(assemble(src).unwrap());
+    assert!(matches!(computer.next_state(), State::Waiting));
+    computer.input(7).input(0);
+    assert_eq!(computer.next(), Some(7));
+    assert_eq!(computer.next(), Some(0));
+    assert!(matches!(computer.next_state(), State::Complete));
+    assert!(matches!(computer.next_state(), State::Complete));
+}
+
+#[test]
+fn jit_volatile_parameters() {
+    // Counts down by rewriting the parameter of its own `out`, and branches on
+    // a comparison written into the condition of a jump
+    let src = "\
+loop:   out #100
+        add [loop+1], #-1 -> [loop+1]
+        lt #0, [loop+1] -> [jump+1]
+jump:   jnz #0, #loop
+        hlt
+";
+    assert_eq!(run(src, &[]), (1..=100).rev().collect::<Vec<_>>());
+
+    let mut computer = JitComputer::new(assemble(src).unwrap());
+    while computer.next().is_some() {}
+    assert!(!computer.is_interpreted());
+}
+
+#[test]
+fn jit_falls_back_to_interpreter() {
+    // Rewrites the opcode of an instruction every time round the loop until
+    // it becomes `hlt`
+    let src = "\
+loop:   add [n], #-1 -> [n]
+        out [n]
+        eq [n], #0 -> [t]
+        mul [t], #98 -> [t]
+        add [t], #1 -> [op]
+op:     add #0, #0 -> [t]
+        jnz #1, #loop
+n:      data 100
+t:      data 0
+";
+    assert_eq!(run(src, &[]), (0..100).rev().collect::<Vec<_>>());
+
+    let mut computer = JitComputer::new(assemble(src).unwrap());
+    assert_eq!(computer.next(), Some(99));
+    assert!(!computer.is_interpreted());
+    while computer.next().is_some() {}
+    assert!(computer.is_interpreted());
+
+    // Writes far past the end of memory are left to the interpreter
+    let src = "\
+        add #1, #2 -> [1000000000]
+        out [1000000000]
+        hlt
+";
+    assert_eq!(run(src, &[]), [3]);
+    let mut computer = JitComputer::new(assemble(src).unwrap());
+    assert_eq!(computer.next(), Some(3));
+    assert!(computer.is_interpreted());
+}
@@ -47,9 +47,10 @@ fn example() {
     assert_eq!(part2(input), 241861950);
 }*/
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1015476);
-    assert_eq!(part2(input), 200878544);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1015476);
+        assert_eq!(part2(input), 200878544);
+    }
 }
@@ -60,21 +60,23 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 1-3 a: abcde
 1-3 b: cdefg
 2-9 c: ccccccccc",
-    );
-    assert_eq!(part1(input.clone()), 2);
-    assert_eq!(part2(input), 1);
+        );
+        assert_eq!(part1(input.clone()), 2);
+        assert_eq!(part2(input), 1);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 445);
-    assert_eq!(part2(input), 491);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 445);
+        assert_eq!(part2(input), 491);
+    }
 }
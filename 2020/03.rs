@@ -50,9 +50,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("\
+advent::example! {
+    fn example() {
+        let input = parse_input("\
 ..##.......
 #...#...#..
 .#....#..#.
@@ -64,13 +64,15 @@ fn example() {
 #.##...#...
 #...##....#
 .#..#...#.#");
-    assert_eq!(part1(input.clone()), 7);
-    assert_eq!(part2(input), 336);
+        assert_eq!(part1(input.clone()), 7);
+        assert_eq!(part2(input), 336);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 294);
-    assert_eq!(part2(input), 5774564250);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 294);
+        assert_eq!(part2(input), 5774564250);
+    }
 }
@@ -107,10 +107,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        r#"ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            r#"ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
 byr:1937 iyr:2017 cid:147 hgt:183cm
 
 iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
@@ -123,15 +123,16 @@ hgt:179cm
 
 hcl:#cfa07d eyr:2025 pid:166559648
 iyr:2011 ecl:brn hgt:59in"#,
-    );
-    assert_eq!(part1(input.clone()), 2);
-    assert_eq!(part2(input), 2);
+        );
+        assert_eq!(part1(input.clone()), 2);
+        assert_eq!(part2(input), 2);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        r#"eyr:1972 cid:100
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            r#"eyr:1972 cid:100
 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
 
 iyr:2019
@@ -144,15 +145,16 @@ ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
 hgt:59cm ecl:zzz
 eyr:2038 hcl:74454a iyr:2023
 pid:3556412378 byr:2007"#,
-    );
-    assert_eq!(part1(input.clone()), 4);
-    assert_eq!(part2(input), 0);
+        );
+        assert_eq!(part1(input.clone()), 4);
+        assert_eq!(part2(input), 0);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input(
-        r#"pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+advent::example! {
+    fn example3() {
+        let input = parse_input(
+            r#"pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
 hcl:#623a2f
 
 eyr:2029 ecl:blu cid:129 byr:1989
@@ -164,14 +166,16 @@ pid:545766238 ecl:hzl
 eyr:2022
 
 iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719"#,
-    );
-    assert_eq!(part1(input.clone()), 4);
-    assert_eq!(part2(input), 4);
+        );
+        assert_eq!(part1(input.clone()), 4);
+        assert_eq!(part2(input), 4);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 242);
-    assert_eq!(part2(input), 186);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 242);
+        assert_eq!(part2(input), 186);
+    }
 }
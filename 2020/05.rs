@@ -38,20 +38,22 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 BFFFBBFRRR
 FFFBBBFRRR
 BBFFBBFRLL",
-    );
-    assert_eq!(part1(input), 820);
+        );
+        assert_eq!(part1(input), 820);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 922);
-    assert_eq!(part2(input), 747);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 922);
+        assert_eq!(part2(input), 747);
+    }
 }
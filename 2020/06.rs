@@ -40,10 +40,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "abc
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "abc
 
 a
 b
@@ -58,14 +58,16 @@ a
 a
 
 b",
-    );
-    assert_eq!(part1(input.clone()), 11);
-    assert_eq!(part2(input), 6);
+        );
+        assert_eq!(part1(input.clone()), 11);
+        assert_eq!(part2(input), 6);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 6297);
-    assert_eq!(part2(input), 3158);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 6297);
+        assert_eq!(part2(input), 3158);
+    }
 }
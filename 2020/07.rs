@@ -63,10 +63,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        r#"light red bags contain 1 bright white bag, 2 muted yellow bags.
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            r#"light red bags contain 1 bright white bag, 2 muted yellow bags.
 dark orange bags contain 3 bright white bags, 4 muted yellow bags.
 bright white bags contain 1 shiny gold bag.
 muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
@@ -76,15 +76,16 @@ vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
 faded blue bags contain no other bags.
 dotted black bags contain no other bags.
 "#,
-    );
-    assert_eq!(part1(input.clone()), 4);
-    assert_eq!(part2(input), 32);
+        );
+        assert_eq!(part1(input.clone()), 4);
+        assert_eq!(part2(input), 32);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        r#"shiny gold bags contain 2 dark red bags.
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            r#"shiny gold bags contain 2 dark red bags.
 dark red bags contain 2 dark orange bags.
 dark orange bags contain 2 dark yellow bags.
 dark yellow bags contain 2 dark green bags.
@@ -92,14 +93,16 @@ dark green bags contain 2 dark blue bags.
 dark blue bags contain 2 dark violet bags.
 dark violet bags contain no other bags.
 "#,
-    );
-    assert_eq!(part1(input.clone()), 0);
-    assert_eq!(part2(input), 126);
+        );
+        assert_eq!(part1(input.clone()), 0);
+        assert_eq!(part2(input), 126);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 252);
-    assert_eq!(part2(input), 35487);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 252);
+        assert_eq!(part2(input), 35487);
+    }
 }
@@ -1,4 +1,5 @@
 use advent::prelude::*;
+use register_machine::{find_patch, Control, Cpu, Detect, Exit, Isa, Machine, Reg};
 
 fn parse_input(s: &str) -> Vec<(Op, i64)> {
     s.lines()
@@ -26,59 +27,55 @@ enum Op {
     Jmp,
 }
 
-#[derive(Debug, Clone)]
-struct Result {
-    is_finite: bool,
-    instrs: Vec<usize>,
-    acc: i64,
-}
+/// The handheld's instruction set, the accumulator is register `a`.
+#[derive(Clone)]
+struct Handheld;
+
+const ACC: Reg = Reg::new('a');
+
+impl Isa for Handheld {
+    type Instr = (Op, i64);
 
-fn exec(prog: &[(Op, i64)]) -> Result {
-    let mut instrs = Vec::new();
-    let mut ptr = 0;
-    let mut acc = 0;
-    while !instrs.contains(&ptr) && ptr < prog.len() {
-        instrs.push(ptr);
-        match prog[ptr] {
-            (Op::Nop, _) => {
-                ptr += 1;
-            }
-            (Op::Acc, inc) => {
-                ptr += 1;
-                acc += inc;
-            }
-            (Op::Jmp, offset) => {
-                ptr = (ptr as i64 + offset) as usize;
-            }
+    fn exec(&self, &(op, arg): &(Op, i64), cpu: &mut Cpu) -> Control {
+        match op {
+            Op::Nop => {}
+            Op::Acc => cpu.regs[ACC] += arg,
+            Op::Jmp => return Control::Jump(arg),
         }
+        Control::Next
     }
-    Result {
-        is_finite: ptr == prog.len(),
-        instrs,
-        acc,
+
+    fn successors(&self, &(op, arg): &(Op, i64)) -> Option<Vec<i64>> {
+        match op {
+            Op::Nop | Op::Acc => Some(vec![1]),
+            Op::Jmp => Some(vec![arg]),
+        }
     }
 }
 
+/// Returns a machine that stops when it is about to run an instruction a
+/// second time.
+fn machine(prog: Vec<(Op, i64)>) -> Machine<Handheld> {
+    let mut m = Machine::new(Handheld, prog);
+    // The accumulator never affects control flow
+    m.detect_loops(Detect::Pc);
+    m
+}
+
 fn part1(prog: Vec<(Op, i64)>) -> i64 {
-    exec(&prog).acc
+    let mut m = machine(prog);
+    assert_eq!(m.run(), Exit::Loop);
+    m.regs()[ACC]
 }
 
-fn part2(mut prog: Vec<(Op, i64)>) -> i64 {
-    for ptr in exec(&prog).instrs {
-        let old = prog[ptr].0;
-        let new = match old {
-            Op::Nop => Op::Jmp,
-            Op::Jmp => Op::Nop,
-            _ => continue,
-        };
-        prog[ptr].0 = new;
-        let Result { is_finite, acc, .. } = exec(&prog);
-        if is_finite {
-            return acc;
-        }
-        prog[ptr].0 = old;
-    }
-    panic!("failed to fix infinite loop")
+fn part2(prog: Vec<(Op, i64)>) -> i64 {
+    let flip = |&(op, arg): &(Op, i64)| match op {
+        Op::Nop => vec![(Op::Jmp, arg)],
+        Op::Jmp => vec![(Op::Nop, arg)],
+        Op::Acc => vec![],
+    };
+    let patch = find_patch(machine(prog), flip, |_| true).expect("failed to fix infinite loop");
+    patch.machine.regs()[ACC]
 }
 
 fn main() {
@@ -86,10 +83,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 nop +0
 acc +1
 jmp +4
@@ -99,14 +96,16 @@ acc -99
 acc +1
 jmp -4
 acc +6",
-    );
-    assert_eq!(part1(input.clone()), 5);
-    assert_eq!(part2(input), 8);
+        );
+        assert_eq!(part1(input.clone()), 5);
+        assert_eq!(part2(input), 8);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1584);
-    assert_eq!(part2(input), 920);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1584);
+        assert_eq!(part2(input), 920);
+    }
 }
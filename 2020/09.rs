@@ -55,17 +55,19 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input =
-        parse_input("35 20 15 25 47 40 62 55 65 95 102 117 150 182 127 219 299 277 309 576");
-    assert_eq!(part1(input.clone(), 5), 127);
-    assert_eq!(part2(input, 127), 62);
+advent::example! {
+    fn example() {
+        let input =
+            parse_input("35 20 15 25 47 40 62 55 65 95 102 117 150 182 127 219 299 277 309 576");
+        assert_eq!(part1(input.clone(), 5), 127);
+        assert_eq!(part2(input, 127), 62);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone(), 25), 70639851);
-    assert_eq!(part2(input, 70639851), 8249240);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone(), 25), 70639851);
+        assert_eq!(part2(input, 70639851), 8249240);
+    }
 }
@@ -48,26 +48,29 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input("16 10 15 5 1 11 7 19 6 12 4");
-    assert_eq!(part1(input.clone()), 35);
-    assert_eq!(part2(input), 8);
+advent::example! {
+    fn example1() {
+        let input = parse_input("16 10 15 5 1 11 7 19 6 12 4");
+        assert_eq!(part1(input.clone()), 35);
+        assert_eq!(part2(input), 8);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "28 33 18 42 31 14 46 20 48 47 24 23 49 45 \
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "28 33 18 42 31 14 46 20 48 47 24 23 49 45 \
          19 38 39 11 1 32 25 35 8 17 7 9 4 2 34 10 3",
-    );
-    assert_eq!(part1(input.clone()), 220);
-    assert_eq!(part2(input), 19208);
+        );
+        assert_eq!(part1(input.clone()), 220);
+        assert_eq!(part2(input), 19208);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1890);
-    assert_eq!(part2(input), 49607173328384);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1890);
+        assert_eq!(part2(input), 49607173328384);
+    }
 }
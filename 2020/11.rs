@@ -152,10 +152,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "L.LL.LL.LL
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "L.LL.LL.LL
 LLLLLLL.LL
 L.L.L..L..
 LLLL.LL.LL
@@ -165,14 +165,16 @@ L.LLLLL.LL
 LLLLLLLLLL
 L.LLLLLL.L
 L.LLLLL.LL",
-    );
-    assert_eq!(part1(input.clone()), 37);
-    assert_eq!(part2(input), 26);
+        );
+        assert_eq!(part1(input.clone()), 37);
+        assert_eq!(part2(input), 26);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 2243);
-    assert_eq!(part2(input), 2027);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 2243);
+        assert_eq!(part2(input), 2027);
+    }
 }
@@ -72,16 +72,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("F10 N3 F7 R90 F11");
-    assert_eq!(part1(input.clone()), 25);
-    assert_eq!(part2(input), 286);
+advent::example! {
+    fn example() {
+        let input = parse_input("F10 N3 F7 R90 F11");
+        assert_eq!(part1(input.clone()), 25);
+        assert_eq!(part2(input), 286);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 845);
-    assert_eq!(part2(input), 27016);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 845);
+        assert_eq!(part2(input), 27016);
+    }
 }
@@ -86,16 +86,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("939\n7,13,x,x,59,x,31,19");
-    assert_eq!(part1(input.clone()), 295);
-    assert_eq!(part2(input), 1068781);
+advent::example! {
+    fn example() {
+        let input = parse_input("939\n7,13,x,x,59,x,31,19");
+        assert_eq!(part1(input.clone()), 295);
+        assert_eq!(part2(input), 1068781);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 115);
-    assert_eq!(part2(input), 756261495958122);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 115);
+        assert_eq!(part2(input), 756261495958122);
+    }
 }
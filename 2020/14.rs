@@ -102,33 +102,36 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "\
 mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
 mem[8] = 11
 mem[7] = 101
 mem[8] = 0",
-    );
-    assert_eq!(part1(input), 165);
+        );
+        assert_eq!(part1(input), 165);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "\
 mask = 000000000000000000000000000000X1001X
 mem[42] = 100
 mask = 00000000000000000000000000000000X0XX
 mem[26] = 1",
-    );
-    assert_eq!(part2(input), 208);
+        );
+        assert_eq!(part2(input), 208);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 11884151942312);
-    assert_eq!(part2(input), 2625449018811);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 11884151942312);
+        assert_eq!(part2(input), 2625449018811);
+    }
 }
@@ -45,25 +45,27 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    for (input, p1, p2) in [
-        (vec![0, 3, 6], 436, 175594),
-        (vec![1, 3, 2], 1, 2578),
-        (vec![2, 1, 3], 10, 3544142),
-        (vec![1, 2, 3], 27, 261214),
-        (vec![2, 3, 1], 78, 6895259),
-        (vec![3, 2, 1], 438, 18),
-        (vec![3, 1, 2], 1836, 362),
-    ] {
-        assert_eq!(part1(input.clone()), p1);
-        assert_eq!(part2(input), p2);
+advent::example! {
+    fn example() {
+        for (input, p1, p2) in [
+            (vec![0, 3, 6], 436, 175594),
+            (vec![1, 3, 2], 1, 2578),
+            (vec![2, 1, 3], 10, 3544142),
+            (vec![1, 2, 3], 27, 261214),
+            (vec![2, 3, 1], 78, 6895259),
+            (vec![3, 2, 1], 438, 18),
+            (vec![3, 1, 2], 1836, 362),
+        ] {
+            assert_eq!(part1(input.clone()), p1);
+            assert_eq!(part2(input), p2);
+        }
     }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 929);
-    assert_eq!(part2(input), 16671510);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 929);
+        assert_eq!(part2(input), 16671510);
+    }
 }
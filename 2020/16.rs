@@ -157,10 +157,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 class: 1-3 or 5-7
 row: 6-11 or 33-44
 seat: 13-40 or 45-50
@@ -173,14 +173,16 @@ nearby tickets:
 40,4,50
 55,2,20
 38,6,12",
-    );
-    assert_eq!(part1(input.clone()), 71);
-    assert_eq!(part2(input), 1);
+        );
+        assert_eq!(part1(input.clone()), 71);
+        assert_eq!(part2(input), 1);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 29019);
-    assert_eq!(part2(input), 517827547723);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 29019);
+        assert_eq!(part2(input), 517827547723);
+    }
 }
@@ -58,16 +58,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_map_set(".#.\n..#\n###");
-    assert_eq!(part1(input.clone()), 112);
-    assert_eq!(part2(input), 848);
+advent::example! {
+    fn example() {
+        let input = parse_map_set(".#.\n..#\n###");
+        assert_eq!(part1(input.clone()), 112);
+        assert_eq!(part2(input), 848);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 242);
-    assert_eq!(part2(input), 2292);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 242);
+        assert_eq!(part2(input), 2292);
+    }
 }
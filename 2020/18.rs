@@ -116,29 +116,31 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let tests = &[
-        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
-        ("2 * 3 + (4 * 5)", 26, 46),
-        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
-        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
-        (
-            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
-            13632,
-            23340,
-        ),
-    ];
-    for &(input, r1, r2) in tests {
-        let input = parse_input(input);
-        assert_eq!(part1(input.clone()), r1);
-        assert_eq!(part2(input), r2);
+advent::example! {
+    fn example() {
+        let tests = &[
+            ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+            ("2 * 3 + (4 * 5)", 26, 46),
+            ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+            ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+            (
+                "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+                13632,
+                23340,
+            ),
+        ];
+        for &(input, r1, r2) in tests {
+            let input = parse_input(input);
+            assert_eq!(part1(input.clone()), r1);
+            assert_eq!(part2(input), r2);
+        }
     }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 7293529867931);
-    assert_eq!(part2(input), 60807587180737);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 7293529867931);
+        assert_eq!(part2(input), 60807587180737);
+    }
 }
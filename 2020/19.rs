@@ -113,10 +113,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        r#"0: 4 1 5
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            r#"0: 4 1 5
 1: 2 3 | 3 2
 2: 4 4 | 5 5
 3: 4 5 | 5 4
@@ -128,14 +128,15 @@ bababa
 abbbab
 aaabbb
 aaaabbb"#,
-    );
-    assert_eq!(part1(input), 2);
+        );
+        assert_eq!(part1(input), 2);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        r#"42: 9 14 | 10 1
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            r#"42: 9 14 | 10 1
 9: 14 27 | 1 26
 10: 23 14 | 28 1
 1: "a"
@@ -182,14 +183,16 @@ aaaabbaaaabbaaa
 aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
 babaaabbbaaabaababbaabababaaab
 aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#,
-    );
-    assert_eq!(part1(input.clone()), 3);
-    assert_eq!(part2(input), 12);
+        );
+        assert_eq!(part1(input.clone()), 3);
+        assert_eq!(part2(input), 12);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 178);
-    assert_eq!(part2(input), 346);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 178);
+        assert_eq!(part2(input), 346);
+    }
 }
@@ -281,10 +281,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 Tile 2311:
 ..##.#..#.
 ##..#.....
@@ -393,14 +393,16 @@ Tile 3079:
 ..#.......
 ..#.###...
 ",
-    );
-    assert_eq!(part1(input.clone()), 20899048083289);
-    assert_eq!(part2(input), 273);
+        );
+        assert_eq!(part1(input.clone()), 20899048083289);
+        assert_eq!(part2(input), 273);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 5966506063747);
-    assert_eq!(part2(input), 1714);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 5966506063747);
+        assert_eq!(part2(input), 1714);
+    }
 }
@@ -100,25 +100,27 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let foods = parse_input(
-        "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+advent::example! {
+    fn example() {
+        let foods = parse_input(
+            "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
 trh fvjkl sbzzf mxmxvkd (contains dairy)
 sqjhc fvjkl (contains soy)
 sqjhc mxmxvkd sbzzf (contains fish)
 ",
-    );
-    assert_eq!(part1(foods.clone()), 5);
-    assert_eq!(part2(foods), "mxmxvkd,sqjhc,fvjkl");
+        );
+        assert_eq!(part1(foods.clone()), 5);
+        assert_eq!(part2(foods), "mxmxvkd,sqjhc,fvjkl");
+    }
 }
 
-#[test]
-fn default() {
-    let foods = default_input();
-    assert_eq!(part1(foods.clone()), 2098);
-    assert_eq!(
-        part2(foods),
-        "ppdplc,gkcplx,ktlh,msfmt,dqsbql,mvqkdj,ggsz,hbhsx"
-    );
+advent::answer! {
+    fn default() {
+        let foods = default_input();
+        assert_eq!(part1(foods.clone()), 2098);
+        assert_eq!(
+            part2(foods),
+            "ppdplc,gkcplx,ktlh,msfmt,dqsbql,mvqkdj,ggsz,hbhsx"
+        );
+    }
 }
@@ -119,24 +119,27 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = [
-        VecDeque::from([9, 2, 6, 3, 1]),
-        VecDeque::from([5, 8, 4, 7, 10]),
-    ];
-    assert_eq!(part1(input.clone()), 306);
-    assert_eq!(part2(input), 291);
+advent::example! {
+    fn example1() {
+        let input = [
+            VecDeque::from([9, 2, 6, 3, 1]),
+            VecDeque::from([5, 8, 4, 7, 10]),
+        ];
+        assert_eq!(part1(input.clone()), 306);
+        assert_eq!(part2(input), 291);
+    }
 }
 
-#[test]
-fn example2() {
-    part2([VecDeque::from([43, 19]), VecDeque::from([2, 29, 14])]);
+advent::example! {
+    fn example2() {
+        part2([VecDeque::from([43, 19]), VecDeque::from([2, 29, 14])]);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 34566);
-    assert_eq!(part2(input), 31854);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 34566);
+        assert_eq!(part2(input), 31854);
+    }
 }
@@ -86,16 +86,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("389125467");
-    assert_eq!(part1(input.clone()), "67384529");
-    assert_eq!(part2(input), 149245887792);
+advent::example! {
+    fn example() {
+        let input = parse_input("389125467");
+        assert_eq!(part1(input.clone()), "67384529");
+        assert_eq!(part2(input), 149245887792);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), "49725386");
-    assert_eq!(part2(input), 538935646702);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), "49725386");
+        assert_eq!(part2(input), 538935646702);
+    }
 }
@@ -88,10 +88,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "sesenwnenenewseeswwswswwnenewsewsw
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "sesenwnenenewseeswwswswwnenewsewsw
 neeenesenwnwwswnenewnwwsewnenwseswesw
 seswneswswsenwwnwse
 nwnwneseeswswnenewneswwnewseswneseene
@@ -111,14 +111,16 @@ nenewswnwewswnenesenwnesewesw
 eneswnwswnwsenenwnwnwwseeswneewsenese
 neswnwewnwnwseenwseesewsenwsweewe
 wseweeenwnesenwwwswnew",
-    );
-    assert_eq!(part1(input.clone()), 10);
-    assert_eq!(part2(input), 2208);
+        );
+        assert_eq!(part1(input.clone()), 10);
+        assert_eq!(part2(input), 2208);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 438);
-    assert_eq!(part2(input), 4038);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 438);
+        assert_eq!(part2(input), 4038);
+    }
 }
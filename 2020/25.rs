@@ -35,13 +35,15 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    assert_eq!(part1((5764801, 17807724)), 14897079);
+advent::example! {
+    fn example() {
+        assert_eq!(part1((5764801, 17807724)), 14897079);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 12929);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 12929);
+    }
 }
@@ -34,16 +34,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("199 200 208 210 200 207 240 269 260 263");
-    assert_eq!(part1(input.clone()), 7);
-    assert_eq!(part2(input), 5);
-}
-
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1342);
-    assert_eq!(part2(input), 1378);
+advent::example! {
+    fn example() {
+        let input = parse_input("199 200 208 210 200 207 240 269 260 263");
+        assert_eq!(part1(input.clone()), 7);
+        assert_eq!(part2(input), 5);
+    }
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1342);
+        assert_eq!(part2(input), 1378);
+    }
 }
@@ -48,25 +48,27 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 forward 5
 down 5
 forward 8
 up 3
 down 8
 forward 2",
-    );
-    assert_eq!(part1(input.clone()), 150);
-    assert_eq!(part2(input), 900);
+        );
+        assert_eq!(part1(input.clone()), 150);
+        assert_eq!(part2(input), 900);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 2117664);
-    assert_eq!(part2(input), 2073416724);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 2117664);
+        assert_eq!(part2(input), 2073416724);
+    }
 }
 
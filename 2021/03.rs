@@ -84,10 +84,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 00100
 11110
 10110
@@ -100,16 +100,18 @@ fn example() {
 11001
 00010
 01010",
-    );
-    assert_eq!(part1(input.clone()), 198);
-    assert_eq!(part2(input), 230);
+        );
+        assert_eq!(part1(input.clone()), 198);
+        assert_eq!(part2(input), 230);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3969000);
-    assert_eq!(part2(input), 4267809);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3969000);
+        assert_eq!(part2(input), 4267809);
+    }
 }
 
 // Part 1:                             (120.5 µs)
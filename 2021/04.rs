@@ -91,10 +91,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
 
 22 13 17 11  0
@@ -114,16 +114,18 @@ fn example() {
 18  8 23 26 20
 22 11 13  6  5
  2  0 12  3  7",
-    );
-    assert_eq!(part1(input.clone()), 4512);
-    assert_eq!(part2(input), 1924);
+        );
+        assert_eq!(part1(input.clone()), 4512);
+        assert_eq!(part2(input), 1924);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 39902);
-    assert_eq!(part2(input), 15573);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 39902);
+        assert_eq!(part2(input), 15573);
+    }
 }
 
 // Part 1:                             (363.5 µs)
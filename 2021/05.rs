@@ -69,10 +69,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 0,9 -> 5,9
 8,0 -> 0,8
 9,4 -> 3,4
@@ -83,14 +83,16 @@ fn example() {
 3,4 -> 1,4
 0,0 -> 8,8
 5,5 -> 8,2",
-    );
-    assert_eq!(part1(input.clone()), 5);
-    assert_eq!(part2(input), 12);
+        );
+        assert_eq!(part1(input.clone()), 5);
+        assert_eq!(part2(input), 12);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 5774);
-    assert_eq!(part2(input), 18423);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 5774);
+        assert_eq!(part2(input), 18423);
+    }
 }
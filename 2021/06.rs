@@ -31,16 +31,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("3,4,3,1,2");
-    assert_eq!(solve(input.clone(), 18), 26);
-    assert_eq!(part2(input), 26984457539);
+advent::example! {
+    fn example() {
+        let input = parse_input("3,4,3,1,2");
+        assert_eq!(solve(input.clone(), 18), 26);
+        assert_eq!(part2(input), 26984457539);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 361169);
-    assert_eq!(part2(input), 1634946868992);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 361169);
+        assert_eq!(part2(input), 1634946868992);
+    }
 }
@@ -66,16 +66,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("16,1,2,0,4,2,7,1,2,14");
-    assert_eq!(part1(input.clone()), 37);
-    assert_eq!(part2(input), 168);
+advent::example! {
+    fn example() {
+        let input = parse_input("16,1,2,0,4,2,7,1,2,14");
+        assert_eq!(part1(input.clone()), 37);
+        assert_eq!(part2(input), 168);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 343468);
-    assert_eq!(part2(input), 96086265);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 343468);
+        assert_eq!(part2(input), 96086265);
+    }
 }
@@ -80,23 +80,25 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 2199943210
 3987894921
 9856789892
 8767896789
 9899965678",
-    );
-    assert_eq!(part1(input.clone()), 15);
-    assert_eq!(part2(input), 1134);
+        );
+        assert_eq!(part1(input.clone()), 15);
+        assert_eq!(part2(input), 1134);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 448);
-    assert_eq!(part2(input), 1417248);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 448);
+        assert_eq!(part2(input), 1417248);
+    }
 }
@@ -90,9 +90,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = "\
+advent::example! {
+    fn example() {
+        let input = "\
 [({(<(())[]>[[{[]{<()<>>
 [(()[<>])]({[<{<<[]>>(
 {([(<{}[<>[]}>{[]{[(<()>
@@ -103,13 +103,15 @@ fn example() {
 [<(<(<(<{}))><([]([]()
 <{([([[(<>()){}]>(<<{{
 <{([{{}}[<[[[<>{}]]]>[]]";
-    assert_eq!(part1(input), 26397);
-    assert_eq!(part2(input), 288957);
+        assert_eq!(part1(input), 26397);
+        assert_eq!(part2(input), 288957);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 167379);
-    assert_eq!(part2(input), 2776842859);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 167379);
+        assert_eq!(part2(input), 2776842859);
+    }
 }
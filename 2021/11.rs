@@ -97,10 +97,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 5483143223
 2745854711
 5264556173
@@ -111,16 +111,18 @@ fn example() {
 6882881134
 4846848554
 5283751526",
-    );
-    assert_eq!(part1(input.clone()), 1656);
-    assert_eq!(part2(input), 195);
+        );
+        assert_eq!(part1(input.clone()), 1656);
+        assert_eq!(part2(input), 195);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1669);
-    assert_eq!(part2(input), 351);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1669);
+        assert_eq!(part2(input), 351);
+    }
 }
 
 // Part 1:                             (377.0 µs)
@@ -71,10 +71,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "\
 start-A
 start-b
 A-c
@@ -82,15 +82,16 @@ A-b
 b-d
 A-end
 b-end",
-    );
-    assert_eq!(part1(input.clone()), 10);
-    assert_eq!(part2(input), 36);
+        );
+        assert_eq!(part1(input.clone()), 10);
+        assert_eq!(part2(input), 36);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "\
 dc-end
 HN-start
 start-kj
@@ -101,15 +102,16 @@ HN-end
 kj-sa
 kj-HN
 kj-dc",
-    );
-    assert_eq!(part1(input.clone()), 19);
-    assert_eq!(part2(input), 103);
+        );
+        assert_eq!(part1(input.clone()), 19);
+        assert_eq!(part2(input), 103);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example3() {
+        let input = parse_input(
+            "\
 fs-end
 he-DX
 fs-he
@@ -128,14 +130,16 @@ he-WI
 zg-he
 pj-fs
 start-RW",
-    );
-    assert_eq!(part1(input.clone()), 226);
-    assert_eq!(part2(input), 3509);
+        );
+        assert_eq!(part1(input.clone()), 226);
+        assert_eq!(part2(input), 3509);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 4104);
-    assert_eq!(part2(input), 119760);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 4104);
+        assert_eq!(part2(input), 119760);
+    }
 }
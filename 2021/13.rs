@@ -1,4 +1,5 @@
 use advent::prelude::*;
+use utilities::Screen;
 
 fn parse_input(input: &str) -> (HashSet<Vector2>, Vec<Fold>) {
     let (dots, folds) = input.split_once("\n\n").unwrap();
@@ -48,24 +49,15 @@ fn part1((dots, folds): (HashSet<Vector2>, Vec<Fold>)) -> usize {
     fold(dots, folds[0]).len()
 }
 
-fn part2((mut dots, folds): (HashSet<Vector2>, Vec<Fold>)) -> String {
+fn fold_all((mut dots, folds): (HashSet<Vector2>, Vec<Fold>)) -> Screen {
     for f in folds {
         dots = fold(dots, f);
     }
+    Screen::from_points(dots.into_iter().map(|v| (v.x, v.y)))
+}
 
-    let mut code = String::new();
-    let max_x = dots.iter().map(|v| v.x).max().unwrap();
-    let max_y = dots.iter().map(|v| v.y).max().unwrap();
-    for y in 0..=max_y {
-        for x in 0..=max_x {
-            code += match dots.contains(&vector![x, y]) {
-                false => "░░",
-                true => "██",
-            };
-        }
-        code += "\n";
-    }
-    code
+fn part2(input: (HashSet<Vector2>, Vec<Fold>)) -> String {
+    fold_all(input).decode().unwrap()
 }
 
 fn main() {
@@ -73,10 +65,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 6,10
 0,14
 9,10
@@ -98,33 +90,25 @@ fn example() {
 
 fold along y=7
 fold along x=5",
-    );
-    assert_eq!(part1(input.clone()), 17);
-    assert_eq!(
-        part2(input),
-        "\
-██████████
-██░░░░░░██
-██░░░░░░██
-██░░░░░░██
-██████████
+        );
+        assert_eq!(part1(input.clone()), 17);
+        assert_eq!(
+            fold_all(input).to_string(),
+            "\
+█████
+█░░░█
+█░░░█
+█░░░█
+█████
 "
-    );
+        );
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 687);
-    assert_eq!(
-        part2(input),
-        "\
-████████░░░░████░░░░██░░░░██░░░░████░░░░██░░░░██░░██████░░░░████████░░░░████░░
-██░░░░░░░░██░░░░██░░██░░██░░░░██░░░░██░░██░░██░░░░██░░░░██░░░░░░░░██░░██░░░░██
-██████░░░░██░░░░░░░░████░░░░░░██░░░░░░░░████░░░░░░██████░░░░░░░░██░░░░██░░░░░░
-██░░░░░░░░██░░████░░██░░██░░░░██░░░░░░░░██░░██░░░░██░░░░██░░░░██░░░░░░██░░████
-██░░░░░░░░██░░░░██░░██░░██░░░░██░░░░██░░██░░██░░░░██░░░░██░░██░░░░░░░░██░░░░██
-██░░░░░░░░░░██████░░██░░░░██░░░░████░░░░██░░░░██░░██████░░░░████████░░░░██████
-"
-    );
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 687);
+        assert_eq!(part2(input), "FGKCKBZG");
+    }
 }
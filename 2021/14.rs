@@ -82,10 +82,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 NNCB
 
 CH -> B
@@ -104,14 +104,16 @@ BB -> N
 BC -> B
 CC -> N
 CN -> C",
-    );
-    assert_eq!(part1(input.clone()), 1588);
-    assert_eq!(part2(input), 2188189693529);
+        );
+        assert_eq!(part1(input.clone()), 1588);
+        assert_eq!(part2(input), 2188189693529);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3555);
-    assert_eq!(part2(input), 4439442043739);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3555);
+        assert_eq!(part2(input), 4439442043739);
+    }
 }
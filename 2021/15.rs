@@ -140,10 +140,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 1163751742
 1381373672
 2136511328
@@ -154,14 +154,16 @@ fn example() {
 3125421639
 1293138521
 2311944581",
-    );
-    assert_eq!(part1(input.clone()), 40);
-    assert_eq!(part2(input), 315);
+        );
+        assert_eq!(part1(input.clone()), 40);
+        assert_eq!(part2(input), 315);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 602);
-    assert_eq!(part2(input), 2935);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 602);
+        assert_eq!(part2(input), 2935);
+    }
 }
@@ -121,64 +121,70 @@ fn literal(version: i64, payload: i64) -> Packet {
     }
 }
 
-#[test]
-fn example1() {
-    let input = parse_input("D2FE28");
-    assert_eq!(
-        parse_packet(&mut input.into_iter()).unwrap(),
-        literal(6, 2021),
-    );
-}
-
-#[test]
-fn example2() {
-    let input = parse_input("38006F45291200");
-    assert_eq!(
-        parse_packet(&mut input.into_iter()).unwrap(),
-        Packet {
-            version: 1,
-            type_id: 6,
-            payload: Payload::Packets(vec![literal(6, 10), literal(2, 20)]),
-        }
-    );
-}
-
-#[test]
-fn example3() {
-    let input = parse_input("EE00D40C823060");
-    assert_eq!(
-        parse_packet(&mut input.into_iter()).unwrap(),
-        Packet {
-            version: 7,
-            type_id: 3,
-            payload: Payload::Packets(vec![literal(2, 1), literal(4, 2), literal(1, 3)]),
-        }
-    );
-}
-
-#[test]
-fn example4() {
-    assert_eq!(part1(parse_input("8A004A801A8002F478")), 16);
-    assert_eq!(part1(parse_input("620080001611562C8802118E34")), 12);
-    assert_eq!(part1(parse_input("C0015000016115A2E0802F182340")), 23);
-    assert_eq!(part1(parse_input("A0016C880162017C3686B18A3D4780")), 31);
-}
-
-#[test]
-fn example5() {
-    assert_eq!(part2(parse_input("C200B40A82")), 3);
-    assert_eq!(part2(parse_input("04005AC33890")), 54);
-    assert_eq!(part2(parse_input("880086C3E88112")), 7);
-    assert_eq!(part2(parse_input("CE00C43D881120")), 9);
-    assert_eq!(part2(parse_input("D8005AC2A8F0")), 1);
-    assert_eq!(part2(parse_input("F600BC2D8F")), 0);
-    assert_eq!(part2(parse_input("9C005AC2F8F0")), 0);
-    assert_eq!(part2(parse_input("9C0141080250320F1802104A08")), 1);
-}
-
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 934);
-    assert_eq!(part2(input), 912901337844);
+advent::example! {
+    fn example1() {
+        let input = parse_input("D2FE28");
+        assert_eq!(
+            parse_packet(&mut input.into_iter()).unwrap(),
+            literal(6, 2021),
+        );
+    }
+}
+
+advent::example! {
+    fn example2() {
+        let input = parse_input("38006F45291200");
+        assert_eq!(
+            parse_packet(&mut input.into_iter()).unwrap(),
+            Packet {
+                version: 1,
+                type_id: 6,
+                payload: Payload::Packets(vec![literal(6, 10), literal(2, 20)]),
+            }
+        );
+    }
+}
+
+advent::example! {
+    fn example3() {
+        let input = parse_input("EE00D40C823060");
+        assert_eq!(
+            parse_packet(&mut input.into_iter()).unwrap(),
+            Packet {
+                version: 7,
+                type_id: 3,
+                payload: Payload::Packets(vec![literal(2, 1), literal(4, 2), literal(1, 3)]),
+            }
+        );
+    }
+}
+
+advent::example! {
+    fn example4() {
+        assert_eq!(part1(parse_input("8A004A801A8002F478")), 16);
+        assert_eq!(part1(parse_input("620080001611562C8802118E34")), 12);
+        assert_eq!(part1(parse_input("C0015000016115A2E0802F182340")), 23);
+        assert_eq!(part1(parse_input("A0016C880162017C3686B18A3D4780")), 31);
+    }
+}
+
+advent::example! {
+    fn example5() {
+        assert_eq!(part2(parse_input("C200B40A82")), 3);
+        assert_eq!(part2(parse_input("04005AC33890")), 54);
+        assert_eq!(part2(parse_input("880086C3E88112")), 7);
+        assert_eq!(part2(parse_input("CE00C43D881120")), 9);
+        assert_eq!(part2(parse_input("D8005AC2A8F0")), 1);
+        assert_eq!(part2(parse_input("F600BC2D8F")), 0);
+        assert_eq!(part2(parse_input("9C005AC2F8F0")), 0);
+        assert_eq!(part2(parse_input("9C0141080250320F1802104A08")), 1);
+    }
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 934);
+        assert_eq!(part2(input), 912901337844);
+    }
 }
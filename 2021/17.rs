@@ -67,23 +67,26 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input("target area: x=20..30, y=-10..-5");
-    assert_eq!(part1(input), 45);
-    assert_eq!(part2(input), 112);
+advent::example! {
+    fn example1() {
+        let input = parse_input("target area: x=20..30, y=-10..-5");
+        assert_eq!(part1(input), 45);
+        assert_eq!(part2(input), 112);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input("target area: x=352..377, y=-49..-30");
-    assert_eq!(part1(input), 66);
-    assert_eq!(part2(input), 820);
+advent::example! {
+    fn example2() {
+        let input = parse_input("target area: x=352..377, y=-49..-30");
+        assert_eq!(part1(input), 66);
+        assert_eq!(part2(input), 820);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 6786);
-    assert_eq!(part2(input), 2313);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 6786);
+        assert_eq!(part2(input), 2313);
+    }
 }
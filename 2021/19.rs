@@ -95,10 +95,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 --- scanner 0 ---
 404,-588,-901
 528,-643,409
@@ -235,14 +235,16 @@ fn example() {
 891,-625,532
 -652,-548,-490
 30,-46,-14",
-    );
-    assert_eq!(part1(input.clone()), 79);
-    assert_eq!(part2(input), 3621);
+        );
+        assert_eq!(part1(input.clone()), 79);
+        assert_eq!(part2(input), 3621);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 405);
-    assert_eq!(part2(input), 12306);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 405);
+        assert_eq!(part2(input), 12306);
+    }
 }
@@ -65,9 +65,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("\
+advent::example! {
+    fn example() {
+        let input = parse_input("\
 ..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
 
 #..#.
@@ -75,13 +75,15 @@ fn example() {
 ##..#
 ..#..
 ..###");
-    assert_eq!(part1(input.clone()), 35);
-    assert_eq!(part2(input), 3351);
+        assert_eq!(part1(input.clone()), 35);
+        assert_eq!(part2(input), 3351);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 5475);
-    assert_eq!(part2(input), 17548);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 5475);
+        assert_eq!(part2(input), 17548);
+    }
 }
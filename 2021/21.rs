@@ -74,16 +74,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = [4, 8];
-    assert_eq!(part1(input), 739785);
-    assert_eq!(part2(input), 444356092776315);
+advent::example! {
+    fn example() {
+        let input = [4, 8];
+        assert_eq!(part1(input), 739785);
+        assert_eq!(part2(input), 444356092776315);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 926610);
-    assert_eq!(part2(input), 146854918035875);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 926610);
+        assert_eq!(part2(input), 146854918035875);
+    }
 }
@@ -75,23 +75,14 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "\
+advent::examples! {
+    parse_input => (part1, part2);
+    example1: "\
 on x=10..12,y=10..12,z=10..12
 on x=11..13,y=11..13,z=11..13
 off x=9..11,y=9..11,z=9..11
-on x=10..10,y=10..10,z=10..10",
-    );
-    assert_eq!(part1(input.clone()), 39);
-    assert_eq!(part2(input), 39);
-}
-
-#[test]
-fn example2() {
-    let input = parse_input(
-        "\
+on x=10..10,y=10..10,z=10..10" => (39, 39),
+    example2: "\
 on x=-20..26,y=-36..17,z=-47..7
 on x=-20..33,y=-21..23,z=-26..28
 on x=-22..28,y=-29..23,z=-38..16
@@ -113,16 +104,8 @@ on x=-49..-5,y=-3..45,z=-29..18
 off x=18..30,y=-20..-8,z=-3..13
 on x=-41..9,y=-7..43,z=-33..15
 on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
-on x=967..23432,y=45373..81175,z=27513..53682",
-    );
-    assert_eq!(part1(input.clone()), 590784);
-    assert_eq!(part2(input), 39769202357779);
-}
-
-#[test]
-fn example3() {
-    let input = parse_input(
-        "\
+on x=967..23432,y=45373..81175,z=27513..53682" => (590784, 39769202357779),
+    example3: "\
 on x=-5..47,y=-31..22,z=-19..33
 on x=-44..5,y=-27..21,z=-14..35
 on x=-49..-1,y=-11..42,z=-10..38
@@ -182,15 +165,13 @@ off x=-37810..49457,y=-71013..-7894,z=-105357..-13188
 off x=-27365..46395,y=31009..98017,z=15428..76570
 off x=-70369..-16548,y=22648..78696,z=-1892..86821
 on x=-53470..21291,y=-120233..-33476,z=-44150..38147
-off x=-93533..-4276,y=-16170..68771,z=-104985..-24507",
-    );
-    assert_eq!(part1(input.clone()), 474140);
-    assert_eq!(part2(input), 2758514936282235);
+off x=-93533..-4276,y=-16170..68771,z=-104985..-24507" => (474140, 2758514936282235),
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 590467);
-    assert_eq!(part2(input), 1225064738333321);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 590467);
+        assert_eq!(part2(input), 1225064738333321);
+    }
 }
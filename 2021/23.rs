@@ -223,23 +223,25 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 #############
 #...........#
 ###B#C#B#D###
   #A#D#C#A#
   #########",
-    );
-    assert_eq!(part1(input), 12521);
-    assert_eq!(part2(input), 44169);
+        );
+        assert_eq!(part1(input), 12521);
+        assert_eq!(part2(input), 44169);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 16157);
-    assert_eq!(part2(input), 43481);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 16157);
+        assert_eq!(part2(input), 43481);
+    }
 }
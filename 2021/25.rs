@@ -84,10 +84,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "\
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "\
 v...>>.vv>
 .vv>>.vv..
 >>.>v>...v
@@ -97,12 +97,14 @@ v>v.vv.v..
 .vv..>.>v.
 v.v..>>v.v
 ....v..v.>",
-    );
-    assert_eq!(part1(input), 58);
+        );
+        assert_eq!(part1(input), 58);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 426);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 426);
+    }
 }
@@ -0,0 +1,432 @@
+//! The ALU from the submarine's MONAD, with a concrete interpreter and a
+//! symbolic evaluator.
+//!
+//! The symbolic evaluator tracks the range of values each register could hold
+//! along with an expression for it in terms of the inputs. Accepted inputs are
+//! found by fixing the input one digit at a time and abandoning a prefix as
+//! soon as the ranges show that `z` can no longer end up as zero.
+
+#![allow(dead_code)]
+
+use std::fmt;
+use std::rc::Rc;
+
+use advent::prelude::*;
+use thiserror::Error;
+
+pub const W: usize = 0;
+pub const X: usize = 1;
+pub const Y: usize = 2;
+pub const Z: usize = 3;
+
+pub type Program = Vec<Instr>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Src {
+    Reg(usize),
+    Imm(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instr {
+    Inp(usize),
+    Bin(Op, usize, Src),
+}
+
+/// An error running a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum Error {
+    #[error("ran out of input")]
+    NoInput,
+    #[error("division by zero")]
+    DivByZero,
+    #[error("invalid modulo of `{a}` by `{b}`")]
+    InvalidMod { a: i64, b: i64 },
+}
+
+/// An inclusive range of values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub lo: i64,
+    pub hi: i64,
+}
+
+/// A symbolic expression over the inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Const(i64),
+    Input(usize),
+    Bin(Op, Rc<Expr>, Rc<Expr>),
+}
+
+/// The symbolic value of a register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Value {
+    pub expr: Rc<Expr>,
+    pub range: Range,
+}
+
+/// A domain of values the program can be evaluated over.
+pub trait Domain: Clone {
+    fn constant(v: i64) -> Self;
+
+    /// Applies the operation, returning `None` if it always fails.
+    fn apply(op: Op, a: &Self, b: &Self) -> Option<Self>;
+}
+
+pub fn parse_program(input: &str) -> Program {
+    let reg = |s: &str| match s {
+        "w" => W,
+        "x" => X,
+        "y" => Y,
+        "z" => Z,
+        s => panic!("unknown register `{s}`"),
+    };
+    input
+        .lines()
+        .map(|line| {
+            let mut it = line.split_whitespace();
+            let name = it.next().unwrap();
+            let a = reg(it.next().unwrap());
+            if name == "inp" {
+                return Instr::Inp(a);
+            }
+            let b = it.next().unwrap();
+            let b = b.parse().map(Src::Imm).unwrap_or_else(|_| Src::Reg(reg(b)));
+            let op = match name {
+                "add" => Op::Add,
+                "mul" => Op::Mul,
+                "div" => Op::Div,
+                "mod" => Op::Mod,
+                "eql" => Op::Eql,
+                name => panic!("unknown instruction `{name}`"),
+            };
+            Instr::Bin(op, a, b)
+        })
+        .collect()
+}
+
+impl Op {
+    pub fn apply(self, a: i64, b: i64) -> Result<i64, Error> {
+        match self {
+            Op::Add => Ok(a + b),
+            Op::Mul => Ok(a * b),
+            Op::Div if b == 0 => Err(Error::DivByZero),
+            Op::Div => Ok(a / b),
+            Op::Mod if a < 0 || b <= 0 => Err(Error::InvalidMod { a, b }),
+            Op::Mod => Ok(a % b),
+            Op::Eql => Ok((a == b) as i64),
+        }
+    }
+}
+
+/// Runs the program from the given registers, returning the final registers.
+pub fn run(
+    program: &[Instr],
+    mut regs: [i64; 4],
+    input: impl IntoIterator<Item = i64>,
+) -> Result<[i64; 4], Error> {
+    let mut input = input.into_iter();
+    for &instr in program {
+        match instr {
+            Instr::Inp(a) => regs[a] = input.next().ok_or(Error::NoInput)?,
+            Instr::Bin(op, a, b) => {
+                let b = match b {
+                    Src::Reg(r) => regs[r],
+                    Src::Imm(v) => v,
+                };
+                regs[a] = op.apply(regs[a], b)?;
+            }
+        }
+    }
+    Ok(regs)
+}
+
+/// Evaluates the program over a domain, returning the final registers or
+/// `None` if every execution fails.
+pub fn eval<D: Domain>(
+    program: &[Instr],
+    mut regs: [D; 4],
+    mut input: impl FnMut(usize) -> D,
+) -> Option<[D; 4]> {
+    let mut n = 0;
+    for &instr in program {
+        match instr {
+            Instr::Inp(a) => {
+                regs[a] = input(n);
+                n += 1;
+            }
+            Instr::Bin(op, a, b) => {
+                let b = match b {
+                    Src::Reg(r) => regs[r].clone(),
+                    Src::Imm(v) => D::constant(v),
+                };
+                regs[a] = D::apply(op, &regs[a], &b)?;
+            }
+        }
+    }
+    Some(regs)
+}
+
+/// Evaluates the program symbolically from zeroed registers, with each input
+/// being a digit from 1 to 9.
+pub fn symbolic(program: &[Instr]) -> Option<[Value; 4]> {
+    let zero = Value::constant(0);
+    let regs = [zero.clone(), zero.clone(), zero.clone(), zero];
+    eval(program, regs, |i| Value::input(i, Range::new(1, 9)))
+}
+
+impl Range {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        Self { lo, hi }
+    }
+
+    pub fn contains(&self, v: i64) -> bool {
+        (self.lo..=self.hi).contains(&v)
+    }
+
+    /// Returns the value if the range only contains one.
+    fn value(&self) -> Option<i64> {
+        (self.lo == self.hi).some(self.lo)
+    }
+
+    /// Returns the range of `f` applied to the corners, `f` must be monotonic
+    /// in each argument over the ranges.
+    fn corners(a: Range, b: Range, f: impl Fn(i64, i64) -> i64) -> Range {
+        let vs = [f(a.lo, b.lo), f(a.lo, b.hi), f(a.hi, b.lo), f(a.hi, b.hi)];
+        Range::new(*vs.iter().min().unwrap(), *vs.iter().max().unwrap())
+    }
+
+    fn union(a: Option<Range>, b: Option<Range>) -> Option<Range> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(Range::new(min(a.lo, b.lo), max(a.hi, b.hi))),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Returns the part of the range between the bounds, if any.
+    fn clamp(self, lo: i64, hi: i64) -> Option<Range> {
+        let r = Range::new(max(self.lo, lo), min(self.hi, hi));
+        (r.lo <= r.hi).some(r)
+    }
+}
+
+impl Domain for Range {
+    fn constant(v: i64) -> Self {
+        Range::new(v, v)
+    }
+
+    fn apply(op: Op, &a: &Self, &b: &Self) -> Option<Self> {
+        // Bounds saturate, which keeps them correct if not exact
+        match op {
+            Op::Add => Some(Range::new(
+                a.lo.saturating_add(b.lo),
+                a.hi.saturating_add(b.hi),
+            )),
+            Op::Mul => Some(Range::corners(a, b, i64::saturating_mul)),
+            Op::Div => {
+                // Truncating division is monotonic as long as the divisor
+                // doesn't change sign, so split it around zero
+                let div = |b: Option<Range>| b.map(|b| Range::corners(a, b, i64::saturating_div));
+                Range::union(div(b.clamp(i64::MIN, -1)), div(b.clamp(1, i64::MAX)))
+            }
+            Op::Mod => {
+                let a = a.clamp(0, i64::MAX)?;
+                let b = b.clamp(1, i64::MAX)?;
+                match a.hi < b.lo {
+                    true => Some(a),
+                    false => Some(Range::new(0, min(a.hi, b.hi - 1))),
+                }
+            }
+            Op::Eql => match (a.value(), b.value()) {
+                (Some(x), Some(y)) => Some(Range::constant((x == y) as i64)),
+                _ if a.hi < b.lo || b.hi < a.lo => Some(Range::constant(0)),
+                _ => Some(Range::new(0, 1)),
+            },
+        }
+    }
+}
+
+impl Value {
+    pub fn input(i: usize, range: Range) -> Self {
+        Self {
+            expr: Rc::new(Expr::Input(i)),
+            range,
+        }
+    }
+}
+
+impl Domain for Value {
+    fn constant(v: i64) -> Self {
+        Self {
+            expr: Rc::new(Expr::Const(v)),
+            range: Range::constant(v),
+        }
+    }
+
+    fn apply(op: Op, a: &Self, b: &Self) -> Option<Self> {
+        let range = Range::apply(op, &a.range, &b.range)?;
+        if let Some(v) = range.value() {
+            return Some(Value::constant(v));
+        }
+        let identity = |v: &Value, x| v.range.value() == Some(x);
+        let expr = match op {
+            Op::Add if identity(a, 0) => b.expr.clone(),
+            Op::Add if identity(b, 0) => a.expr.clone(),
+            Op::Mul if identity(a, 1) => b.expr.clone(),
+            Op::Mul if identity(b, 1) => a.expr.clone(),
+            Op::Div if identity(b, 1) => a.expr.clone(),
+            Op::Mod if a.range.lo >= 0 && a.range.hi < b.range.lo => a.expr.clone(),
+            op => Rc::new(Expr::Bin(op, a.expr.clone(), b.expr.clone())),
+        };
+        Some(Self { expr, range })
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nested = |f: &mut fmt::Formatter<'_>, e: &Expr| match e {
+            Expr::Bin(..) => write!(f, "({e})"),
+            e => write!(f, "{e}"),
+        };
+        match self {
+            Expr::Const(v) => write!(f, "{v}"),
+            Expr::Input(i) => write!(f, "in{i}"),
+            Expr::Bin(op, a, b) => {
+                let sym = match op {
+                    Op::Add => "+",
+                    Op::Mul => "*",
+                    Op::Div => "/",
+                    Op::Mod => "%",
+                    Op::Eql => "==",
+                };
+                nested(f, a)?;
+                write!(f, " {sym} ")?;
+                nested(f, b)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in {}..={}", self.expr, self.range.lo, self.range.hi)
+    }
+}
+
+/// Searches for an input accepted by the program, that is one that leaves
+/// zero in `z`.
+///
+/// Each input is one of the given digits, and they are tried in the order
+/// given so the first accepted input is the largest or smallest number when
+/// the digits are descending or ascending.
+pub fn search(program: &[Instr], digits: &[i64]) -> Option<Vec<i64>> {
+    let starts: Vec<_> = program
+        .iter()
+        .positions(|instr| matches!(instr, Instr::Inp(_)))
+        .collect();
+    let first = starts.first().copied().unwrap_or(program.len());
+    let regs = run(&program[..first], [0; 4], []).ok()?;
+    let range = Range::new(*digits.iter().min()?, *digits.iter().max()?);
+    let live = liveness(program);
+    let mut s = Search {
+        program,
+        starts,
+        live,
+        digits,
+        range,
+        dead: HashSet::new(),
+    };
+    let mut input = Vec::new();
+    s.dfs(0, regs, &mut input).some(input)
+}
+
+struct Search<'a> {
+    program: &'a [Instr],
+    /// The index of each input instruction.
+    starts: Vec<usize>,
+    /// The registers live before each instruction.
+    live: Vec<[bool; 4]>,
+    digits: &'a [i64],
+    range: Range,
+    /// States at an input instruction that are known not to be accepted.
+    dead: HashSet<(usize, [i64; 4])>,
+}
+
+impl Search<'_> {
+    fn dfs(&mut self, k: usize, regs: [i64; 4], input: &mut Vec<i64>) -> bool {
+        let Some(&start) = self.starts.get(k) else {
+            return regs[Z] == 0;
+        };
+        // Registers that are never read again don't matter
+        let live = self.live[start];
+        let key = (k, [0, 1, 2, 3].map(|r| if live[r] { regs[r] } else { 0 }));
+        if self.dead.contains(&key) || !self.feasible(start, regs) {
+            self.dead.insert(key);
+            return false;
+        }
+        let end = self
+            .starts
+            .get(k + 1)
+            .copied()
+            .unwrap_or(self.program.len());
+        for &d in self.digits {
+            let Ok(next) = run(&self.program[start..end], regs, [d]) else {
+                continue;
+            };
+            input.push(d);
+            if self.dfs(k + 1, next, input) {
+                return true;
+            }
+            input.pop();
+        }
+        self.dead.insert(key);
+        false
+    }
+
+    /// Whether the rest of the program could leave zero in `z`.
+    fn feasible(&self, start: usize, regs: [i64; 4]) -> bool {
+        let regs = regs.map(Range::constant);
+        eval(&self.program[start..], regs, |_| self.range).is_some_and(|regs| regs[Z].contains(0))
+    }
+}
+
+/// Returns the registers live before each instruction, only `z` is live at
+/// the end.
+fn liveness(program: &[Instr]) -> Vec<[bool; 4]> {
+    let mut live = vec![[false; 4]; program.len() + 1];
+    live[program.len()][Z] = true;
+    for (i, &instr) in program.iter().enumerate().rev() {
+        let mut l = live[i + 1];
+        match instr {
+            Instr::Inp(a) => l[a] = false,
+            // Always zero, whatever the register was
+            Instr::Bin(Op::Mul, a, Src::Imm(0)) => l[a] = false,
+            // These can fail so the operands matter even if the result doesn't
+            Instr::Bin(Op::Div | Op::Mod, a, b) => {
+                l[a] = true;
+                if let Src::Reg(b) = b {
+                    l[b] = true;
+                }
+            }
+            Instr::Bin(_, a, b) => {
+                if l[a] {
+                    if let Src::Reg(b) = b {
+                        l[b] = true;
+                    }
+                }
+            }
+        }
+        live[i] = l;
+    }
+    live
+}
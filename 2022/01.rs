@@ -38,10 +38,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "1000
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "1000
 2000
 3000
 
@@ -55,14 +55,16 @@ fn example() {
 9000
 
 10000",
-    );
-    assert_eq!(part1(input.clone()), 24000);
-    assert_eq!(part2(input), 45000);
+        );
+        assert_eq!(part1(input.clone()), 24000);
+        assert_eq!(part2(input), 45000);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 71300);
-    assert_eq!(part2(input), 209691);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 71300);
+        assert_eq!(part2(input), 209691);
+    }
 }
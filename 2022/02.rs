@@ -55,20 +55,22 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "A Y
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "A Y
 B X
 C Z",
-    );
-    assert_eq!(part1(input.clone()), 15);
-    assert_eq!(part2(input), 12);
+        );
+        assert_eq!(part1(input.clone()), 15);
+        assert_eq!(part2(input), 12);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 9241);
-    assert_eq!(part2(input), 14610);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 9241);
+        assert_eq!(part2(input), 14610);
+    }
 }
@@ -60,23 +60,25 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "vJrwpWtwJgWrhcsFMMfFFhFp
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw",
-    );
-    assert_eq!(part1(input.clone()), 157);
-    assert_eq!(part2(input), 70);
+        );
+        assert_eq!(part1(input.clone()), 157);
+        assert_eq!(part2(input), 70);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 7428);
-    assert_eq!(part2(input), 2650);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 7428);
+        assert_eq!(part2(input), 2650);
+    }
 }
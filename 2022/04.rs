@@ -53,23 +53,25 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "2-4,6-8
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "2-4,6-8
 2-3,4-5
 5-7,7-9
 2-8,3-7
 6-6,4-6
 2-6,4-8",
-    );
-    assert_eq!(part1(input.clone()), 2);
-    assert_eq!(part2(input), 4);
+        );
+        assert_eq!(part1(input.clone()), 2);
+        assert_eq!(part2(input), 4);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 605);
-    assert_eq!(part2(input), 914);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 605);
+        assert_eq!(part2(input), 914);
+    }
 }
@@ -90,10 +90,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "    [D]
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "    [D]
 [N] [C]
 [Z] [M] [P]
  1   2   3
@@ -102,14 +102,16 @@ move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2",
-    );
-    assert_eq!(part1(input.clone()), "CMZ");
-    assert_eq!(part2(input), "MCD");
+        );
+        assert_eq!(part1(input.clone()), "CMZ");
+        assert_eq!(part2(input), "MCD");
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), "ZSQVCCJLL");
-    assert_eq!(part2(input), "QZFJRWHGS");
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), "ZSQVCCJLL");
+        assert_eq!(part2(input), "QZFJRWHGS");
+    }
 }
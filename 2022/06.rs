@@ -33,27 +33,30 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    assert_eq!(part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 7);
-    assert_eq!(part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
-    assert_eq!(part1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
-    assert_eq!(part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
-    assert_eq!(part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
-}
-
-#[test]
-fn example2() {
-    assert_eq!(part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-    assert_eq!(part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
-    assert_eq!(part2("nppdvjthqldpwncqszvftbrmjlhg"), 23);
-    assert_eq!(part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
-    assert_eq!(part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
-}
-
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 1361);
-    assert_eq!(part2(input), 3263);
+advent::example! {
+    fn example1() {
+        assert_eq!(part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 7);
+        assert_eq!(part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
+        assert_eq!(part1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
+        assert_eq!(part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
+        assert_eq!(part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
+    }
+}
+
+advent::example! {
+    fn example2() {
+        assert_eq!(part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
+        assert_eq!(part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
+        assert_eq!(part2("nppdvjthqldpwncqszvftbrmjlhg"), 23);
+        assert_eq!(part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
+        assert_eq!(part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+    }
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 1361);
+        assert_eq!(part2(input), 3263);
+    }
 }
@@ -47,10 +47,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "$ cd /
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "$ cd /
 $ ls
 dir a
 14848514 b.txt
@@ -73,14 +73,16 @@ $ ls
 8033020 d.log
 5626152 d.ext
 7214296 k",
-    );
-    assert_eq!(part1(input.clone()), 95437);
-    assert_eq!(part2(input), 24933642);
+        );
+        assert_eq!(part1(input.clone()), 95437);
+        assert_eq!(part2(input), 24933642);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1477771);
-    assert_eq!(part2(input), 3579501);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1477771);
+        assert_eq!(part2(input), 3579501);
+    }
 }
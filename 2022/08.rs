@@ -91,22 +91,24 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "30373
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "30373
 25512
 65332
 33549
 35390",
-    );
-    assert_eq!(part1(input), 21);
-    assert_eq!(part2(input), 8);
+        );
+        assert_eq!(part1(input), 21);
+        assert_eq!(part2(input), 8);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 1845);
-    assert_eq!(part2(input), 230112);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 1845);
+        assert_eq!(part2(input), 230112);
+    }
 }
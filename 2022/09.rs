@@ -63,10 +63,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "R 4
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "R 4
 U 4
 L 3
 D 1
@@ -74,14 +74,15 @@ R 4
 D 1
 L 5
 R 2",
-    );
-    assert_eq!(part1(input), 13);
+        );
+        assert_eq!(part1(input), 13);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = parse_input(
-        "R 5
+advent::example! {
+    fn example2() {
+        let input = parse_input(
+            "R 5
 U 8
 L 8
 D 3
@@ -89,13 +90,15 @@ R 17
 D 10
 L 25
 U 20",
-    );
-    assert_eq!(part2(input), 36);
+        );
+        assert_eq!(part2(input), 36);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 6175);
-    assert_eq!(part2(input), 2578);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 6175);
+        assert_eq!(part2(input), 2578);
+    }
 }
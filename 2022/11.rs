@@ -108,10 +108,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "Monkey 0:
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "Monkey 0:
   Starting items: 79, 98
   Operation: new = old * 19
   Test: divisible by 23
@@ -138,14 +138,16 @@ Monkey 3:
   Test: divisible by 17
     If true: throw to monkey 0
     If false: throw to monkey 1",
-    );
-    assert_eq!(part1(input.clone()), 10605);
-    assert_eq!(part2(input), 2713310158);
+        );
+        assert_eq!(part1(input.clone()), 10605);
+        assert_eq!(part2(input), 2713310158);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 66802);
-    assert_eq!(part2(input), 21800916620);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 66802);
+        assert_eq!(part2(input), 21800916620);
+    }
 }
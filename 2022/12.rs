@@ -78,21 +78,23 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = "Sabqponm
+advent::example! {
+    fn example() {
+        let input = "Sabqponm
 abcryxxl
 accszExk
 acctuvwj
 abdefghi";
-    let input = parse_input(input);
-    assert_eq!(part1(input), 31);
-    assert_eq!(part2(input), 29);
+        let input = parse_input(input);
+        assert_eq!(part1(input), 31);
+        assert_eq!(part2(input), 29);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 361);
-    assert_eq!(part2(input), 354);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 361);
+        assert_eq!(part2(input), 354);
+    }
 }
@@ -90,10 +90,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "[1,1,3,1,1]
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "[1,1,3,1,1]
 [1,1,5,1,1]
 
 [[1],[2,3,4]]
@@ -116,14 +116,16 @@ fn example() {
 
 [1,[2,[3,[4,[5,6,7]]]],8,9]
 [1,[2,[3,[4,[5,6,0]]]],8,9]",
-    );
-    assert_eq!(part1(input.clone()), 13);
-    assert_eq!(part2(input), 140);
+        );
+        assert_eq!(part1(input.clone()), 13);
+        assert_eq!(part2(input), 140);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 5557);
-    assert_eq!(part2(input), 22425);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 5557);
+        assert_eq!(part2(input), 22425);
+    }
 }
@@ -72,19 +72,21 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "498,4 -> 498,6 -> 496,6
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9",
-    );
-    assert_eq!(part1(input.clone()), 24);
-    assert_eq!(part2(input), 93);
+        );
+        assert_eq!(part1(input.clone()), 24);
+        assert_eq!(part2(input), 93);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 825);
-    assert_eq!(part2(input), 26729);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 825);
+        assert_eq!(part2(input), 26729);
+    }
 }
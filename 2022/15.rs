@@ -99,10 +99,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
 Sensor at x=12, y=14: closest beacon is at x=10, y=16
@@ -116,14 +116,16 @@ Sensor at x=17, y=20: closest beacon is at x=21, y=22
 Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3",
-    );
-    assert_eq!(part1(input.clone()), 26);
-    assert_eq!(part2(input), 56000011);
+        );
+        assert_eq!(part1(input.clone()), 26);
+        assert_eq!(part2(input), 56000011);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 5073496);
-    assert_eq!(part2(input), 13081194638237);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 5073496);
+        assert_eq!(part2(input), 13081194638237);
+    }
 }
@@ -134,10 +134,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
 Valve BB has flow rate=13; tunnels lead to valves CC, AA
 Valve CC has flow rate=2; tunnels lead to valves DD, BB
 Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
@@ -147,14 +147,16 @@ Valve GG has flow rate=0; tunnels lead to valves FF, HH
 Valve HH has flow rate=22; tunnel leads to valve GG
 Valve II has flow rate=0; tunnels lead to valves AA, JJ
 Valve JJ has flow rate=21; tunnel leads to valve II",
-    );
-    assert_eq!(part1(input.clone()), 1651);
-    assert_eq!(part2(input), 1707);
+        );
+        assert_eq!(part1(input.clone()), 1651);
+        assert_eq!(part2(input), 1707);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1896);
-    assert_eq!(part2(input), 2576);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1896);
+        assert_eq!(part2(input), 2576);
+    }
 }
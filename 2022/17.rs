@@ -112,16 +112,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
-    assert_eq!(part1(input.clone()), 3068);
-    assert_eq!(part2(input), 1514285714288);
+advent::example! {
+    fn example() {
+        let input = parse_input(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+        assert_eq!(part1(input.clone()), 3068);
+        assert_eq!(part2(input), 1514285714288);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3211);
-    assert_eq!(part2(input), 1589142857183);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3211);
+        assert_eq!(part2(input), 1589142857183);
+    }
 }
@@ -78,10 +78,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "2,2,2
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "2,2,2
 1,2,2
 3,2,2
 2,1,2
@@ -94,14 +94,16 @@ fn example() {
 3,2,5
 2,1,5
 2,3,5",
-    );
-    assert_eq!(part1(input.clone()), 64);
-    assert_eq!(part2(input), 58);
+        );
+        assert_eq!(part1(input.clone()), 64);
+        assert_eq!(part2(input), 58);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 4504);
-    assert_eq!(part2(input), 2556);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 4504);
+        assert_eq!(part2(input), 2556);
+    }
 }
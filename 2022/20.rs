@@ -39,16 +39,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input("1\n2\n-3\n3\n-2\n0\n4");
-    assert_eq!(part1(input.clone()), 3);
-    assert_eq!(part2(input), 1623178306);
-}
-
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 4914);
-    assert_eq!(part2(input), 7973051839072);
+advent::example! {
+    fn example() {
+        let input = parse_input("1\n2\n-3\n3\n-2\n0\n4");
+        assert_eq!(part1(input.clone()), 3);
+        assert_eq!(part2(input), 1623178306);
+    }
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 4914);
+        assert_eq!(part2(input), 7973051839072);
+    }
 }
@@ -120,10 +120,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "root: pppw + sjmn
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "root: pppw + sjmn
 dbpl: 5
 cczh: sllz + lgvd
 zczc: 2
@@ -138,14 +138,16 @@ pppw: cczh / lfqf
 lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32",
-    );
-    assert_eq!(part1(input.clone()), 152);
-    assert_eq!(part2(input), 301);
+        );
+        assert_eq!(part1(input.clone()), 152);
+        assert_eq!(part2(input), 301);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 24947355373338);
-    assert_eq!(part2(input), 3876907167495);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 24947355373338);
+        assert_eq!(part2(input), 3876907167495);
+    }
 }
@@ -183,10 +183,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "        ...#
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "        ...#
         .#..
         #...
         ....
@@ -201,36 +201,39 @@ fn example() {
 
 10R5L5R10L4R5L5
 ",
-    );
-    assert_eq!(part1(input), 6032);
+        );
+        assert_eq!(part1(input), 6032);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 189140);
-    assert_eq!(part2(input), 115063);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 189140);
+        assert_eq!(part2(input), 115063);
+    }
 }
 
-#[test]
-fn test_wrap_cube() {
-    let inv = |d| match d {
-        Right => Left,
-        Down => Up,
-        Left => Right,
-        Up => Down,
-    };
-    let (map, _) = default_input();
-    for &p in map.keys().sorted() {
-        for d in [Right, Down, Left, Up] {
-            let (np, nd) = wrap_cube(&map, p, d);
-            if (np, nd) == (p, d) {
-                continue;
+advent::answer! {
+    fn test_wrap_cube() {
+        let inv = |d| match d {
+            Right => Left,
+            Down => Up,
+            Left => Right,
+            Up => Down,
+        };
+        let (map, _) = default_input();
+        for &p in map.keys().sorted() {
+            for d in [Right, Down, Left, Up] {
+                let (np, nd) = wrap_cube(&map, p, d);
+                if (np, nd) == (p, d) {
+                    continue;
+                }
+                let nd_back = inv(nd);
+                let (rp, rd_back) = wrap_cube(&map, np, nd_back);
+                let rd = inv(rd_back);
+                assert_eq!((p, d), (rp, rd));
             }
-            let nd_back = inv(nd);
-            let (rp, rd_back) = wrap_cube(&map, np, nd_back);
-            let rd = inv(rd_back);
-            assert_eq!((p, d), (rp, rd));
         }
     }
 }
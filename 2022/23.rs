@@ -82,24 +82,26 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = parse_input(
-        "....#..
+advent::example! {
+    fn example1() {
+        let input = parse_input(
+            "....#..
 ..###.#
 #...#.#
 .#...##
 #.###..
 ##.#.##
 .#..#..",
-    );
-    assert_eq!(part1(input.clone()), 110);
-    assert_eq!(part2(input), 20);
+        );
+        assert_eq!(part1(input.clone()), 110);
+        assert_eq!(part2(input), 20);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3849);
-    assert_eq!(part2(input), 995);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3849);
+        assert_eq!(part2(input), 995);
+    }
 }
@@ -101,23 +101,25 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example() {
-    let input = parse_input(
-        "#.######
+advent::example! {
+    fn example() {
+        let input = parse_input(
+            "#.######
 #>>.<^<#
 #.<..<<#
 #>v.><>#
 #<^v^^>#
 ######.#",
-    );
-    assert_eq!(part1(input.clone()), 18);
-    assert_eq!(part2(input), 54);
+        );
+        assert_eq!(part1(input.clone()), 18);
+        assert_eq!(part2(input), 54);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 279);
-    assert_eq!(part2(input), 762);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 279);
+        assert_eq!(part2(input), 762);
+    }
 }
@@ -45,32 +45,34 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), "2=-0=01----22-0-1-10");
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), "2=-0=01----22-0-1-10");
+    }
 }
 
-#[test]
-fn to_and_from() {
-    for (dec, snafu) in [
-        (1, "1"),
-        (2, "2"),
-        (3, "1="),
-        (4, "1-"),
-        (5, "10"),
-        (6, "11"),
-        (7, "12"),
-        (8, "2="),
-        (9, "2-"),
-        (10, "20"),
-        (15, "1=0"),
-        (20, "1-0"),
-        (2022, "1=11-2"),
-        (12345, "1-0---0"),
-        (314159265, "1121-1110-1=0"),
-    ] {
-        assert_eq!(to_snafu(dec), snafu);
-        assert_eq!(from_snafu(snafu), dec);
+advent::example! {
+    fn to_and_from() {
+        for (dec, snafu) in [
+            (1, "1"),
+            (2, "2"),
+            (3, "1="),
+            (4, "1-"),
+            (5, "10"),
+            (6, "11"),
+            (7, "12"),
+            (8, "2="),
+            (9, "2-"),
+            (10, "20"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+        ] {
+            assert_eq!(to_snafu(dec), snafu);
+            assert_eq!(from_snafu(snafu), dec);
+        }
     }
 }
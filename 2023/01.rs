@@ -56,20 +56,21 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "\
+advent::example! {
+    fn example1() {
+        let input = "\
 1abc2
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet";
-    assert_eq!(part1(input), 142);
-    assert_eq!(part2(input), 142);
+        assert_eq!(part1(input), 142);
+        assert_eq!(part2(input), 142);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = "\
+advent::example! {
+    fn example2() {
+        let input = "\
 two1nine
 eightwothree
 abcone2threexyz
@@ -77,12 +78,14 @@ xtwone3four
 4nineeightseven2
 zoneight234
 7pqrstsixteen";
-    assert_eq!(part2(input), 281);
+        assert_eq!(part2(input), 281);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 54388);
-    assert_eq!(part2(input), 53515);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 54388);
+        assert_eq!(part2(input), 53515);
+    }
 }
@@ -89,9 +89,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "467..114..
+advent::example! {
+    fn example1() {
+        let input = "467..114..
 ...*......
 ..35..633.
 ......#...
@@ -101,13 +101,15 @@ fn example1() {
 ......755.
 ...$.*....
 .664.598..";
-    assert_eq!(part1(input), 4361);
-    assert_eq!(part2(input), 467835);
+        assert_eq!(part1(input), 4361);
+        assert_eq!(part2(input), 467835);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 525911);
-    assert_eq!(part2(input), 75805607);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 525911);
+        assert_eq!(part2(input), 75805607);
+    }
 }
@@ -35,21 +35,23 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+advent::example! {
+    fn example1() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-    assert_eq!(part1(parse_input(input)), 13);
-    assert_eq!(part2(parse_input(input)), 30);
+        assert_eq!(part1(parse_input(input)), 13);
+        assert_eq!(part2(parse_input(input)), 30);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 23750);
-    assert_eq!(part2(input), 13261850);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 23750);
+        assert_eq!(part2(input), 13261850);
+    }
 }
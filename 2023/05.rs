@@ -101,9 +101,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "seeds: 79 14 55 13
+advent::example! {
+    fn example1() {
+        let input = "seeds: 79 14 55 13
 
 seed-to-soil map:
 50 98 2
@@ -136,13 +136,15 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4";
-    assert_eq!(part1(parse_input(input)), 35);
-    assert_eq!(part2(parse_input(input)), 46);
+        assert_eq!(part1(parse_input(input)), 35);
+        assert_eq!(part2(parse_input(input)), 46);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 379811651);
-    assert_eq!(part2(input), 27992443);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 379811651);
+        assert_eq!(part2(input), 27992443);
+    }
 }
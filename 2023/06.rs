@@ -55,9 +55,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 2374848);
-    assert_eq!(part2(input), 39132886);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 2374848);
+        assert_eq!(part2(input), 39132886);
+    }
 }
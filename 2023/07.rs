@@ -93,20 +93,22 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "32T3K 765
+advent::example! {
+    fn example1() {
+        let input = "32T3K 765
 T55J5 684
 KK677 280
 KTJJT 220
 QQQJA 483";
-    assert_eq!(part1(parse_input(input)), 6440);
-    assert_eq!(part2(parse_input(input)), 5905);
+        assert_eq!(part1(parse_input(input)), 6440);
+        assert_eq!(part2(parse_input(input)), 5905);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 253866470);
-    assert_eq!(part2(input), 254494947);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 253866470);
+        assert_eq!(part2(input), 254494947);
+    }
 }
@@ -72,9 +72,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "RL
+advent::example! {
+    fn example1() {
+        let input = "RL
 
 AAA = (BBB, CCC)
 BBB = (DDD, EEE)
@@ -83,22 +83,24 @@ DDD = (DDD, DDD)
 EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)";
-    assert_eq!(part1(parse_input(input)), 2);
+        assert_eq!(part1(parse_input(input)), 2);
+    }
 }
 
-#[test]
-fn example2() {
-    let input = "LLR
+advent::example! {
+    fn example2() {
+        let input = "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
-    assert_eq!(part1(parse_input(input)), 6);
+        assert_eq!(part1(parse_input(input)), 6);
+    }
 }
 
-#[test]
-fn example3() {
-    let input = "LR
+advent::example! {
+    fn example3() {
+        let input = "LR
 
 11A = (11B, XXX)
 11B = (XXX, 11Z)
@@ -109,12 +111,14 @@ fn example3() {
 22Z = (22B, 22B)
 XXX = (XXX, XXX)
 ";
-    assert_eq!(part2(parse_input(input)), 6);
+        assert_eq!(part2(parse_input(input)), 6);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 19241);
-    assert_eq!(part2(input), 9606140307013);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 19241);
+        assert_eq!(part2(input), 9606140307013);
+    }
 }
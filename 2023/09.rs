@@ -38,9 +38,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1974913025);
-    assert_eq!(part2(input), 884);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1974913025);
+        assert_eq!(part2(input), 884);
+    }
 }
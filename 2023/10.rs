@@ -72,9 +72,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "..........
+advent::example! {
+    fn example1() {
+        let input = "..........
 .S------7.
 .|F----7|.
 .||....||.
@@ -84,13 +84,15 @@ fn example1() {
 .L--JL--J.
 ..........
 ";
-    assert_eq!(part1(input), 22);
-    assert_eq!(part2(input), 5);
+        assert_eq!(part1(input), 22);
+        assert_eq!(part2(input), 5);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input), 7086);
-    assert_eq!(part2(input), 317);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input), 7086);
+        assert_eq!(part2(input), 317);
+    }
 }
@@ -102,9 +102,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "...#......
+advent::example! {
+    fn example1() {
+        let input = "...#......
 .......#..
 #.........
 ..........
@@ -115,13 +115,15 @@ fn example1() {
 .......#..
 #...#.....
 ";
-    assert_eq!(part1(parse_input(input)), 374);
-    assert_eq!(part2(parse_input(input)), 82000210);
+        assert_eq!(part1(parse_input(input)), 374);
+        assert_eq!(part2(parse_input(input)), 82000210);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 9545480);
-    assert_eq!(part2(input), 406725732046);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 9545480);
+        assert_eq!(part2(input), 406725732046);
+    }
 }
@@ -148,22 +148,24 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "???.### 1,1,3
+advent::example! {
+    fn example1() {
+        let input = "???.### 1,1,3
 .??..??...?##. 1,1,3
 ?#?#?#?#?#?#?#? 1,3,1,6
 ????.#...#... 4,1,1
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1
 ";
-    assert_eq!(part1(parse_input(input)), 21);
-    assert_eq!(part2(parse_input(input)), 525152);
+        assert_eq!(part1(parse_input(input)), 21);
+        assert_eq!(part2(parse_input(input)), 525152);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 7344);
-    assert_eq!(part2(input), 1088006519007);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 7344);
+        assert_eq!(part2(input), 1088006519007);
+    }
 }
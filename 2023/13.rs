@@ -72,9 +72,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "#.##..##.
+advent::example! {
+    fn example1() {
+        let input = "#.##..##.
 ..#.##.#.
 ##......#
 ##......#
@@ -90,13 +90,15 @@ fn example1() {
 ..##..###
 #....#..#
 ";
-    assert_eq!(part1(parse_input(input)), 405);
-    // assert_eq!(part2(parse_input(input)), 400);
+        assert_eq!(part1(parse_input(input)), 405);
+        // assert_eq!(part2(parse_input(input)), 400);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 27505);
-    assert_eq!(part2(input), 22906);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 27505);
+        assert_eq!(part2(input), 22906);
+    }
 }
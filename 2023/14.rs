@@ -86,9 +86,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "O....#....
+advent::example! {
+    fn example1() {
+        let input = "O....#....
 O.OO#....#
 .....##...
 OO.#O....O
@@ -99,13 +99,15 @@ O.#..O.#.#
 #....###..
 #OO..#....
 ";
-    assert_eq!(part1(parse_input(input)), 136);
-    assert_eq!(part2(parse_input(input)), 64);
+        assert_eq!(part1(parse_input(input)), 136);
+        assert_eq!(part2(parse_input(input)), 64);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 106990);
-    assert_eq!(part2(input), 100531);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 106990);
+        assert_eq!(part2(input), 100531);
+    }
 }
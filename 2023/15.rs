@@ -49,16 +49,18 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-    assert_eq!(part1(parse_input(input)), 1320);
-    assert_eq!(part2(parse_input(input)), 145);
+advent::example! {
+    fn example1() {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+        assert_eq!(part1(parse_input(input)), 1320);
+        assert_eq!(part2(parse_input(input)), 145);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 505427);
-    assert_eq!(part2(input), 243747);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 505427);
+        assert_eq!(part2(input), 243747);
+    }
 }
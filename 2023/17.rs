@@ -117,9 +117,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "2413432311323
+advent::examples! {
+    parse_input => (part1, part2);
+    example1: "2413432311323
 3215453535623
 3255245654254
 3446585845452
@@ -132,26 +132,19 @@ fn example1() {
 1224686865563
 2546548887735
 4322674655533
-";
-    assert_eq!(part1(parse_input(input)), 102);
-    assert_eq!(part2(parse_input(input)), 94);
-}
-
-#[test]
-fn example2() {
-    let input = "111111111111
+" => (102, 94),
+    example2: "111111111111
 999999999991
 999999999991
 999999999991
 999999999991
-";
-    assert_eq!(part1(parse_input(input)), 59);
-    assert_eq!(part2(parse_input(input)), 71);
+" => (59, 71),
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 635);
-    assert_eq!(part2(input), 734);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 635);
+        assert_eq!(part2(input), 734);
+    }
 }
@@ -72,9 +72,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "R 6 (#70c710)
+advent::example! {
+    fn example1() {
+        let input = "R 6 (#70c710)
 D 5 (#0dc571)
 L 2 (#5713f0)
 D 2 (#d2c081)
@@ -89,13 +89,15 @@ U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)
 ";
-    assert_eq!(part1(parse_input(input)), 62);
-    assert_eq!(part2(parse_input(input)), 952408144115);
+        assert_eq!(part1(parse_input(input)), 62);
+        assert_eq!(part2(parse_input(input)), 952408144115);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 50746);
-    assert_eq!(part2(input), 70086216556038);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 50746);
+        assert_eq!(part2(input), 70086216556038);
+    }
 }
@@ -158,9 +158,9 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn example1() {
-    let input = "px{a<2006:qkq,m>2090:A,rfg}
+advent::example! {
+    fn example1() {
+        let input = "px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
 lnx{m>1548:A,A}
 rfg{s<537:gd,x>2440:R,A}
@@ -178,13 +178,15 @@ hdj{m>838:A,pv}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}
 ";
-    assert_eq!(part1(parse_input(input)), 19114);
-    assert_eq!(part2(parse_input(input)), 167409079868000);
+        assert_eq!(part1(parse_input(input)), 19114);
+        assert_eq!(part2(parse_input(input)), 167409079868000);
+    }
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 449531);
-    assert_eq!(part2(input), 122756210763577);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 449531);
+        assert_eq!(part2(input), 122756210763577);
+    }
 }
@@ -59,9 +59,10 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 3782);
-    assert_eq!(part2(input), 630661863455116);
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 3782);
+        assert_eq!(part2(input), 630661863455116);
+    }
 }
@@ -27,6 +27,10 @@ pub struct Time(Number);
 #[derive(Debug, Clone, Copy)]
 pub struct Samples(Number);
 
+/// Represents a count of anything, possibly fractional, e.g. a rate.
+#[derive(Debug, Clone, Copy)]
+pub struct Count(Number);
+
 impl Number {
     pub fn new(v: f64) -> Self {
         let scales = [
@@ -80,6 +84,13 @@ impl Samples {
     }
 }
 
+impl Count {
+    pub fn new(v: f64) -> Self {
+        let Number(_, scale) = Number::new(v);
+        Self(Number::with_scale(v, max(scale, Scale::Unit)))
+    }
+}
+
 impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Some examples of what we need to handle nicely
@@ -136,6 +147,31 @@ impl fmt::Display for Samples {
     }
 }
 
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Some examples of what we need to handle nicely
+        //
+        // - 0.500
+        // - 123.4
+        // - 1.234k
+        // - 412.3M
+        //
+        // Rules:
+        // - Always use the same number of significant figures
+
+        let &Self(Number(v, s)) = self;
+        let p = 4 - digits(v);
+        let suffix = match s {
+            Scale::Unit => "",
+            Scale::Kilo => "k",
+            Scale::Mega => "M",
+            Scale::Giga => "G",
+            _ => unreachable!(),
+        };
+        fmt::Display::fmt(&format!("{v:.p$}{suffix}"), f)
+    }
+}
+
 fn digits(mut v: f64) -> usize {
     let mut n = 0;
     while v >= 1. {
@@ -186,4 +222,18 @@ mod tests {
             assert_eq!(Samples::new(t).to_string(), expected);
         }
     }
+
+    #[test]
+    fn count_display() {
+        let test_cases = [
+            (0.5, "0.500"),
+            (123.4, "123.4"),
+            (1234.0, "1.234k"),
+            (412_300_000.0, "412.3M"),
+            (2.5e9, "2.500G"),
+        ];
+        for (v, expected) in test_cases {
+            assert_eq!(Count::new(v).to_string(), expected);
+        }
+    }
 }
@@ -46,6 +46,8 @@
 //!   ```
 //!   use advent::prelude::*;
 //!   ```
+//! - **`testing`** enables the [`testing`][crate::testing] module for property
+//!   based and fuzz testing of parse functions
 //!
 //! They can be enabled in your Cargo manifest like this:
 //!
@@ -54,17 +56,59 @@
 //! advent = { git = "https://github.com/rossmacarthur/advent", features = ["festive", "json"] }
 //! ```
 //!
+//! # Testing
+//!
+//! Tests can be tagged using the [`example!`] and [`answer!`] macros
+//! depending on whether they use an example from the puzzle description or the
+//! real puzzle input.
+//!
+//! ```
+//! # fn default_input() -> &'static str { "abc" }
+//! # fn part1(s: &str) -> usize { s.len() }
+//! advent::example! {
+//!     fn example() {
+//!         assert_eq!(part1("a"), 1);
+//!     }
+//! }
+//!
+//! advent::answer! {
+//!     fn default() {
+//!         assert_eq!(part1(default_input()), 3);
+//!     }
+//! }
+//! ```
+//!
+//! Example tests are named `<name>::example` and answer tests are named
+//! `<name>::answer` so they can be filtered using the usual test harness
+//! filters, e.g. `cargo test -- ::example`.
+//!
+//! When there are several examples that test both parts the same way the
+//! [`examples!`] macro can be used to declare them as a table.
+//!
+//! ```
+//! # fn parse_input(s: &str) -> Vec<i64> { s.lines().map(|l| l.parse().unwrap()).collect() }
+//! # fn part1(v: Vec<i64>) -> i64 { v.iter().sum() }
+//! # fn part2(v: Vec<i64>) -> i64 { v.iter().product() }
+//! advent::examples! {
+//!     parse_input => (part1, part2);
+//!     example1: "1\n2\n3" => (6, 6),
+//!     example2: "2\n2" => (_, 4),
+//! }
+//! ```
+//!
 //! # CLI
 //!
 //! The command line interface looks like this.
 //!
 //! ```text
-//! Usage: example [--bench] [--output <output>]
+//! Usage: example [--bench] [--profile <profile>] [--duration <duration>] [--output <output>]
 //!
 //! Run the program.
 //!
 //! Options:
 //!   --bench           whether to benchmark
+//!   --profile         run the given part in a loop, for use with a profiler
+//!   --duration        how many seconds to loop for when profiling
 //!   --output          the output style
 //!   --help            display usage information
 //! ```
@@ -72,6 +116,10 @@
 //! The output style can be `boring`, `festive`, or `json`. To use json this
 //! crate requires the `json` feature to be set.
 //!
+//! Passing `--profile 2` runs part 2 over and over for `--duration` seconds
+//! (10 by default) so that a profiler like `perf` has something to sample.
+//! This is what `cargo advent profile` uses under the hood.
+//!
 
 use std::fmt::Display;
 use std::hint;
@@ -84,21 +132,34 @@ use yansi::Paint;
 #[cfg(feature = "prelude")]
 pub use prelude;
 
-use crate::summary::{Bench, Run, Summary};
+#[doc(hidden)]
+pub use crate::macros::check_example;
+
+use crate::summary::{Bench, Profile, Run, Summary, Work};
 
 mod human;
+mod macros;
 mod stats;
 pub mod summary;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 type FnParse<'a, I> = Box<dyn Fn() -> I + 'a>;
 type FnPart<'a, I> = Box<dyn Fn(I) -> Box<dyn Display + 'a> + UnwindSafe + 'a>;
+type FnWork<'a, I> = Box<dyn Fn(I) -> u64 + UnwindSafe + 'a>;
+
+/// A part and optionally the unit and a function counting the work it does.
+struct Part<'a, I> {
+    f: FnPart<'a, I>,
+    work: Option<(String, FnWork<'a, I>)>,
+}
 
 /// A builder for a [`Solution`].
 #[must_use]
 pub struct Builder<'a, I> {
     parse: Option<FnParse<'a, I>>,
     parse_ok: bool,
-    parts: Vec<(Option<String>, FnPart<'a, I>)>,
+    parts: Vec<(Option<String>, Part<'a, I>)>,
 }
 
 /// A runner and benchmarker for an Advent of Code solution.
@@ -108,7 +169,7 @@ pub struct Builder<'a, I> {
 pub struct Solution<'a, I> {
     parse: FnParse<'a, I>,
     parse_ok: bool,
-    parts: Vec<(String, FnPart<'a, I>)>,
+    parts: Vec<(String, Part<'a, I>)>,
 }
 
 /// Returns a new builder for a new Advent of Code run or benchmark using the
@@ -144,7 +205,8 @@ impl<'a, I> Builder<'a, I>
             R: Display + 'a,
             F: Fn(I) -> R + UnwindSafe + 'a,
     {
-        self.parts.push((None, Box::new(move |i| Box::new(f(i)))));
+        let f = Box::new(move |i| Box::new(f(i)) as Box<dyn Display>);
+        self.parts.push((None, Part { f, work: None }));
         self
     }
 
@@ -159,7 +221,28 @@ impl<'a, I> Builder<'a, I>
             F: Fn(I) -> R + UnwindSafe + 'a,
     {
         let name = Some(String::from(name));
-        self.parts.push((name, Box::new(move |i| Box::new(f(i)))));
+        let f = Box::new(move |i| Box::new(f(i)) as Box<dyn Display>);
+        self.parts.push((name, Part { f, work: None }));
+        self
+    }
+
+    /// Adds a named part along with a function that counts the work it does,
+    /// e.g. the number of instructions executed, so that the rate can be
+    /// reported when benchmarking.
+    ///
+    /// The counting function is only called by [`Solution::bench`], once
+    /// outside of any timing, so it can be much slower than the part itself.
+    #[doc(hidden)]
+    pub fn named_with_work<F, W, R>(&mut self, name: &str, unit: &str, f: F, work: W) -> &mut Self
+        where
+            R: Display + 'a,
+            F: Fn(I) -> R + UnwindSafe + 'a,
+            W: Fn(I) -> u64 + UnwindSafe + 'a,
+    {
+        let name = Some(String::from(name));
+        let f = Box::new(move |i| Box::new(f(i)) as Box<dyn Display>);
+        let work = Some((String::from(unit), Box::new(work) as FnWork<'a, I>));
+        self.parts.push((name, Part { f, work }));
         self
     }
 
@@ -197,7 +280,7 @@ impl<'a, I> Solution<'a, I>
 
         // Time each part
         let input = (parse)();
-        for (name, f) in parts {
+        for (name, Part { f, .. }) in parts {
             let input = input.clone();
 
             let (result, elapsed) = {
@@ -239,29 +322,70 @@ impl<'a, I> Solution<'a, I>
             benches.push(Bench {
                 name: "Parse".to_owned(),
                 stats,
+                work: None,
             });
         }
 
         // Benchmark each part
         let input = (parse)();
-        for (name, f) in parts {
+        for (name, Part { f, work }) in parts {
             let stats = bench_with_input(input.clone(), &f);
-            benches.push(Bench { name, stats });
+            let work = count_work(work, &input);
+            benches.push(Bench { name, stats, work });
         }
 
         Summary::Bench(benches)
     }
 
+    /// Consumes this struct and runs a single part repeatedly for the given
+    /// duration.
+    ///
+    /// Parts are numbered from 1. This is intended to be run under a
+    /// profiler, the parse function is only called once up front so that it
+    /// doesn't pollute the profile.
+    pub fn profile(self, part: usize, duration: Duration) -> Summary {
+        let Self { parse, parts, .. } = self;
+
+        let len = parts.len();
+        let (name, f) = part
+            .checked_sub(1)
+            .and_then(|i| parts.into_iter().nth(i))
+            .map(|(name, part)| (name, part.f))
+            .unwrap_or_else(|| panic!("expected part in the range 1..={len}, got {part}"));
+
+        let input = (parse)();
+        let mut iterations = 0;
+        let start = Instant::now();
+        while Instant::now() - start < duration || iterations == 0 {
+            hint::black_box(f(input.clone()));
+            iterations += 1;
+        }
+        let elapsed = (Instant::now() - start).as_secs_f64();
+
+        Summary::Profile(Profile {
+            name,
+            iterations,
+            elapsed,
+        })
+    }
+
     /// Parses the command line arguments and executes the run or benchmark.
     pub fn cli(self) {
-        let Opt { bench, output } = argh::from_env();
+        let Opt {
+            bench,
+            profile,
+            duration,
+            output,
+        } = argh::from_env();
 
         #[cfg(feature = "festive")]
         if let Output::Festive = output {
             println!("{}", ascii_art::fun());
         }
 
-        let summary = if bench {
+        let summary = if let Some(part) = profile {
+            self.profile(part, Duration::from_secs(duration))
+        } else if bench {
             if cfg!(not(profile = "release")) {
                 eprintln!(
                     "{}\n",
@@ -281,6 +405,17 @@ impl<'a, I> Solution<'a, I>
     }
 }
 
+/// Counts the work done by a part, if it counts work and doesn't panic.
+fn count_work<I>(work: Option<(String, FnWork<'_, I>)>, input: &I) -> Option<Work>
+    where
+        I: Clone + UnwindSafe,
+{
+    let (unit, f) = work?;
+    let input = input.clone();
+    let count = std::panic::catch_unwind(move || f(input)).ok()?;
+    Some(Work { unit, count })
+}
+
 fn bench<F, O>(f: F) -> summary::Stats
     where
         F: Fn() -> O,
@@ -322,6 +457,12 @@ struct Opt {
     /// whether to benchmark
     #[argh(switch)]
     bench: bool,
+    /// run the given part in a loop, for use with a profiler
+    #[argh(option)]
+    profile: Option<usize>,
+    /// how many seconds to loop for when profiling
+    #[argh(option, default = "10")]
+    duration: u64,
     /// the output style (boring, festive, json)
     #[argh(option, default = "default_output()")]
     output: Output,
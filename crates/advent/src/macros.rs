@@ -0,0 +1,161 @@
+//! Macros for tagging tests.
+//!
+//! Tests that only need an example from the puzzle description are declared
+//! with [`example!`][crate::example] and tests that need the real puzzle input
+//! are declared with [`answer!`][crate::answer]. Each test is generated in a
+//! module of the given name, so the test binary reports them as
+//! `example1::example` and `default::answer`. This is what allows
+//! `cargo advent test --examples-only` and `--inputs-only` to filter them.
+
+use std::fmt::Debug;
+
+/// Declares a test that only depends on an example input.
+///
+/// # Examples
+///
+/// ```
+/// # fn part1(s: &str) -> usize { s.len() }
+/// advent::example! {
+///     fn example1() {
+///         assert_eq!(part1("abc"), 3);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! example {
+    ($(#[$attr:meta])* fn $name:ident() $(-> $ret:ty)? $body:block) => {
+        #[cfg(test)]
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            $(#[$attr])*
+            fn example() $(-> $ret)? $body
+        }
+    };
+}
+
+/// Declares a test that depends on the real puzzle input.
+///
+/// # Examples
+///
+/// ```
+/// # fn default_input() -> &'static str { "abc" }
+/// # fn part1(s: &str) -> usize { s.len() }
+/// advent::answer! {
+///     fn default() {
+///         assert_eq!(part1(default_input()), 3);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! answer {
+    ($(#[$attr:meta])* fn $name:ident() $(-> $ret:ty)? $body:block) => {
+        #[cfg(test)]
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            $(#[$attr])*
+            fn answer() $(-> $ret)? $body
+        }
+    };
+}
+
+/// Declares a table of example tests.
+///
+/// The first line gives the parse function and the two part functions, each
+/// following line gives the name of the test, the input, and the expected
+/// answers for part 1 and part 2. An expected answer of `_` means that the
+/// example doesn't apply to that part and it won't be run. The input can be
+/// any `&str` expression, e.g. a string literal or an `include_str!`.
+///
+/// Each entry generates an [`example!`][crate::example] test, failures report
+/// the example name and both answers.
+///
+/// # Examples
+///
+/// ```
+/// # fn parse_input(s: &str) -> Vec<i64> { s.lines().map(|l| l.parse().unwrap()).collect() }
+/// # fn part1(v: Vec<i64>) -> i64 { v.iter().sum() }
+/// # fn part2(v: Vec<i64>) -> i64 { v.iter().product() }
+/// advent::examples! {
+///     parse_input => (part1, part2);
+///     example1: "1\n2\n3" => (6, 6),
+///     example2: "2\n2" => (_, 4),
+/// }
+/// ```
+#[macro_export]
+macro_rules! examples {
+    (
+        $parse:expr => ($part1:expr, $part2:expr);
+        $($name:ident: $input:expr => $expected:tt),+ $(,)?
+    ) => {
+        $(
+            $crate::example! {
+                fn $name() {
+                    $crate::examples!(@check $name, $parse, $part1, $part2, $input, $expected);
+                }
+            }
+        )+
+    };
+
+    (@check $name:ident, $parse:expr, $part1:expr, $part2:expr, $input:expr, (_, $two:expr)) => {
+        let input = ($parse)($input);
+        $crate::check_example(
+            stringify!($name),
+            None::<((), ())>,
+            Some((($part2)(input), $two)),
+        );
+    };
+
+    (@check $name:ident, $parse:expr, $part1:expr, $part2:expr, $input:expr, ($one:expr, _)) => {
+        let input = ($parse)($input);
+        $crate::check_example(
+            stringify!($name),
+            Some((($part1)(input), $one)),
+            None::<((), ())>,
+        );
+    };
+
+    (@check $name:ident, $parse:expr, $part1:expr, $part2:expr, $input:expr, ($one:expr, $two:expr)) => {
+        let input = ($parse)($input);
+        $crate::check_example(
+            stringify!($name),
+            Some((($part1)(input.clone()), $one)),
+            Some((($part2)(input), $two)),
+        );
+    };
+}
+
+/// Checks the answers for an example generated by [`examples!`], panicking
+/// with both answers if either is wrong.
+#[doc(hidden)]
+#[track_caller]
+pub fn check_example<A, B, C, D>(name: &str, part1: Option<(A, B)>, part2: Option<(C, D)>)
+    where
+        A: PartialEq<B> + Debug,
+        B: Debug,
+        C: PartialEq<D> + Debug,
+        D: Debug,
+{
+    fn describe<T, U>(part: &Option<(T, U)>) -> (bool, String)
+        where
+            T: PartialEq<U> + Debug,
+            U: Debug,
+    {
+        match part {
+            None => (true, "n/a".to_owned()),
+            Some((answer, expected)) if answer == expected => (true, format!("{answer:?}")),
+            Some((answer, expected)) => (false, format!("{answer:?} (expected {expected:?})")),
+        }
+    }
+
+    let (ok1, one) = describe(&part1);
+    let (ok2, two) = describe(&part2);
+    if !(ok1 && ok2) {
+        panic!("example `{name}` failed\n  part 1: {one}\n  part 2: {two}");
+    }
+}
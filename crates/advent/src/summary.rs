@@ -10,6 +10,8 @@ pub enum Summary {
     Bench(Vec<Bench>),
     #[cfg_attr(feature = "json", serde(rename = "runs"))]
     Run(Vec<Run>),
+    #[cfg_attr(feature = "json", serde(rename = "profile"))]
+    Profile(Profile),
 }
 
 /// The result of a benchmark.
@@ -22,6 +24,8 @@ pub struct Bench {
     /// The data for the benchmark.
     #[cfg_attr(feature = "json", serde(flatten))]
     pub stats: Stats,
+    /// The work done by each sample, if it is counted.
+    pub work: Option<Work>,
 }
 
 /// The result of a run.
@@ -37,6 +41,31 @@ pub struct Run {
     pub elapsed: f64,
 }
 
+/// The result of running a single part in a loop.
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct Profile {
+    /// The name of the part.
+    pub name: String,
+    /// The number of times the part was run.
+    pub iterations: usize,
+    /// How long all the iterations took in seconds.
+    pub elapsed: f64,
+}
+
+/// An amount of work done by a part, e.g. the number of instructions
+/// executed.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct Work {
+    /// What is being counted.
+    pub unit: String,
+    /// How much work was done.
+    pub count: u64,
+}
+
 /// Data for a benchmark.
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "json", derive(serde::Serialize))]
@@ -60,6 +89,7 @@ impl Summary {
         match self {
             Self::Bench(parts) => print_bench_summary(parts),
             Self::Run(parts) => print_run_summary(parts),
+            Self::Profile(profile) => print_profile_summary(profile),
         }
     }
 
@@ -72,7 +102,7 @@ impl Summary {
 
 fn print_bench_summary(parts: &[Bench]) {
     for (i, part) in parts.iter().enumerate() {
-        let Bench { name, stats } = part;
+        let Bench { name, stats, work } = part;
         if i != 0 {
             println!();
         }
@@ -100,6 +130,14 @@ fn print_bench_summary(parts: &[Bench]) {
             Paint::cyan(&min),
             Paint::magenta(&max),
         );
+        if let Some(Work { unit, count }) = work {
+            let rate = human::Count::new(*count as f64 / stats.mean);
+            println!(
+                "  Rate ({}):           {:>9} {unit}/s",
+                Paint::yellow("mean").bold(),
+                Paint::yellow(&rate).bold(),
+            );
+        }
     }
 }
 
@@ -123,3 +161,26 @@ fn print_run_summary(parts: &[Run]) {
         );
     }
 }
+
+fn print_profile_summary(profile: &Profile) {
+    let Profile {
+        name,
+        iterations,
+        elapsed,
+    } = profile;
+    println!(
+        "{}{:>width$}",
+        Paint::new(name).bold(),
+        Paint::fixed(&human::Samples::new(*iterations), 245),
+        width = 46 - name.chars().count(),
+    );
+    let total = human::Time::new(*elapsed);
+    let each = human::Time::new(*elapsed / *iterations as f64);
+    println!(
+        "  Time ({} / {}):     {:>9} / {:>9}",
+        Paint::green("total").bold(),
+        Paint::green("each"),
+        Paint::green(&total).bold(),
+        Paint::green(&each),
+    );
+}
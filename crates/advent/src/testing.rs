@@ -0,0 +1,409 @@
+//! Property based and fuzz testing for parse functions.
+//!
+//! A [`Gen`] produces random but well-formed puzzle inputs. Pass one to
+//! [`check`] and then use the returned [`Check`] to run a property against
+//! a parse function.
+//!
+//! ```
+//! use advent::testing::{self, Grid};
+//!
+//! fn parse_input(input: &str) -> Vec<Vec<bool>> {
+//!     input
+//!         .lines()
+//!         .map(|line| line.chars().map(|c| c == '#').collect())
+//!         .collect()
+//! }
+//!
+//! fn print(map: &Vec<Vec<bool>>) -> String {
+//!     map.iter()
+//!         .map(|row| row.iter().map(|&b| if b { '#' } else { '.' }).collect::<String>())
+//!         .collect::<Vec<_>>()
+//!         .join("\n")
+//! }
+//!
+//! testing::check(Grid::new(".#")).no_panic(parse_input);
+//! testing::check(Grid::new(".#")).round_trip(parse_input, print);
+//! ```
+//!
+//! Failures report the seed of the failing case, setting the `ADVENT_SEED`
+//! environment variable to that seed reproduces the same inputs.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Debug;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub use rand;
+use rand::prelude::*;
+pub use rand::rngs::StdRng;
+
+/// A generator of random puzzle inputs.
+///
+/// This is implemented for closures so that one off formats can be written
+/// inline.
+pub trait Gen {
+    /// Generates a random input.
+    fn generate(&self, rng: &mut StdRng) -> String;
+}
+
+impl<F> Gen for F
+    where
+        F: Fn(&mut StdRng) -> String,
+{
+    fn generate(&self, rng: &mut StdRng) -> String {
+        self(rng)
+    }
+}
+
+/// Generates a rectangular grid of characters, the format read by
+/// `prelude::parse_map`.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    alphabet: Vec<char>,
+    width: RangeInclusive<usize>,
+    height: RangeInclusive<usize>,
+}
+
+impl Grid {
+    /// Returns a new grid generator that uses the characters in `alphabet`.
+    pub fn new(alphabet: &str) -> Self {
+        assert!(!alphabet.is_empty(), "expected a non-empty alphabet");
+        Self {
+            alphabet: alphabet.chars().collect(),
+            width: 1..=20,
+            height: 1..=20,
+        }
+    }
+
+    /// Sets the range of widths to generate.
+    pub fn width(mut self, width: RangeInclusive<usize>) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the range of heights to generate.
+    pub fn height(mut self, height: RangeInclusive<usize>) -> Self {
+        self.height = height;
+        self
+    }
+}
+
+impl Gen for Grid {
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let width = rng.gen_range(self.width.clone());
+        let height = rng.gen_range(self.height.clone());
+        let mut s = String::with_capacity((width + 1) * height);
+        for _ in 0..height {
+            s.extend((0..width).map(|_| *self.alphabet.choose(rng).unwrap()));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// Generates lines of integers separated by arbitrary text, the format read
+/// by `prelude::get_numbers`.
+#[derive(Debug, Clone)]
+pub struct Numbers {
+    lines: RangeInclusive<usize>,
+    per_line: RangeInclusive<usize>,
+    values: RangeInclusive<i64>,
+    separators: Vec<String>,
+}
+
+impl Numbers {
+    /// Returns a new number generator.
+    ///
+    /// By default this generates 1 to 20 lines of 1 to 5 numbers between
+    /// -1000 and 1000 separated by spaces.
+    pub fn new() -> Self {
+        Self {
+            lines: 1..=20,
+            per_line: 1..=5,
+            values: -1000..=1000,
+            separators: vec![" ".to_owned()],
+        }
+    }
+
+    /// Sets the range of the number of lines to generate.
+    pub fn lines(mut self, lines: RangeInclusive<usize>) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    /// Sets the range of the number of numbers on each line.
+    pub fn per_line(mut self, per_line: RangeInclusive<usize>) -> Self {
+        self.per_line = per_line;
+        self
+    }
+
+    /// Sets the range of values to generate.
+    pub fn values(mut self, values: RangeInclusive<i64>) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Sets the text that can appear between numbers, e.g. `", "` or
+    /// `" -> "`. One is chosen at random between each pair of numbers.
+    pub fn separators(mut self, separators: &[&str]) -> Self {
+        assert!(!separators.is_empty(), "expected at least one separator");
+        self.separators = separators.iter().map(|s| s.to_string()).collect();
+        self
+    }
+}
+
+impl Default for Numbers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Numbers {
+    /// Generates a random input, also returning the numbers in it in the
+    /// order they appear.
+    pub fn generate_with_numbers(&self, rng: &mut StdRng) -> (String, Vec<i64>) {
+        let mut s = String::new();
+        let mut numbers = Vec::new();
+        for _ in 0..rng.gen_range(self.lines.clone()) {
+            for i in 0..rng.gen_range(self.per_line.clone()) {
+                if i != 0 {
+                    s.push_str(self.separators.choose(rng).unwrap());
+                }
+                let n = rng.gen_range(self.values.clone());
+                s.push_str(&n.to_string());
+                numbers.push(n);
+            }
+            s.push('\n');
+        }
+        (s, numbers)
+    }
+}
+
+impl Gen for Numbers {
+    fn generate(&self, rng: &mut StdRng) -> String {
+        self.generate_with_numbers(rng).0
+    }
+}
+
+/// Returns a new property check using the given generator.
+pub fn check<G: Gen>(gen: G) -> Check<G> {
+    let seed = match std::env::var("ADVENT_SEED") {
+        Ok(seed) => seed.parse().expect("`ADVENT_SEED` must be a number"),
+        Err(_) => rand::random(),
+    };
+    Check {
+        gen,
+        cases: 256,
+        seed,
+    }
+}
+
+/// A property check over randomly generated inputs.
+///
+/// Constructed using [`check`].
+#[must_use]
+pub struct Check<G> {
+    gen: G,
+    cases: usize,
+    seed: u64,
+}
+
+impl<G: Gen> Check<G> {
+    /// Sets the number of inputs to generate, the default is 256.
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Sets the seed used to generate the inputs.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn inputs(&self) -> impl Iterator<Item = (u64, String)> + '_ {
+        (0..self.cases as u64).map(|i| {
+            let seed = self.seed.wrapping_add(i);
+            (seed, self.gen.generate(&mut StdRng::seed_from_u64(seed)))
+        })
+    }
+
+    /// Checks that the parse function doesn't panic on any generated input.
+    #[track_caller]
+    pub fn no_panic<F, T>(self, parse: F)
+        where
+            F: Fn(&str) -> T,
+    {
+        for (seed, input) in self.inputs() {
+            if catch(|| parse(&input)).is_none() {
+                panic!("parse panicked (seed {seed}) on input:\n{input}");
+            }
+        }
+    }
+
+    /// Checks that printing a parsed input and parsing it again gives the same
+    /// result.
+    #[track_caller]
+    pub fn round_trip<F, P, T>(self, parse: F, print: P)
+        where
+            F: Fn(&str) -> T,
+            P: Fn(&T) -> String,
+            T: PartialEq + Debug,
+    {
+        for (seed, input) in self.inputs() {
+            let Some(parsed) = catch(|| parse(&input)) else {
+                panic!("parse panicked (seed {seed}) on input:\n{input}");
+            };
+            let printed = print(&parsed);
+            let Some(reparsed) = catch(|| parse(&printed)) else {
+                panic!("parse panicked (seed {seed}) on printed input:\n{printed}");
+            };
+            if parsed != reparsed {
+                panic!(
+                    "round trip failed (seed {seed})\n  \
+                     input: {input:?}\n  \
+                     parsed: {parsed:?}\n  \
+                     printed: {printed:?}\n  \
+                     reparsed: {reparsed:?}"
+                );
+            }
+        }
+    }
+
+    /// Keeps generating inputs for the given duration, ignoring the number of
+    /// cases, and stops at the first input that makes the parse function
+    /// panic.
+    ///
+    /// Like `cargo fuzz` the crashing input is written to the artifacts
+    /// directory as `crash-<hash>` so it can be added to a corpus and replayed
+    /// with [`corpus`].
+    #[track_caller]
+    pub fn fuzz<F, T>(self, parse: F, duration: Duration, artifacts: impl AsRef<Path>)
+        where
+            F: Fn(&str) -> T,
+    {
+        let start = Instant::now();
+        let mut seed = self.seed;
+        while Instant::now() - start < duration {
+            let input = self.gen.generate(&mut StdRng::seed_from_u64(seed));
+            if catch(|| parse(&input)).is_none() {
+                let path = save_crash(artifacts.as_ref(), &input);
+                panic!(
+                    "parse panicked (seed {seed}), input written to `{}`",
+                    path.display()
+                );
+            }
+            seed = seed.wrapping_add(1);
+        }
+    }
+}
+
+/// Runs the parse function on every file in a corpus directory and panics
+/// listing every file that made it panic.
+#[track_caller]
+pub fn corpus<F, T>(dir: impl AsRef<Path>, parse: F)
+    where
+        F: Fn(&str) -> T,
+{
+    let dir = dir.as_ref();
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("failed to read corpus `{}`: {err}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    let failures: Vec<_> = paths
+        .into_iter()
+        .filter(|path| {
+            let input = fs::read_to_string(path)
+                .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", path.display()));
+            catch(|| parse(&input)).is_none()
+        })
+        .map(|path| format!("  {}", path.display()))
+        .collect();
+
+    if !failures.is_empty() {
+        panic!("parse panicked on corpus inputs:\n{}", failures.join("\n"));
+    }
+}
+
+fn catch<T>(f: impl FnOnce() -> T) -> Option<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).ok()
+}
+
+fn save_crash(artifacts: &Path, input: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    let path = artifacts.join(format!("crash-{:016x}", hasher.finish()));
+    fs::create_dir_all(artifacts)
+        .and_then(|()| fs::write(&path, input))
+        .unwrap_or_else(|err| panic!("failed to write `{}`: {err}", path.display()));
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_is_rectangular() {
+        let gen = Grid::new(".#").width(3..=3).height(2..=4);
+        check(gen).no_panic(|input| {
+            let widths: Vec<_> = input.lines().map(|line| line.chars().count()).collect();
+            assert!((2..=4).contains(&widths.len()));
+            assert!(widths.iter().all(|&w| w == 3));
+        });
+    }
+
+    #[test]
+    fn numbers_round_trip() {
+        let parse = |input: &str| -> Vec<Vec<i64>> {
+            input
+                .lines()
+                .map(|line| line.split(", ").map(|n| n.parse().unwrap()).collect())
+                .collect()
+        };
+        let print = |numbers: &Vec<Vec<i64>>| {
+            numbers
+                .iter()
+                .map(|line| line.iter().map(i64::to_string).collect::<Vec<_>>().join(", "))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        check(Numbers::new().separators(&[", "])).round_trip(parse, print);
+    }
+
+    #[test]
+    #[should_panic(expected = "parse panicked")]
+    fn no_panic_reports_failure() {
+        check(Numbers::new())
+            .cases(16)
+            .no_panic(|input| input.parse::<i64>().unwrap());
+    }
+
+    #[cfg(feature = "prelude")]
+    #[test]
+    fn get_numbers_finds_every_number() {
+        let gen = Numbers::new().separators(&[" ", ", ", " -> ", ": x=", ",y="]);
+        for seed in 0..256 {
+            let (input, numbers) = gen.generate_with_numbers(&mut StdRng::seed_from_u64(seed));
+            assert_eq!(prelude::get_numbers::<i64>(&input), numbers, "input: {input:?}");
+        }
+    }
+
+    #[cfg(feature = "prelude")]
+    #[test]
+    fn parse_map_covers_grid() {
+        let gen = Grid::new(".#").width(1..=10).height(1..=10);
+        check(gen).no_panic(|input| {
+            let map: prelude::HashMap<_, _> = prelude::parse_map(input, |c| c);
+            let expected: usize = input.lines().map(|line| line.len()).sum();
+            assert_eq!(map.len(), expected);
+        });
+    }
+}
@@ -0,0 +1,478 @@
+//! A simulator for circuits of logic gates connected by named wires.
+//!
+//! A netlist is parsed into a DAG of gates, one per wire, which is evaluated
+//! in topological order with values masked to the circuit's bit width. Wires
+//! can be overridden with a fixed value, after which only the gates downstream
+//! of the wire are re-evaluated, and the outputs of two gates can be swapped.
+//!
+//! ```
+//! use circuit::Circuit;
+//!
+//! let mut c = Circuit::parse("3 -> x\nx LSHIFT 2 -> y\nNOT y -> z", 8).unwrap();
+//! assert_eq!(c.get("z"), Some(0b1111_0011));
+//! c.set("x", 1).unwrap();
+//! assert_eq!(c.get("z"), Some(0b1111_1011));
+//! ```
+//!
+//! # Netlist format
+//!
+//! Each line drives a single wire, with signals being either wire names or
+//! constants.
+//!
+//! ```text
+//! 123 -> x
+//! x AND y -> z
+//! x OR y -> z
+//! x XOR y -> z
+//! x LSHIFT 2 -> z
+//! x RSHIFT 2 -> z
+//! NOT x -> z
+//! x: 1
+//! ```
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt::Write;
+use std::mem;
+
+use thiserror::Error;
+
+/// A wire in a circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wire(usize);
+
+/// An input to a gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+    Wire(Wire),
+    Const(u64),
+}
+
+/// A gate driving a wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gate {
+    Buf(Signal),
+    Not(Signal),
+    And(Signal, Signal),
+    Or(Signal, Signal),
+    Xor(Signal, Signal),
+    Shl(Signal, u32),
+    Shr(Signal, u32),
+}
+
+/// An error building or modifying a circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Error {
+    #[error("bit width must be between 1 and 64, got {0}")]
+    Width(u32),
+    #[error("invalid gate `{0}`")]
+    Parse(String),
+    #[error("wire `{0}` is driven more than once")]
+    MultipleDrivers(String),
+    #[error("wire `{0}` is not driven")]
+    Undriven(String),
+    #[error("unknown wire `{0}`")]
+    Unknown(String),
+    #[error("wires form a cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// A circuit of gates.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    mask: u64,
+    names: Vec<String>,
+    index: HashMap<String, Wire>,
+    gates: Vec<Gate>,
+    overrides: Vec<Option<u64>>,
+    /// The wires each wire is an input to.
+    fanout: Vec<Vec<Wire>>,
+    /// The position of each wire in topological order.
+    order: Vec<usize>,
+    values: Vec<u64>,
+}
+
+impl Gate {
+    /// Returns the wires the gate reads.
+    pub fn inputs(&self) -> impl Iterator<Item = Wire> {
+        let (a, b) = match *self {
+            Gate::Buf(a) | Gate::Not(a) | Gate::Shl(a, _) | Gate::Shr(a, _) => (a, None),
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::Xor(a, b) => (a, Some(b)),
+        };
+        [Some(a), b].into_iter().flatten().filter_map(|s| match s {
+            Signal::Wire(w) => Some(w),
+            Signal::Const(_) => None,
+        })
+    }
+
+    fn eval(&self, values: &[u64], mask: u64) -> u64 {
+        let get = |s: Signal| match s {
+            Signal::Wire(w) => values[w.0],
+            Signal::Const(v) => v,
+        };
+        match *self {
+            Gate::Buf(a) => get(a),
+            Gate::Not(a) => !get(a) & mask,
+            Gate::And(a, b) => get(a) & get(b),
+            Gate::Or(a, b) => get(a) | get(b),
+            Gate::Xor(a, b) => get(a) ^ get(b),
+            Gate::Shl(a, n) => get(a).checked_shl(n).unwrap_or(0) & mask,
+            Gate::Shr(a, n) => get(a).checked_shr(n).unwrap_or(0),
+        }
+    }
+}
+
+impl Circuit {
+    /// Parses a netlist into a circuit with the given bit width.
+    pub fn parse(netlist: &str, width: u32) -> Result<Self, Error> {
+        if !(1..=64).contains(&width) {
+            return Err(Error::Width(width));
+        }
+        let mask = u64::MAX >> (64 - width);
+        let mut names = Vec::new();
+        let mut index = HashMap::new();
+        let mut wire = |name: &str| {
+            *index.entry(name.to_owned()).or_insert_with(|| {
+                names.push(name.to_owned());
+                Wire(names.len() - 1)
+            })
+        };
+        let mut drivers = Vec::new();
+        for line in netlist.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let err = || Error::Parse(line.to_owned());
+            let mut signal = |s: &str| match s.parse::<u64>() {
+                Ok(v) if v <= mask => Ok(Signal::Const(v)),
+                Ok(_) => Err(err()),
+                Err(_) if s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+                    Ok(Signal::Wire(wire(s)))
+                }
+                Err(_) => Err(err()),
+            };
+            let (gate, out) = match *line.split_whitespace().collect::<Vec<_>>() {
+                [name, value] if name.ends_with(':') => {
+                    let Signal::Const(v) = signal(value)? else {
+                        return Err(err());
+                    };
+                    (Gate::Buf(Signal::Const(v)), &name[..name.len() - 1])
+                }
+                [a, "->", out] => (Gate::Buf(signal(a)?), out),
+                ["NOT", a, "->", out] => (Gate::Not(signal(a)?), out),
+                [a, op, b, "->", out] => {
+                    let a = signal(a)?;
+                    let shift = || b.parse().map_err(|_| err());
+                    let gate = match op {
+                        "AND" => Gate::And(a, signal(b)?),
+                        "OR" => Gate::Or(a, signal(b)?),
+                        "XOR" => Gate::Xor(a, signal(b)?),
+                        "LSHIFT" => Gate::Shl(a, shift()?),
+                        "RSHIFT" => Gate::Shr(a, shift()?),
+                        _ => return Err(err()),
+                    };
+                    (gate, out)
+                }
+                _ => return Err(err()),
+            };
+            let Signal::Wire(out) = signal(out)? else {
+                return Err(err());
+            };
+            drivers.push((out, gate));
+        }
+
+        let mut gates = vec![None; names.len()];
+        for (out, gate) in drivers {
+            if gates[out.0].replace(gate).is_some() {
+                return Err(Error::MultipleDrivers(names[out.0].clone()));
+            }
+        }
+        let gates = gates
+            .into_iter()
+            .enumerate()
+            .map(|(i, g)| g.ok_or_else(|| Error::Undriven(names[i].clone())))
+            .collect::<Result<_, _>>()?;
+
+        let n = names.len();
+        let mut circuit = Self {
+            mask,
+            names,
+            index,
+            gates,
+            overrides: vec![None; n],
+            fanout: Vec::new(),
+            order: Vec::new(),
+            values: vec![0; n],
+        };
+        circuit.sort()?;
+        circuit.eval_all();
+        Ok(circuit)
+    }
+
+    /// Returns the bit mask for the circuit's width.
+    pub fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    pub fn wire(&self, name: &str) -> Option<Wire> {
+        self.index.get(name).copied()
+    }
+
+    pub fn name(&self, wire: Wire) -> &str {
+        &self.names[wire.0]
+    }
+
+    /// Returns all the wires in the circuit, in the order they first appear in
+    /// the netlist.
+    pub fn wires(&self) -> impl Iterator<Item = Wire> {
+        (0..self.names.len()).map(Wire)
+    }
+
+    /// Returns the gate driving the wire.
+    pub fn gate(&self, wire: Wire) -> &Gate {
+        &self.gates[wire.0]
+    }
+
+    /// Returns the value on the wire.
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.wire(name).map(|w| self.values[w.0])
+    }
+
+    /// Overrides the wire with a fixed value, re-evaluating the gates that
+    /// depend on it.
+    pub fn set(&mut self, name: &str, value: u64) -> Result<(), Error> {
+        let w = self.lookup(name)?;
+        self.overrides[w.0] = Some(value & self.mask);
+        self.propagate(w);
+        Ok(())
+    }
+
+    /// Removes an override, returning the wire to the value from its gate.
+    pub fn unset(&mut self, name: &str) -> Result<(), Error> {
+        let w = self.lookup(name)?;
+        if self.overrides[w.0].take().is_some() {
+            self.propagate(w);
+        }
+        Ok(())
+    }
+
+    /// Swaps the gates driving two wires.
+    ///
+    /// If this would create a cycle then the circuit is left unchanged.
+    pub fn swap(&mut self, a: &str, b: &str) -> Result<(), Error> {
+        let (a, b) = (self.lookup(a)?, self.lookup(b)?);
+        self.gates.swap(a.0, b.0);
+        if let Err(err) = self.sort() {
+            self.gates.swap(a.0, b.0);
+            self.sort().unwrap();
+            return Err(err);
+        }
+        self.eval_all();
+        Ok(())
+    }
+
+    /// Exports the circuit as a Graphviz DOT graph, with a node for each wire
+    /// labelled with its gate.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n");
+        for w in self.wires() {
+            let signal = |s: Signal| match s {
+                Signal::Wire(_) => String::new(),
+                Signal::Const(v) => format!(" {v}"),
+            };
+            let label = match (self.overrides[w.0], self.gates[w.0]) {
+                (Some(v), _) => format!("= {v}"),
+                (None, Gate::Buf(Signal::Wire(_))) => String::new(),
+                (None, Gate::Buf(Signal::Const(v))) => v.to_string(),
+                (None, Gate::Not(_)) => "NOT".to_owned(),
+                (None, Gate::And(a, b)) => format!("AND{}{}", signal(a), signal(b)),
+                (None, Gate::Or(a, b)) => format!("OR{}{}", signal(a), signal(b)),
+                (None, Gate::Xor(a, b)) => format!("XOR{}{}", signal(a), signal(b)),
+                (None, Gate::Shl(_, n)) => format!("LSHIFT {n}"),
+                (None, Gate::Shr(_, n)) => format!("RSHIFT {n}"),
+            };
+            let name = self.name(w);
+            match label.is_empty() {
+                true => writeln!(dot, "    \"{name}\";").unwrap(),
+                false => writeln!(dot, "    \"{name}\" [label=\"{name}\\n{label}\"];").unwrap(),
+            }
+            if self.overrides[w.0].is_none() {
+                for input in self.gates[w.0].inputs() {
+                    writeln!(dot, "    \"{}\" -> \"{name}\";", self.name(input)).unwrap();
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn lookup(&self, name: &str) -> Result<Wire, Error> {
+        self.wire(name)
+            .ok_or_else(|| Error::Unknown(name.to_owned()))
+    }
+
+    /// Computes the fanout and a topological order of the wires.
+    fn sort(&mut self) -> Result<(), Error> {
+        let n = self.names.len();
+        let mut fanout = vec![Vec::new(); n];
+        let mut indegree = vec![0; n];
+        for (i, gate) in self.gates.iter().enumerate() {
+            for input in gate.inputs() {
+                fanout[input.0].push(Wire(i));
+                indegree[i] += 1;
+            }
+        }
+        let mut order = vec![usize::MAX; n];
+        let mut queue: VecDeque<_> = (0..n).filter(|&i| indegree[i] == 0).collect();
+        let mut next = 0;
+        while let Some(i) = queue.pop_front() {
+            order[i] = next;
+            next += 1;
+            for &Wire(f) in &fanout[i] {
+                indegree[f] -= 1;
+                if indegree[f] == 0 {
+                    queue.push_back(f);
+                }
+            }
+        }
+        if next < n {
+            return Err(Error::Cycle(self.cycle(&indegree)));
+        }
+        self.fanout = fanout;
+        self.order = order;
+        Ok(())
+    }
+
+    /// Finds a cycle among the wires left over from a topological sort,
+    /// returned in the direction signals flow.
+    fn cycle(&self, indegree: &[usize]) -> Vec<String> {
+        // Every leftover wire has a leftover input, so walking backwards
+        // through them must eventually repeat.
+        let mut w = (0..indegree.len()).find(|&i| indegree[i] > 0).unwrap();
+        let mut path = Vec::new();
+        let start = loop {
+            if let Some(pos) = path.iter().position(|&p| p == w) {
+                break pos;
+            }
+            path.push(w);
+            w = self.gates[w]
+                .inputs()
+                .map(|Wire(i)| i)
+                .find(|&i| indegree[i] > 0)
+                .unwrap();
+        };
+        path[start..]
+            .iter()
+            .rev()
+            .map(|&i| self.names[i].clone())
+            .collect()
+    }
+
+    fn eval_all(&mut self) {
+        let mut wires: Vec<_> = (0..self.names.len()).collect();
+        wires.sort_by_key(|&i| self.order[i]);
+        for i in wires {
+            self.values[i] = self.eval(Wire(i));
+        }
+    }
+
+    fn eval(&self, w: Wire) -> u64 {
+        match self.overrides[w.0] {
+            Some(v) => v,
+            None => self.gates[w.0].eval(&self.values, self.mask),
+        }
+    }
+
+    /// Re-evaluates the wire and everything downstream of it that changes, in
+    /// topological order.
+    fn propagate(&mut self, w: Wire) {
+        let mut pending = BTreeSet::from([(self.order[w.0], w)]);
+        while let Some((_, w)) = pending.pop_first() {
+            let value = self.eval(w);
+            if mem::replace(&mut self.values[w.0], value) != value {
+                pending.extend(self.fanout[w.0].iter().map(|&f| (self.order[f.0], f)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i";
+
+    #[test]
+    fn eval() {
+        let c = Circuit::parse(EXAMPLE, 16).unwrap();
+        let values = ["d", "e", "f", "g", "h", "i", "x", "y"].map(|w| c.get(w).unwrap());
+        assert_eq!(values, [72, 507, 492, 114, 65412, 65079, 123, 456]);
+        assert_eq!(c.get("z"), None);
+
+        let c = Circuit::parse(EXAMPLE, 8).unwrap_err();
+        assert_eq!(c, Error::Parse("456 -> y".to_owned()));
+
+        let c = Circuit::parse("x: 1\ny: 1\nx XOR y -> z\nx AND y -> c", 1).unwrap();
+        assert_eq!((c.get("z"), c.get("c")), (Some(0), Some(1)));
+    }
+
+    #[test]
+    fn override_and_swap() {
+        let mut c = Circuit::parse(EXAMPLE, 16).unwrap();
+        c.set("x", 1).unwrap();
+        assert_eq!(c.get("d"), Some(0));
+        assert_eq!(c.get("f"), Some(4));
+        assert_eq!(c.get("h"), Some(65534));
+        assert_eq!(c.get("g"), Some(114));
+        c.unset("x").unwrap();
+        assert_eq!(c.get("f"), Some(492));
+        assert_eq!(c.set("z", 1), Err(Error::Unknown("z".to_owned())));
+
+        c.swap("d", "e").unwrap();
+        assert_eq!((c.get("d"), c.get("e")), (Some(507), Some(72)));
+    }
+
+    #[test]
+    fn errors() {
+        let err = |netlist| Circuit::parse(netlist, 16).unwrap_err().to_string();
+        assert_eq!(
+            err("a -> b\nb -> c\nc -> a"),
+            "wires form a cycle: b -> c -> a"
+        );
+        assert_eq!(err("1 -> a\n2 -> a"), "wire `a` is driven more than once");
+        assert_eq!(err("a AND b -> c\n1 -> a"), "wire `b` is not driven");
+        assert_eq!(err("a NAND b -> c"), "invalid gate `a NAND b -> c`");
+        assert_eq!(
+            Circuit::parse("", 65).unwrap_err().to_string(),
+            "bit width must be between 1 and 64, got 65"
+        );
+
+        let mut c = Circuit::parse("1 -> a\na -> b\nNOT b -> c", 16).unwrap();
+        assert_eq!(
+            c.swap("a", "c"),
+            Err(Error::Cycle(vec!["b".to_owned(), "a".to_owned()]))
+        );
+        assert_eq!(c.get("c"), Some(65534));
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut c = Circuit::parse("1 -> a\nb AND 1 -> c\nNOT a -> b", 16).unwrap();
+        c.set("a", 3).unwrap();
+        assert_eq!(
+            c.to_dot(),
+            r#"digraph circuit {
+    "a" [label="a\n= 3"];
+    "b" [label="b\nNOT"];
+    "a" -> "b";
+    "c" [label="c\nAND 1"];
+    "b" -> "c";
+}
+"#
+        );
+    }
+}
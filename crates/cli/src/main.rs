@@ -3,11 +3,12 @@ use std::fmt::Display;
 use std::fs;
 use std::io;
 use std::io::IsTerminal;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 
 use anyhow::{Context, Result};
 use argh::FromArgs;
+use inferno::collapse::Collapse;
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
@@ -23,10 +24,18 @@ struct Opt {
     #[argh(option, short = 'd')]
     day: u32,
 
-    /// the subcommand: bench, new, open, run, or test
+    /// the subcommand: bench, new, open, profile, run, or test
     #[argh(positional)]
     command: Command,
 
+    /// only run tests declared with `advent::example!`
+    #[argh(switch)]
+    examples_only: bool,
+
+    /// only run tests declared with `advent::answer!`
+    #[argh(switch)]
+    inputs_only: bool,
+
     #[argh(positional, greedy)]
     args: Vec<String>,
 }
@@ -36,6 +45,7 @@ enum Command {
     Bench,
     New,
     Open,
+    Profile,
     Run,
     Test,
 }
@@ -46,9 +56,10 @@ impl argh::FromArgValue for Command {
             "bench" => Ok(Self::Bench),
             "new" => Ok(Self::New),
             "open" => Ok(Self::Open),
+            "profile" => Ok(Self::Profile),
             "run" => Ok(Self::Run),
             "test" => Ok(Self::Test),
-            _ => Err("expected one of: bench, new, open, run, test".into()),
+            _ => Err("expected one of: bench, new, open, profile, run, test".into()),
         }
     }
 }
@@ -58,6 +69,8 @@ fn main() -> Result<()> {
         year,
         day,
         command,
+        examples_only,
+        inputs_only,
         args,
     } = argh::from_env();
 
@@ -65,8 +78,9 @@ fn main() -> Result<()> {
         Command::Bench => bench(year, day, &args),
         Command::New => new(year, day),
         Command::Open => open(year, day, &args),
+        Command::Profile => profile(year, day, &args),
         Command::Run => run(year, day, &args),
-        Command::Test => test(year, day, &args),
+        Command::Test => test(year, day, examples_only, inputs_only, &args),
     }
 }
 
@@ -123,6 +137,20 @@ fn new(year: u32, day: u32) -> Result<()> {
         println!("• {bin_display} was created");
     }
 
+    // Add examples/{year}/{day}-1.txt file
+    let example = workspace_dir.join(format!("examples/{year:04}/{day:02}-1.txt"));
+    let example_display = example
+        .strip_prefix(&workspace_dir)
+        .unwrap_or(&example)
+        .display();
+    if example.exists() {
+        println!("• {example_display} already exists");
+    } else {
+        fs::create_dir_all(example.parent().unwrap())?;
+        fs::write(&example, "")?;
+        println!("• {example_display} was created");
+    }
+
     // Update Cargo.toml
     let manifest = fs::read_to_string(&manifest_path)?;
     let index = manifest.find("[[bin]]").unwrap();
@@ -212,28 +240,172 @@ fn bench(year: u32, day: u32, args: &[String]) -> Result<()> {
     process::exit(status.code().unwrap())
 }
 
-fn run(year: u32, day: u32, args: &[String]) -> Result<()> {
+fn profile(year: u32, day: u32, args: &[String]) -> Result<()> {
     check_input(year, day)?;
 
     let bin_name = format!("{year:04}{day:02}");
 
+    let part: usize = match args.first() {
+        Some(part) => part.parse().context("part must be a number")?,
+        None => 1,
+    };
+    let duration: u64 = match args.get(1) {
+        Some(duration) => duration.parse().context("duration must be a number")?,
+        None => 10,
+    };
+
+    // Build in release mode but with debug symbols so that the profile has
+    // function names in it.
     let status = process::Command::new(env!("CARGO"))
-        .args(["run", "--release", "--bin", &bin_name])
-        .args(args)
+        .args(["build", "--release", "--bin", &bin_name])
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
         .status()?;
+    if !status.success() {
+        process::exit(status.code().unwrap())
+    }
 
-    process::exit(status.code().unwrap())
+    let workspace_dir = PathBuf::from(env!("CARGO_WORKSPACE_DIR"));
+    let target_dir = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_dir.join("target"));
+    let bin = target_dir.join("release").join(&bin_name);
+    let bin_args = [
+        "--profile".to_owned(),
+        part.to_string(),
+        "--duration".to_owned(),
+        duration.to_string(),
+    ];
+
+    let has_perf = process::Command::new("perf")
+        .arg("--version")
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .status()
+        .map_or(false, |s| s.success());
+    if !has_perf {
+        print("Skipping", "profiler, `perf` is not available");
+        let status = process::Command::new(&bin).args(bin_args).status()?;
+        process::exit(status.code().unwrap())
+    }
+
+    let profile_dir = target_dir.join("advent-profiles");
+    fs::create_dir_all(&profile_dir)?;
+    let stem = format!("{bin_name}-part{part}");
+    let data = profile_dir.join(format!("{stem}.perf.data"));
+    let folded = profile_dir.join(format!("{stem}.folded"));
+    let svg = profile_dir.join(format!("{stem}.svg"));
+
+    print(
+        "Profiling",
+        format!("part {part} (year: {year}, day: {day:02}) for {duration}s"),
+    );
+    let status = process::Command::new("perf")
+        .args(["record", "--call-graph", "dwarf", "--output"])
+        .arg(&data)
+        .arg("--")
+        .arg(&bin)
+        .args(bin_args)
+        .status()?;
+    if !status.success() {
+        process::exit(status.code().unwrap())
+    }
+
+    let output = process::Command::new("perf")
+        .args(["script", "--input"])
+        .arg(&data)
+        .stderr(process::Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        process::exit(output.status.code().unwrap())
+    }
+
+    let mut stacks = Vec::new();
+    inferno::collapse::perf::Folder::default().collapse(&output.stdout[..], &mut stacks)?;
+    fs::write(&folded, &stacks)?;
+    print("Folded", display(&folded, &workspace_dir));
+
+    let mut opts = inferno::flamegraph::Options::default();
+    opts.title = format!("{year} day {day:02} part {part}");
+    inferno::flamegraph::from_reader(&mut opts, &stacks[..], fs::File::create(&svg)?)?;
+    print("Flamegraph", display(&svg, &workspace_dir));
+
+    Ok(())
+}
+
+fn display<'a>(path: &'a Path, workspace_dir: &Path) -> std::path::Display<'a> {
+    path.strip_prefix(workspace_dir).unwrap_or(path).display()
 }
 
-fn test(year: u32, day: u32, args: &[String]) -> Result<()> {
+fn run(year: u32, day: u32, args: &[String]) -> Result<()> {
     check_input(year, day)?;
 
     let bin_name = format!("{year:04}{day:02}");
 
     let status = process::Command::new(env!("CARGO"))
-        .args(["test", "--release", "--bin", &bin_name])
+        .args(["run", "--release", "--bin", &bin_name])
         .args(args)
         .status()?;
 
     process::exit(status.code().unwrap())
 }
+
+fn test(
+    year: u32,
+    day: u32,
+    examples_only: bool,
+    inputs_only: bool,
+    args: &[String],
+) -> Result<()> {
+    let bin_name = format!("{year:04}{day:02}");
+
+    if examples_only && inputs_only {
+        anyhow::bail!("`--examples-only` and `--inputs-only` are mutually exclusive");
+    }
+    let mut args = args.to_vec();
+
+    // Filter the tests using the names generated by `advent::example!` and
+    // `advent::answer!`.
+    let filter = if examples_only {
+        Some("::example")
+    } else if inputs_only {
+        Some("::answer")
+    } else {
+        None
+    };
+    if let Some(filter) = filter {
+        let i = match args.iter().position(|a| a == "--") {
+            Some(i) => i + 1,
+            None => {
+                args.push("--".to_owned());
+                args.len()
+            }
+        };
+        args.insert(i, filter.to_owned());
+    }
+
+    let mut cmd = process::Command::new(env!("CARGO"));
+    cmd.args(["test", "--release", "--bin", &bin_name]).args(&args);
+
+    let workspace_dir = PathBuf::from(env!("CARGO_WORKSPACE_DIR"));
+    let input = workspace_dir.join(format!("input/{year:04}/{day:02}.txt"));
+    if examples_only && !input.exists() {
+        // Build without the puzzle input, this needs a separate target
+        // directory so that it doesn't clobber the normal build.
+        print("Skipping", "puzzle input, only running examples");
+        let target_dir = env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| workspace_dir.join("target"));
+        let rustflags = match env::var("RUSTFLAGS") {
+            Ok(flags) if !flags.is_empty() => format!("{flags} --cfg advent_no_input"),
+            _ => "--cfg advent_no_input".to_owned(),
+        };
+        cmd.env("RUSTFLAGS", rustflags)
+            .env("CARGO_TARGET_DIR", target_dir.join("no-input"));
+    } else {
+        check_input(year, day)?;
+    }
+
+    let status = cmd.status()?;
+
+    process::exit(status.code().unwrap())
+}
@@ -21,9 +21,15 @@ fn main() {
     solution.cli()
 }
 
-#[test]
-fn default() {
-    let input = default_input();
-    assert_eq!(part1(input.clone()), 1);
-    assert_eq!(part2(input), 2);
+advent::examples! {
+    parse_input => (part1, part2);
+    example1: include_example!({ year } / { day }, 1) => (1, 2),
+}
+
+advent::answer! {
+    fn default() {
+        let input = default_input();
+        assert_eq!(part1(input.clone()), 1);
+        assert_eq!(part2(input), 2);
+    }
 }
@@ -15,20 +15,52 @@ pub use vectrix::{Matrix, vector, Vector};
 
 pub use then::Some as _;
 
-pub use advent_ocr::ocr;
+pub use utilities::ocr;
 
 pub type Vector2 = vectrix::Vector<i64, 2>;
 pub type Vector3 = vectrix::Vector<i64, 3>;
 pub type Vector4 = vectrix::Vector<i64, 4>;
 
+/// Includes the puzzle input for the given year and day.
+///
+/// When compiled with `--cfg advent_no_input` the input is not read and
+/// calling this panics instead. `cargo advent test --examples-only` uses this
+/// so that the example tests can be run without the puzzle input.
 #[macro_export]
 macro_rules! include_input {
     ($year:literal / $day:literal) => {{
+        #[cfg(not(advent_no_input))]
+        fn input() -> &'static str {
+            include_str!(concat!(
+                "../input/",
+                stringify!($year),
+                "/",
+                stringify!($day),
+                ".txt"
+            ))
+        }
+        #[cfg(advent_no_input)]
+        fn input() -> &'static str {
+            panic!("puzzle input is not available in an `advent_no_input` build")
+        }
+        input()
+    }};
+}
+
+/// Includes an example input for the given year and day.
+///
+/// Examples are numbered from 1 and stored alongside the solutions, e.g.
+/// `include_example!(2022 / 10, 1)` reads `examples/2022/10-1.txt`.
+#[macro_export]
+macro_rules! include_example {
+    ($year:literal / $day:literal, $n:literal) => {{
         include_str!(concat!(
-            "../input/",
+            "../examples/",
             stringify!($year),
             "/",
             stringify!($day),
+            "-",
+            stringify!($n),
             ".txt"
         ))
     }};
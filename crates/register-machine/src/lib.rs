@@ -0,0 +1,543 @@
+//! A small register machine for the assembly-like puzzles.
+//!
+//! A handful of puzzles (2015/23, 2016's assembunny, 2017/18 duet, 2020/08
+//! handheld) share the same shape: a list of instructions, a program counter,
+//! a register file and sometimes message queues between machines. The
+//! [`Machine`] here provides all of that, as well as a step budget and loop
+//! detection, and the instruction set is plugged in by implementing [`Isa`].
+//!
+//! ```
+//! use register_machine::{Control, Cpu, Exit, Isa, Machine, Operand, Reg};
+//!
+//! enum Instr {
+//!     Dec(Reg),
+//!     Jnz(Operand, i64),
+//! }
+//!
+//! struct Countdown;
+//!
+//! impl Isa for Countdown {
+//!     type Instr = Instr;
+//!
+//!     fn exec(&self, instr: &Instr, cpu: &mut Cpu) -> Control {
+//!         match *instr {
+//!             Instr::Dec(r) => {
+//!                 cpu.regs[r] -= 1;
+//!                 Control::Next
+//!             }
+//!             Instr::Jnz(x, offset) if cpu.get(x) != 0 => Control::Jump(offset),
+//!             Instr::Jnz(..) => Control::Next,
+//!         }
+//!     }
+//! }
+//!
+//! let a = Reg::new('a');
+//! let mut m = Machine::new(Countdown, vec![Instr::Dec(a), Instr::Jnz(Operand::Reg(a), -1)]);
+//! m.regs_mut()[a] = 3;
+//! assert_eq!(m.run(), Exit::Halted);
+//! assert_eq!(m.steps(), 6);
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+pub use crate::patch::{find_patch, Patch};
+
+mod patch;
+
+/// A register, named by a lowercase letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reg(u8);
+
+/// The register file, every register starts at zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Registers([i64; 26]);
+
+/// An instruction argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Reg(Reg),
+    Imm(i64),
+}
+
+/// An error parsing a register or operand.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid register or value `{input}`")]
+pub struct ParseError {
+    input: String,
+}
+
+/// The state instructions act on.
+#[derive(Debug, Clone, Default)]
+pub struct Cpu {
+    pub regs: Registers,
+    inbox: VecDeque<i64>,
+    outbox: VecDeque<i64>,
+    sent: u64,
+}
+
+/// How execution continues after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// Continue at the next instruction.
+    Next,
+    /// Jump relative to the current instruction.
+    Jump(i64),
+    /// The instruction is waiting for input and will be executed again when
+    /// the machine is next run.
+    Wait,
+    /// Stop the machine.
+    Halt,
+}
+
+/// An instruction set.
+pub trait Isa {
+    type Instr;
+
+    /// Executes a single instruction.
+    fn exec(&self, instr: &Self::Instr, cpu: &mut Cpu) -> Control;
+
+    /// Returns every relative jump the instruction could make, or `None` if
+    /// this can't be known without running it (or it could halt). Used for
+    /// static analysis of the program.
+    fn successors(&self, _instr: &Self::Instr) -> Option<Vec<i64>> {
+        None
+    }
+}
+
+/// The reason a machine stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// The program counter left the program or an instruction halted.
+    Halted,
+    /// An instruction is waiting for input.
+    Blocked,
+    /// The step budget was exhausted.
+    Budget,
+    /// The machine was about to return to a state it has already been in.
+    Loop,
+}
+
+/// The state remembered for loop detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detect {
+    /// Only the program counter, for instruction sets where the registers
+    /// never affect control flow.
+    Pc,
+    /// The program counter and the registers.
+    State,
+}
+
+/// A count of the instructions a machine has executed and an optional limit
+/// on how many more it may execute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    executed: u64,
+    remaining: Option<u64>,
+}
+
+/// A machine running a program.
+pub struct Machine<I: Isa> {
+    isa: I,
+    program: Vec<I::Instr>,
+    pc: i64,
+    cpu: Cpu,
+    budget: Budget,
+    detect: Option<(Detect, HashSet<u64>)>,
+    halted: bool,
+}
+
+impl Reg {
+    /// Returns the register with the given name.
+    ///
+    /// # Panics
+    ///
+    /// If the name is not a lowercase ASCII letter.
+    pub const fn new(name: char) -> Self {
+        assert!(
+            name.is_ascii_lowercase(),
+            "register must be a lowercase letter"
+        );
+        Self(name as u8 - b'a')
+    }
+
+    pub fn name(self) -> char {
+        (b'a' + self.0) as char
+    }
+}
+
+impl Index<Reg> for Registers {
+    type Output = i64;
+
+    fn index(&self, reg: Reg) -> &Self::Output {
+        &self.0[reg.0 as usize]
+    }
+}
+
+impl IndexMut<Reg> for Registers {
+    fn index_mut(&mut self, reg: Reg) -> &mut Self::Output {
+        &mut self.0[reg.0 as usize]
+    }
+}
+
+impl FromStr for Reg {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_lowercase() => Ok(Self::new(c)),
+            _ => Err(ParseError::new(s)),
+        }
+    }
+}
+
+impl FromStr for Operand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(Operand::Reg)
+            .or_else(|_| s.parse().map(Operand::Imm))
+            .map_err(|_| ParseError::new(s))
+    }
+}
+
+impl ParseError {
+    fn new(input: &str) -> Self {
+        Self {
+            input: input.to_owned(),
+        }
+    }
+}
+
+impl Cpu {
+    /// Returns the value of the operand.
+    pub fn get(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Reg(r) => self.regs[r],
+            Operand::Imm(v) => v,
+        }
+    }
+
+    /// Sends a value to the output channel.
+    pub fn send(&mut self, value: i64) {
+        self.outbox.push_back(value);
+        self.sent += 1;
+    }
+
+    /// Receives a value from the input channel.
+    pub fn recv(&mut self) -> Option<i64> {
+        self.inbox.pop_front()
+    }
+}
+
+impl Budget {
+    /// Returns the number of instructions executed so far.
+    pub fn executed(&self) -> u64 {
+        self.executed
+    }
+
+    /// Sets how many more instructions may be executed, `None` removes the
+    /// limit.
+    pub fn limit(&mut self, remaining: Option<u64>) {
+        self.remaining = remaining;
+    }
+
+    /// Returns whether `n` more instructions may be executed.
+    pub fn allows(&self, n: u64) -> bool {
+        self.remaining.map_or(true, |remaining| remaining >= n)
+    }
+
+    /// Records that `n` instructions were executed.
+    ///
+    /// # Panics
+    ///
+    /// If that is more than the budget allows.
+    pub fn spend(&mut self, n: u64) {
+        assert!(self.allows(n), "instruction budget exceeded");
+        self.executed += n;
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= n;
+        }
+    }
+}
+
+impl<I: Isa> Machine<I> {
+    pub fn new(isa: I, program: Vec<I::Instr>) -> Self {
+        Self {
+            isa,
+            program,
+            pc: 0,
+            cpu: Cpu::default(),
+            budget: Budget::default(),
+            detect: None,
+            halted: false,
+        }
+    }
+
+    pub fn program(&self) -> &[I::Instr] {
+        &self.program
+    }
+
+    pub fn program_mut(&mut self) -> &mut [I::Instr] {
+        &mut self.program
+    }
+
+    /// Returns the program counter, this is outside of the program once the
+    /// machine has finished.
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
+
+    pub fn regs(&self) -> &Registers {
+        &self.cpu.regs
+    }
+
+    pub fn regs_mut(&mut self) -> &mut Registers {
+        &mut self.cpu.regs
+    }
+
+    /// Adds a value to the input channel.
+    pub fn input(&mut self, value: i64) {
+        self.cpu.inbox.push_back(value);
+    }
+
+    /// Takes all the values from the output channel.
+    pub fn output(&mut self) -> impl Iterator<Item = i64> + '_ {
+        self.cpu.outbox.drain(..)
+    }
+
+    /// Returns the total number of values sent to the output channel.
+    pub fn sent(&self) -> u64 {
+        self.cpu.sent
+    }
+
+    /// Returns the number of steps taken, summed over every run. Steps that
+    /// block waiting for input aren't counted.
+    pub fn steps(&self) -> u64 {
+        self.budget.executed()
+    }
+
+    /// Allows the machine to take this many more steps before it stops with
+    /// [`Exit::Budget`], or removes the limit if `None`.
+    pub fn set_budget(&mut self, steps: Option<u64>) {
+        self.budget.limit(steps);
+    }
+
+    /// Stops the machine with [`Exit::Loop`] before it returns to a state it
+    /// has been in since this was called.
+    pub fn detect_loops(&mut self, detect: Detect) {
+        self.detect = Some((detect, HashSet::new()));
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) -> Option<Exit> {
+        if self.halted {
+            return Some(Exit::Halted);
+        }
+        let Some(instr) = usize::try_from(self.pc)
+            .ok()
+            .and_then(|pc| self.program.get(pc))
+        else {
+            return Some(Exit::Halted);
+        };
+        if !self.budget.allows(1) {
+            return Some(Exit::Budget);
+        }
+        let state = self.detect.as_ref().map(|(detect, seen)| {
+            let mut h = DefaultHasher::new();
+            self.pc.hash(&mut h);
+            if *detect == Detect::State {
+                self.cpu.regs.hash(&mut h);
+            }
+            (h.finish(), seen)
+        });
+        if let Some((state, seen)) = state {
+            if seen.contains(&state) {
+                return Some(Exit::Loop);
+            }
+        }
+        let state = state.map(|(state, _)| state);
+
+        let control = self.isa.exec(instr, &mut self.cpu);
+        if control == Control::Wait {
+            return Some(Exit::Blocked);
+        }
+        if let (Some((_, seen)), Some(state)) = (&mut self.detect, state) {
+            seen.insert(state);
+        }
+        self.budget.spend(1);
+        match control {
+            Control::Next => self.pc += 1,
+            Control::Jump(offset) => self.pc += offset,
+            Control::Halt => {
+                self.halted = true;
+                return Some(Exit::Halted);
+            }
+            Control::Wait => unreachable!(),
+        }
+        None
+    }
+
+    /// Runs the machine until it stops.
+    pub fn run(&mut self) -> Exit {
+        loop {
+            if let Some(exit) = self.step() {
+                return exit;
+            }
+        }
+    }
+}
+
+impl<I> Clone for Machine<I>
+where
+    I: Isa + Clone,
+    I::Instr: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            isa: self.isa.clone(),
+            program: self.program.clone(),
+            pc: self.pc,
+            cpu: self.cpu.clone(),
+            budget: self.budget,
+            detect: self.detect.clone(),
+            halted: self.halted,
+        }
+    }
+}
+
+/// Runs two machines with the output of each connected to the input of the
+/// other, until both are halted or blocked waiting on each other. Returns how
+/// each machine stopped.
+pub fn run_pair<I: Isa>(a: &mut Machine<I>, b: &mut Machine<I>) -> (Exit, Exit) {
+    loop {
+        let x = a.run();
+        b.cpu.inbox.extend(a.cpu.outbox.drain(..));
+        let y = b.run();
+        a.cpu.inbox.extend(b.cpu.outbox.drain(..));
+        let stuck = |exit, m: &Machine<I>| match exit {
+            Exit::Halted => true,
+            Exit::Blocked => m.cpu.inbox.is_empty(),
+            Exit::Budget | Exit::Loop => true,
+        };
+        if stuck(x, a) && stuck(y, b) {
+            return (x, y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tiny instruction set for testing.
+    #[derive(Debug, Clone, Copy)]
+    enum Instr {
+        Inc(Reg),
+        Jmp(i64),
+        Snd(Operand),
+        Rcv(Reg),
+    }
+
+    #[derive(Clone)]
+    struct Test;
+
+    impl Isa for Test {
+        type Instr = Instr;
+
+        fn exec(&self, instr: &Instr, cpu: &mut Cpu) -> Control {
+            match *instr {
+                Instr::Inc(r) => cpu.regs[r] += 1,
+                Instr::Jmp(offset) => return Control::Jump(offset),
+                Instr::Snd(x) => cpu.send(cpu.get(x)),
+                Instr::Rcv(r) => match cpu.recv() {
+                    Some(v) => cpu.regs[r] = v,
+                    None => return Control::Wait,
+                },
+            }
+            Control::Next
+        }
+
+        fn successors(&self, instr: &Instr) -> Option<Vec<i64>> {
+            match *instr {
+                Instr::Jmp(offset) => Some(vec![offset]),
+                Instr::Rcv(_) => None,
+                _ => Some(vec![1]),
+            }
+        }
+    }
+
+    const A: Reg = Reg::new('a');
+    const B: Reg = Reg::new('b');
+
+    #[test]
+    fn parse() {
+        assert_eq!("b".parse(), Ok(Operand::Reg(B)));
+        assert_eq!("-7".parse(), Ok(Operand::Imm(-7)));
+        assert_eq!("+3".parse(), Ok(Operand::Imm(3)));
+        assert_eq!(
+            "ab".parse::<Operand>().unwrap_err().to_string(),
+            "invalid register or value `ab`"
+        );
+        assert_eq!(B.name(), 'b');
+    }
+
+    #[test]
+    fn detect_loops() {
+        let program = vec![Instr::Inc(A), Instr::Jmp(-1)];
+
+        let mut m = Machine::new(Test, program.clone());
+        m.detect_loops(Detect::Pc);
+        assert_eq!(m.run(), Exit::Loop);
+        assert_eq!((m.pc(), m.regs()[A], m.steps()), (0, 1, 2));
+
+        // The registers change every time so the state never repeats
+        let mut m = Machine::new(Test, program);
+        m.detect_loops(Detect::State);
+        m.set_budget(Some(100));
+        assert_eq!(m.run(), Exit::Budget);
+        assert_eq!(m.regs()[A], 50);
+    }
+
+    #[test]
+    fn run_pair_until_blocked() {
+        let program = vec![Instr::Snd(Operand::Imm(1)), Instr::Rcv(A), Instr::Rcv(B)];
+        let mut a = Machine::new(Test, program.clone());
+        let mut b = Machine::new(Test, program);
+        assert_eq!(run_pair(&mut a, &mut b), (Exit::Blocked, Exit::Blocked));
+        assert_eq!((a.pc(), a.regs()[A], a.sent()), (2, 1, 1));
+        assert_eq!((b.pc(), b.regs()[A], b.sent()), (2, 1, 1));
+
+        a.input(5);
+        assert_eq!(a.run(), Exit::Halted);
+        assert_eq!(a.regs()[B], 5);
+    }
+
+    #[test]
+    fn find_patch_until_halted() {
+        let program = vec![Instr::Inc(A), Instr::Jmp(2), Instr::Inc(A), Instr::Jmp(-3)];
+        let mut m = Machine::new(Test, program);
+        m.detect_loops(Detect::Pc);
+        let mutations = |instr: &Instr| match instr {
+            Instr::Jmp(_) => vec![Instr::Inc(B)],
+            _ => Vec::new(),
+        };
+
+        // Patching the first jump would still loop, only the second works
+        let patch = find_patch(m.clone(), mutations, |_| true).unwrap();
+        assert_eq!(patch.index, 3);
+        assert!(matches!(patch.instr, Instr::Inc(B)));
+        assert_eq!(patch.machine.regs()[A], 1);
+        assert_eq!(patch.machine.regs()[B], 1);
+        assert_eq!((patch.machine.pc(), patch.machine.steps()), (4, 3));
+
+        assert!(find_patch(m, mutations, |m| m.regs()[A] == 2).is_none());
+    }
+}
@@ -0,0 +1,114 @@
+//! Search for a single instruction patch that makes a program terminate.
+//!
+//! Rather than re-running the whole program for every candidate, the search
+//! runs the original program once. Up until the patched instruction is first
+//! executed the patched program behaves exactly the same, so each candidate
+//! is tried from a snapshot taken at that point. Candidates are also pruned
+//! using the control-flow graph of the original program: a patched program can
+//! only terminate if one of the new instruction's successors can reach the
+//! end of the original program, since any path through the patched
+//! instruction can be shortened to one after its last visit.
+
+use std::collections::VecDeque;
+
+use crate::{Exit, Isa, Machine};
+
+/// A patch that makes the program terminate.
+pub struct Patch<I: Isa> {
+    /// The index of the patched instruction.
+    pub index: usize,
+    /// The replacement instruction.
+    pub instr: I::Instr,
+    /// The machine with the patched program, after it terminated.
+    pub machine: Machine<I>,
+}
+
+/// Finds an instruction to replace so that the program terminates.
+///
+/// The machine should be ready to run, with loop detection or a budget so that
+/// it stops. `mutations` returns the candidate replacements for an instruction
+/// and `accept` decides whether a terminated machine is the one wanted.
+/// Candidates are tried in the order the original program first executes the
+/// instructions. Returns `None` if no patch works.
+pub fn find_patch<I, M, A>(mut machine: Machine<I>, mutations: M, accept: A) -> Option<Patch<I>>
+where
+    I: Isa + Clone,
+    I::Instr: Clone,
+    M: Fn(&I::Instr) -> Vec<I::Instr>,
+    A: Fn(&Machine<I>) -> bool,
+{
+    let len = machine.program().len();
+    let ends = terminating(&machine);
+    let isa = machine.isa.clone();
+    let can_end = |index: usize, instr: &I::Instr| match isa.successors(instr) {
+        Some(succs) => succs
+            .iter()
+            .any(|off| match usize::try_from(index as i64 + off) {
+                Ok(next) if next < len => ends[next],
+                _ => true,
+            }),
+        None => true,
+    };
+
+    let mut visited = vec![false; len];
+    loop {
+        let index = usize::try_from(machine.pc()).ok().filter(|&pc| pc < len)?;
+        if !visited[index] {
+            visited[index] = true;
+            let candidates = mutations(&machine.program()[index]);
+            for instr in candidates {
+                if !can_end(index, &instr) {
+                    continue;
+                }
+                let mut patched = machine.clone();
+                patched.program_mut()[index] = instr.clone();
+                if patched.run() == Exit::Halted && accept(&patched) {
+                    return Some(Patch {
+                        index,
+                        instr,
+                        machine: patched,
+                    });
+                }
+            }
+        }
+        if machine.step().is_some() {
+            return None;
+        }
+    }
+}
+
+/// Returns which instructions could lead to the end of the program.
+fn terminating<I: Isa>(machine: &Machine<I>) -> Vec<bool> {
+    let program = machine.program();
+    let len = program.len();
+    let mut preds = vec![Vec::new(); len];
+    let mut ends = vec![false; len];
+    let mut queue = VecDeque::new();
+    for (index, instr) in program.iter().enumerate() {
+        let Some(succs) = machine.isa.successors(instr) else {
+            ends[index] = true;
+            queue.push_back(index);
+            continue;
+        };
+        for off in succs {
+            match usize::try_from(index as i64 + off) {
+                Ok(next) if next < len => preds[next].push(index),
+                _ => {
+                    if !ends[index] {
+                        ends[index] = true;
+                        queue.push_back(index);
+                    }
+                }
+            }
+        }
+    }
+    while let Some(index) = queue.pop_front() {
+        for &pred in &preds[index] {
+            if !ends[pred] {
+                ends[pred] = true;
+                queue.push_back(pred);
+            }
+        }
+    }
+    ends
+}
@@ -1,16 +1,5 @@
 mod ocr;
+mod screen;
 
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use crate::ocr::{ocr, OcrError};
+pub use crate::screen::Screen;
@@ -1,16 +1,230 @@
-/*pub fn ocr(letters: &[bool], width: usize, height: usize) -> String {
-    let mut rotated_letters = vec!['\n'; width * (height + 1)];
-    for y in 0..height {
-        for x in 0..width {
-            let c = if letters[y * width + x] { '#' } else { '.' };
-            rotated_letters[x * (height + 1) + y] = c;
+//! Reads the capital letters that puzzles draw with pixels.
+//!
+//! Puzzles use one of two fonts: a small one where letters are 6 pixels high
+//! and mostly 4 wide, and a large one where they are 10 high and 6 wide. The
+//! image is cropped to the pixels that are on, its height picks the font, and
+//! then each run of columns between blank ones is matched against the font,
+//! splitting runs where letters touch.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use thiserror::Error;
+
+use crate::Screen;
+
+/// An error reading the letters in an image.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum OcrError {
+    /// No pixels are on.
+    #[error("no pixels are on")]
+    Empty,
+    /// The letters are a height that neither font has.
+    #[error("letters are {0} pixels high but the fonts are 6 or 10")]
+    Height(usize),
+    /// The pixels starting at the given column don't match any letter.
+    #[error("unknown letter at column {column}\n{glyph}")]
+    Unknown { column: usize, glyph: String },
+}
+
+/// A font, mapping each letter's columns as bit sets to the letter.
+struct Font {
+    glyphs: HashMap<Vec<u16>, char>,
+    max_width: usize,
+}
+
+const SMALL_LETTERS: &str = "ABCEFGHIJKLOPRSUYZ";
+const SMALL: &str = "\
+.##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####";
+
+const LARGE_LETTERS: &str = "ABCEFGHJKLNPRXZ";
+const LARGE: &str = "\
+..##...#####...####..######.######..####..#....#....###.#....#.#......#....#.#####..#####..#....#.######
+.#..#..#....#.#....#.#......#......#....#.#....#.....#..#...#..#......##...#.#....#.#....#.#....#......#
+#....#.#....#.#......#......#......#......#....#.....#..#..#...#......##...#.#....#.#....#..#..#.......#
+#....#.#....#.#......#......#......#......#....#.....#..#.#....#......#.#..#.#....#.#....#..#..#......#.
+#....#.#####..#......#####..#####..#......######.....#..##.....#......#.#..#.#####..#####....##......#..
+######.#....#.#......#......#......#..###.#....#.....#..##.....#......#..#.#.#......#..#.....##.....#...
+#....#.#....#.#......#......#......#....#.#....#.....#..#.#....#......#..#.#.#......#...#...#..#...#....
+#....#.#....#.#......#......#......#....#.#....#.#...#..#..#...#......#...##.#......#...#...#..#..#.....
+#....#.#....#.#....#.#......#......#...##.#....#.#...#..#...#..#......#...##.#......#....#.#....#.#.....
+#....#.#####...####..######.#.......###.#.#....#..###...#....#.######.#....#.#......#....#.#....#.######";
+
+/// Reads the letters in an image.
+///
+/// The image can be anything that converts into a [`Screen`]: a rendered
+/// string, a slice of pixels with the width of a row, or a set of points.
+///
+/// ```
+/// let image = "\
+/// ░██░░███░░░██░
+/// █░░█░█░░█░█░░█
+/// █░░█░███░░█░░░
+/// ████░█░░█░█░░░
+/// █░░█░█░░█░█░░█
+/// █░░█░███░░░██░";
+/// assert_eq!(utilities::ocr(image).unwrap(), "ABC");
+/// ```
+pub fn ocr(image: impl Into<Screen>) -> Result<String, OcrError> {
+    read(&image.into())
+}
+
+pub(crate) fn read(screen: &Screen) -> Result<String, OcrError> {
+    let lit_row = |y| (0..screen.width()).any(|x| screen.get(x, y));
+    let lit_col = |x| (0..screen.height()).any(|y| screen.get(x, y));
+    let top = (0..screen.height())
+        .find(|&y| lit_row(y))
+        .ok_or(OcrError::Empty)?;
+    let bottom = (0..screen.height()).rfind(|&y| lit_row(y)).unwrap();
+    let font = match bottom - top + 1 {
+        6 => Font::small(),
+        10 => Font::large(),
+        h => return Err(OcrError::Height(h)),
+    };
+    let columns: Vec<u16> = (0..screen.width())
+        .map(|x| (top..=bottom).fold(0, |acc, y| acc << 1 | screen.get(x, y) as u16))
+        .collect();
+
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < columns.len() {
+        if !lit_col(x) {
+            x += 1;
+            continue;
         }
+        let end = (x..columns.len())
+            .find(|&x| !lit_col(x))
+            .unwrap_or(columns.len());
+        let (width, letter) =
+            font.longest_match(&columns[x..end])
+                .ok_or_else(|| OcrError::Unknown {
+                    column: x,
+                    glyph: render(&columns[x..end], bottom - top + 1),
+                })?;
+        letters.push(letter);
+        x += width;
+    }
+    Ok(letters)
+}
+
+impl Font {
+    fn small() -> &'static Font {
+        static FONT: OnceLock<Font> = OnceLock::new();
+        FONT.get_or_init(|| Font::new(SMALL_LETTERS, SMALL))
+    }
+
+    fn large() -> &'static Font {
+        static FONT: OnceLock<Font> = OnceLock::new();
+        FONT.get_or_init(|| Font::new(LARGE_LETTERS, LARGE))
+    }
+
+    /// Builds a font from a sheet of letters separated by blank columns.
+    fn new(letters: &str, sheet: &str) -> Self {
+        let rows: Vec<&[u8]> = sheet.lines().map(str::as_bytes).collect();
+        let column = |x: usize| {
+            rows.iter()
+                .fold(0, |acc, row| acc << 1 | (row[x] == b'#') as u16)
+        };
+        let columns: Vec<u16> = (0..rows[0].len()).map(column).collect();
+        let glyphs: HashMap<_, _> = columns
+            .split(|&c| c == 0)
+            .filter(|glyph| !glyph.is_empty())
+            .map(<[u16]>::to_vec)
+            .zip(letters.chars())
+            .collect();
+        assert_eq!(
+            glyphs.len(),
+            letters.len(),
+            "font sheet doesn't match letters"
+        );
+        let max_width = glyphs.keys().map(Vec::len).max().unwrap();
+        Self { glyphs, max_width }
+    }
+
+    /// Returns the widest letter at the start of the columns.
+    fn longest_match(&self, columns: &[u16]) -> Option<(usize, char)> {
+        (1..=self.max_width.min(columns.len()))
+            .rev()
+            .find_map(|w| self.glyphs.get(&columns[..w]).map(|&c| (w, c)))
+    }
+}
+
+/// Renders columns of pixels, for showing in errors.
+fn render(columns: &[u16], height: usize) -> String {
+    (0..height)
+        .map(|y| {
+            columns
+                .iter()
+                .map(|c| match c >> (height - 1 - y) & 1 {
+                    1 => '#',
+                    _ => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fonts() {
+        assert_eq!(ocr(SMALL).unwrap(), SMALL_LETTERS);
+        assert_eq!(ocr(LARGE).unwrap(), LARGE_LETTERS);
+
+        // Y touches the letter after it, and the image has a border
+        let image = "\
+..........
+.#...##...
+.#...##...
+..#.#.#...
+...#..#...
+...#..#...
+...#..####
+";
+        assert_eq!(ocr(image).unwrap(), "YL");
+
+        let pixels: Vec<bool> = "#..##..#####..###..##.###."
+            .chars()
+            .map(|c| c == '#')
+            .collect();
+        let mut points = Vec::new();
+        for (i, row) in SMALL.lines().enumerate() {
+            for (x, c) in row[..9].char_indices() {
+                if c == '#' {
+                    points.push((x as i64 - 4, i as i64 + 7));
+                }
+            }
+        }
+        assert_eq!(ocr(points.as_slice()).unwrap(), "AB");
+        assert_eq!(ocr((pixels.as_slice(), 26)), Err(OcrError::Height(1)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(ocr("...\n..."), Err(OcrError::Empty));
+        let err = ocr("#.#\n#.#\n###\n#.#\n#.#\n#.#").unwrap_err();
+        assert_eq!(
+            err,
+            OcrError::Unknown {
+                column: 0,
+                glyph: "#.#\n#.#\n###\n#.#\n#.#\n#.#".into()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "unknown letter at column 0\n#.#\n#.#\n###\n#.#\n#.#\n#.#"
+        );
+        assert_eq!(
+            OcrError::Height(5).to_string(),
+            "letters are 5 pixels high but the fonts are 6 or 10"
+        );
     }
-    let string: String = rotated_letters.into_iter().collect();
-    let string = string.replace(
-        "....##\n...#..\n###...\n...#..\n....##",
-        "....##\n...#..\n###...\n...#..\n....##\n......\n"
-    );
-
-    "hi".to_string()
-}*/
\ No newline at end of file
+}
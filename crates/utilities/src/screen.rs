@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::BuildHasher;
+
+use crate::ocr::{self, OcrError};
+
+/// A screen of pixels that are either on or off, like the ones puzzles draw
+/// their answers on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Screen {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Screen {
+    /// Returns a screen with every pixel off.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    /// Returns the smallest screen that fits the points with them turned on.
+    pub fn from_points(points: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        let points: Vec<_> = points.into_iter().collect();
+        let min_x = points.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let min_y = points.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let max_x = points.iter().map(|&(x, _)| x).max().unwrap_or(-1);
+        let max_y = points.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let mut screen = Self::new(width, height);
+        for (x, y) in points {
+            screen.set((x - min_x) as usize, (y - min_y) as usize);
+        }
+        screen
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.pixels[self.index(x, y)]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize) {
+        let i = self.index(x, y);
+        self.pixels[i] = true;
+    }
+
+    pub fn clear(&mut self, x: usize, y: usize) {
+        let i = self.index(x, y);
+        self.pixels[i] = false;
+    }
+
+    /// Turns on every pixel in the rectangle at the top left of the screen.
+    pub fn rect(&mut self, width: usize, height: usize) {
+        for y in 0..height {
+            for x in 0..width {
+                self.set(x, y);
+            }
+        }
+    }
+
+    /// Rotates a row to the right, pixels falling off the end wrap around.
+    pub fn rotate_row(&mut self, y: usize, by: usize) {
+        if self.width == 0 {
+            return;
+        }
+        let row = &mut self.pixels[y * self.width..(y + 1) * self.width];
+        row.rotate_right(by % self.width);
+    }
+
+    /// Rotates a column down, pixels falling off the end wrap around.
+    pub fn rotate_column(&mut self, x: usize, by: usize) {
+        let column: Vec<_> = (0..self.height).map(|y| self.get(x, y)).collect();
+        for (y, on) in column.into_iter().enumerate() {
+            let i = self.index(x, (y + by) % self.height);
+            self.pixels[i] = on;
+        }
+    }
+
+    /// Returns the number of pixels that are on.
+    pub fn lit(&self) -> usize {
+        self.pixels.iter().filter(|&&on| on).count()
+    }
+
+    /// Renders the screen using the given characters, with a newline after
+    /// every row.
+    pub fn render(&self, on: char, off: char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            let row = &self.pixels[y * self.width..(y + 1) * self.width];
+            s.extend(row.iter().map(|&p| if p { on } else { off }));
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Reads the capital letters drawn on the screen.
+    pub fn decode(&self) -> Result<String, OcrError> {
+        ocr::read(self)
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({x}, {y}) is off the screen"
+        );
+        y * self.width + x
+    }
+}
+
+/// Renders the screen with `█` for pixels that are on and `░` for ones that
+/// are off.
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render('█', '░'))
+    }
+}
+
+/// Reads a rendered screen, `#`, `█` and `*` are pixels that are on and
+/// anything else is off. Short lines are padded with pixels that are off, so
+/// trailing whitespace can be trimmed.
+impl From<&str> for Screen {
+    fn from(s: &str) -> Self {
+        let width = s
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut screen = Self::new(width, s.lines().count());
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if matches!(c, '#' | '█' | '*') {
+                    screen.set(x, y);
+                }
+            }
+        }
+        screen
+    }
+}
+
+/// Reads rows of pixels laid out one after another, with the given width.
+impl From<(&[bool], usize)> for Screen {
+    fn from((pixels, width): (&[bool], usize)) -> Self {
+        assert!(
+            width > 0 && pixels.len() % width == 0,
+            "{} pixels don't fill rows of {width}",
+            pixels.len()
+        );
+        Self {
+            width,
+            height: pixels.len() / width,
+            pixels: pixels.to_vec(),
+        }
+    }
+}
+
+impl From<&[(i64, i64)]> for Screen {
+    fn from(points: &[(i64, i64)]) -> Self {
+        Self::from_points(points.iter().copied())
+    }
+}
+
+impl<S: BuildHasher> From<&HashSet<(i64, i64), S>> for Screen {
+    fn from(points: &HashSet<(i64, i64), S>) -> Self {
+        Self::from_points(points.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operations() {
+        let mut screen = Screen::new(7, 3);
+        screen.rect(3, 2);
+        screen.rotate_column(1, 1);
+        screen.rotate_row(0, 4);
+        screen.rotate_column(1, 1);
+        assert_eq!(screen.render('#', '.'), ".#..#.#\n#.#....\n.#.....\n");
+        assert_eq!(screen.lit(), 6);
+        screen.clear(1, 0);
+        assert!(!screen.get(1, 0));
+        assert_eq!(screen.to_string(), "░░░░█░█\n█░█░░░░\n░█░░░░░\n");
+
+        let screen = Screen::from_points([(-1, 5), (1, 4)]);
+        assert_eq!(screen.render('#', '.'), "..#\n#..\n");
+        assert_eq!(Screen::from("..#\n#"), screen);
+
+        // Screens with no columns are valid, just empty
+        assert_eq!(Screen::from_points([]).to_string(), "");
+        assert_eq!(Screen::from("").to_string(), "");
+        let mut screen = Screen::new(0, 2);
+        screen.rotate_row(1, 3);
+        assert_eq!(screen.render('#', '.'), "\n\n");
+    }
+
+    #[test]
+    fn decode() {
+        let decode = |s: &str| Screen::from(s).decode();
+        assert_eq!(
+            decode(
+                "\
+**** *  *   ** **** ***    ** **** ****
+   * * *     * *    *  *    * *       *
+  *  **      * ***  ***     * ***    *
+ *   * *     * *    *  *    * *     *
+*    * *  *  * *    *  * *  * *    *
+**** *  *  **  *    ***   **  *    ****"
+            )
+            .as_deref(),
+            Ok("ZKJFBJFZ")
+        );
+        assert_eq!(
+            decode(
+                "\
+.###..####.#..#.#..#.####..##..####.#..#...
+.#..#.#....#.#..#..#.#....#..#....#.#..#...
+.#..#.###..##...####.###..#......#..#..#...
+.###..#....#.#..#..#.#....#.....#...#..#...
+.#....#....#.#..#..#.#....#..#.#....#..#...
+.#....#....#..#.#..#.####..##..####..##...."
+            )
+            .as_deref(),
+            Ok("PFKHECZU")
+        );
+        assert_eq!(
+            decode(
+                "\
+█░░░░█░░█░░░░░░░██████░░░░██░░░░█░░░░█░░█████░░░░████░░░██████
+█░░░░█░░█░░░░░░░░░░░░█░░░█░░█░░░█░░░█░░░█░░░░█░░█░░░░█░░░░░░░█
+░█░░█░░░█░░░░░░░░░░░░█░░█░░░░█░░█░░█░░░░█░░░░█░░█░░░░░░░░░░░░█
+░█░░█░░░█░░░░░░░░░░░█░░░█░░░░█░░█░█░░░░░█░░░░█░░█░░░░░░░░░░░█░
+░░██░░░░█░░░░░░░░░░█░░░░█░░░░█░░██░░░░░░█████░░░█░░░░░░░░░░█░░
+░░██░░░░█░░░░░░░░░█░░░░░██████░░██░░░░░░█░░░░█░░█░░███░░░░█░░░
+░█░░█░░░█░░░░░░░░█░░░░░░█░░░░█░░█░█░░░░░█░░░░█░░█░░░░█░░░█░░░░
+░█░░█░░░█░░░░░░░█░░░░░░░█░░░░█░░█░░█░░░░█░░░░█░░█░░░░█░░█░░░░░
+█░░░░█░░█░░░░░░░█░░░░░░░█░░░░█░░█░░░█░░░█░░░░█░░█░░░██░░█░░░░░
+█░░░░█░░██████░░██████░░█░░░░█░░█░░░░█░░█████░░░░███░█░░██████"
+            )
+            .as_deref(),
+            Ok("XLZAKBGZ")
+        );
+        assert_eq!(
+            decode("#####\n#...#\n#...#\n#...#\n#####"),
+            Err(OcrError::Height(5))
+        );
+    }
+}